@@ -12,6 +12,8 @@ use std::sync::atomic::AtomicUsize;
 
 pub use self::types::{LlmProvider, UseCase, LlmAnalysis};
 use self::tools::{truncate_chars, parse_llm_json};
+use crate::content::job_text;
+use crate::structured::with_structured_data;
 
 /// Lista de keywords técnicas para el Fallback (Extracción de emergencia)
 const COMMON_TECH_KEYWORDS: &[&str] = &[
@@ -28,7 +30,7 @@ pub struct AnalyzerAgent {
     llm: LlmProvider,
     http: reqwest::Client,
     ws_tx: Option<broadcast::Sender<String>>,
-    max_html_chars: usize,
+    max_content_chars: usize,
     pub usage_count: AtomicUsize,
 }
 
@@ -38,7 +40,7 @@ impl AnalyzerAgent {
             llm: LlmProvider::OpenAI { api_key, base_url, model, use_case },
             http: reqwest::Client::builder().timeout(Duration::from_secs(90)).build().unwrap(),
             ws_tx: None, 
-            max_html_chars: 12_000,
+            max_content_chars: 12_000,
             usage_count: AtomicUsize::new(0),
         }
     }
@@ -48,7 +50,7 @@ impl AnalyzerAgent {
             llm: LlmProvider::Anthropic { api_key, base_url, model, use_case, version: "2023-06-01".into() },
             http: reqwest::Client::builder().timeout(Duration::from_secs(90)).build().unwrap(),
             ws_tx: None, 
-            max_html_chars: 12_000,
+            max_content_chars: 12_000,
             usage_count: AtomicUsize::new(0),
         }
     }
@@ -59,7 +61,7 @@ impl AnalyzerAgent {
             // Timeout largo para modelos locales lentos
             http: reqwest::Client::builder().timeout(Duration::from_secs(900)).build().unwrap(),
             ws_tx: None, 
-            max_html_chars: 4_000, 
+            max_content_chars: 4_000, 
             usage_count: AtomicUsize::new(0),
         }
     }
@@ -164,6 +166,9 @@ CV TEXT:
     }
 
     async fn analyze_job(&self, raw: &RawJobPosting, criteria: &SearchCriteria) -> Result<AnalyzedJobPosting, AgentError> {
+        // schema.org JobPosting: salario, fechas y empresa deterministas antes del LLM
+        let raw = &with_structured_data(raw.clone(), &raw.html_content);
+
         let use_recursive = match &self.llm {
             LlmProvider::OpenAI { use_case, .. } | LlmProvider::Anthropic { use_case, .. } => 
                 matches!(use_case, UseCase::Deep | UseCase::LongContext),
@@ -174,19 +179,55 @@ CV TEXT:
             return self.analyze_job_recursive(raw, criteria).await;
         }
 
-        let html_snip = truncate_chars(&raw.html_content, self.max_html_chars);
+        // Texto limpio (sin navegación, scripts ni CSS) para no gastar el presupuesto en boilerplate
         let cv = criteria.user_cv.as_deref().map(|s| truncate_chars(s, 3000)).unwrap_or_else(|| "No CV".to_string());
-        
-        let prompt = format!(
-            r#"Role: Recruiter.
-Task: Evaluate Candidate vs Job.
+        let facts = &raw.normalized;
+
+        let prompt = if facts.is_complete() {
+            // La fuente ya dio los datos factuales: el modelo solo puntúa
+            let description = facts.description.clone().unwrap_or_else(|| job_text(&raw.html_content));
+            format!(
+                r#"Role: Recruiter.
+Task: Score Candidate vs Job. The job facts are exact; do not extract or restate them.
 Output: Strict JSON.
 
 Candidate:
 {cv}
 
+Job facts:
+{facts}
+
+Job description:
+{job}
+
+JSON Structure:
+{{
+  "match_score": 0.0 to 1.0,
+  "match_reasons": ["reason1", "reason2"],
+  "red_flags": ["flag1"],
+  "skills_analysis": {{ "matching": ["skill1"], "missing": ["skill2"] }}
+}}"#,
+                cv = cv,
+                facts = job_facts(facts).join("\n"),
+                job = truncate_chars(&description, self.max_content_chars)
+            )
+        } else {
+            let known = job_facts(facts);
+            let known = if known.is_empty() {
+                String::new()
+            } else {
+                format!("\nKnown job fields (authoritative):\n{}\n", known.join("\n"))
+            };
+            format!(
+                r#"Role: Recruiter.
+Task: Evaluate Candidate vs Job.
+Output: Strict JSON.
+
+Candidate:
+{cv}
+{known}
 Job (Snippet):
-{html}
+{job}
 
 JSON Structure:
 {{
@@ -200,9 +241,11 @@ JSON Structure:
   "location": "City or Remote",
   "is_remote": true/false
 }}"#,
-            cv = cv,
-            html = html_snip
-        );
+                cv = cv,
+                known = known,
+                job = truncate_chars(&job_text(&raw.html_content), self.max_content_chars)
+            )
+        };
 
         self.emit_log("info", format!("🤖 [Lineal] Analizando: {}", raw.url));
         let text = self.call_llm(&prompt).await?;
@@ -220,6 +263,34 @@ JSON Structure:
     }
 }
 
+/// Datos ya conocidos de la oferta (board o schema.org) para que el modelo no
+/// los adivine; el JSON-LD no llega al texto limpio.
+fn job_facts(facts: &NormalizedPosting) -> Vec<String> {
+    let mut lines = Vec::new();
+    if let Some(title) = &facts.title {
+        lines.push(format!("- title: {}", title));
+    }
+    if let Some(company) = &facts.company {
+        lines.push(format!("- company: {}", company));
+    }
+    if let Some(location) = &facts.location {
+        lines.push(format!("- location: {}", location));
+    }
+    if let Some(remote) = facts.is_remote {
+        lines.push(format!("- remote: {}", remote));
+    }
+    if let Some(s) = &facts.salary {
+        lines.push(format!("- salary: {}-{} {} ({:?})", s.min, s.max, s.currency, s.period));
+    }
+    if let Some(job_type) = &facts.job_type {
+        lines.push(format!("- job type: {:?}", job_type));
+    }
+    if !facts.tags.is_empty() {
+        lines.push(format!("- tags: {}", facts.tags.join(", ")));
+    }
+    lines
+}
+
 #[async_trait]
 impl Agent for AnalyzerAgent {
     async fn process(&self, msg: AgentMessage) -> Result<AgentMessage, AgentError> {
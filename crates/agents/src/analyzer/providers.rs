@@ -1,6 +1,9 @@
 use super::types::{LlmProvider, UseCase};
 use super::AnalyzerAgent;
+use crate::http::{HttpTape, TapeMode};
 use job_hunter_core::AgentError;
+use reqwest::header::CONTENT_TYPE;
+use reqwest::StatusCode;
 use serde_json::Value;
 use std::sync::atomic::Ordering;
 
@@ -19,6 +22,33 @@ impl AnalyzerAgent {
         Ok(())
     }
 
+    /// POST JSON al proveedor. Pasa por la grabadora HTTP: en `replay` no
+    /// toca la red y en `record` guarda la respuesta (sin cabeceras de petición).
+    async fn post_json(&self, url: &str, headers: &[(&str, &str)], body: &Value) -> Result<(StatusCode, String), AgentError> {
+        let payload = body.to_string();
+        let tape = HttpTape::current();
+        if let Some(tape) = tape.as_deref().filter(|t| t.mode() == TapeMode::Replay) {
+            let rec = tape
+                .replay("POST", url, Some(&payload))
+                .ok_or_else(|| AgentError::Llm(tape.miss_message("POST", url)))?;
+            return Ok((StatusCode::from_u16(rec.status).unwrap_or(StatusCode::OK), rec.body));
+        }
+
+        let mut req = self.http.post(url).header(CONTENT_TYPE, "application/json").body(payload.clone());
+        for (k, v) in headers {
+            req = req.header(*k, *v);
+        }
+        let res = req.send().await.map_err(|e| AgentError::Llm(e.to_string()))?;
+        let status = res.status();
+        let res_headers = res.headers().clone();
+        let txt = res.text().await.unwrap_or_default();
+
+        if let Some(tape) = tape.as_deref().filter(|t| t.mode() == TapeMode::Record) {
+            tape.record("POST", url, Some(&payload), status.as_u16(), &res_headers, &txt);
+        }
+        Ok((status, txt))
+    }
+
     /// Router centralizado para llamadas LLM
     pub(crate) async fn call_llm(&self, prompt: &str) -> Result<String, AgentError> {
         match &self.llm {
@@ -61,11 +91,7 @@ impl AnalyzerAgent {
             "format": "json"
         });
 
-        let res = self.http.post(&url)
-            .json(&body)
-            .send().await.map_err(|e| AgentError::Llm(e.to_string()))?;
-        
-        let txt = res.text().await.unwrap_or_default();
+        let (_, txt) = self.post_json(&url, &[], &body).await?;
         
         // Intentar parsear la respuesta
         let v: Value = serde_json::from_str(&txt).map_err(|_| AgentError::Llm("Ollama devolvió JSON inválido".into()))?;
@@ -106,11 +132,8 @@ impl AnalyzerAgent {
             "response_format": { "type": "json_object" } 
         });
 
-        let res = self.http.post(&url).bearer_auth(key).json(&body).send().await
-            .map_err(|e| AgentError::Llm(e.to_string()))?;
-        
-        let status = res.status();
-        let txt = res.text().await.unwrap_or_default();
+        let auth = format!("Bearer {}", key);
+        let (status, txt) = self.post_json(&url, &[("Authorization", &auth)], &body).await?;
         
         if !status.is_success() {
              return Err(AgentError::Llm(format!("OpenAI API Error (HTTP {}): {}", status, txt)));
@@ -151,12 +174,9 @@ impl AnalyzerAgent {
             ]
         });
 
-        let res = self.http.post(&url)
-            .header("x-api-key", key).header("anthropic-version", ver)
-            .json(&body).send().await.map_err(|e| AgentError::Llm(e.to_string()))?;
-
-        let status = res.status();
-        let txt = res.text().await.unwrap_or_default();
+        let (status, txt) = self
+            .post_json(&url, &[("x-api-key", key), ("anthropic-version", ver)], &body)
+            .await?;
         
         if !status.is_success() {
              return Err(AgentError::Llm(format!("Anthropic API Error (HTTP {}): {}", status, txt)));
@@ -1,6 +1,7 @@
 use super::types::{LlmAnalysis};
 use super::tools::{find_snippets, parse_llm_json};
 use super::AnalyzerAgent;
+use crate::content::job_text;
 use job_hunter_core::{AgentError, AnalyzedJobPosting, RawJobPosting, SearchCriteria};
 use serde::Deserialize;
 use serde_json::Value;
@@ -20,7 +21,8 @@ impl AnalyzerAgent {
         criteria: &SearchCriteria,
     ) -> Result<AnalyzedJobPosting, AgentError> {
         
-        let doc_text = &raw.html_content;
+        // Texto limpio por líneas: `find_snippets` devuelve frases, no markup
+        let doc_text = &job_text(&raw.html_content);
         let cv_text = criteria.user_cv.as_deref().unwrap_or("");
         
         // Estado del Grafo de Conocimiento (Knowledge Graph en texto)
@@ -105,7 +107,7 @@ RESPONDE SOLO JSON:
             context
         );
         let txt = self.call_llm(&final_prompt).await?;
-        let json = parse_llm_json(&txt).map_err(|e| AgentError::Analysis(e))?;
+        let json = parse_llm_json(&txt).map_err(AgentError::Analysis)?;
         let analysis: LlmAnalysis = serde_json::from_value(json).map_err(|e| AgentError::Analysis(e.to_string()))?;
         
         let res = analysis.into_analyzed(raw, criteria);
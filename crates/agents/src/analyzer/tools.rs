@@ -151,7 +151,7 @@ fn try_close_json(s: &str) -> String {
     let mut out = s.to_string();
     
     // Balance básico de comillas (si es impar, falta una al final)
-    if out.matches('"').count() % 2 != 0 {
+    if !out.matches('"').count().is_multiple_of(2) {
         out.push('"');
     }
     
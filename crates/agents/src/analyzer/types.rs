@@ -1,6 +1,11 @@
 use job_hunter_core::*;
 use serde::{Deserialize, Serialize};
 
+use crate::salary::parse_salary_text;
+
+/// Longitud máxima del resumen tomado de la descripción del board.
+const SUMMARY_CHARS: usize = 400;
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum UseCase {
     Fast,
@@ -10,6 +15,8 @@ pub enum UseCase {
 }
 
 impl UseCase {
+    // Parser tolerante (nunca falla), no es un `FromStr`
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Self {
         match s.to_lowercase().as_str() {
             "fast" | "rapido" | "rápido" => Self::Fast,
@@ -55,7 +62,6 @@ pub struct LlmAnalysis {
     pub company: Option<CompanyInfo>,
     pub company_name: Option<String>,
     pub description: Option<String>,
-    pub salary_normalized: Option<f64>,
     pub red_flags: Option<Vec<String>>,
     pub skills_analysis: Option<TolerantSkillsGap>,
     pub requirements: Option<Vec<String>>,
@@ -71,7 +77,9 @@ pub struct LlmAnalysis {
 
 impl LlmAnalysis {
     pub fn into_analyzed(self, raw: &RawJobPosting, criteria: &SearchCriteria) -> AnalyzedJobPosting {
-        let company = match (self.company, self.company_name) {
+        // Los datos normalizados de la fuente son exactos: mandan sobre el modelo
+        let facts = &raw.normalized;
+        let mut company = match (self.company, self.company_name) {
             (Some(c), _) => Some(c),
             (None, Some(name)) if !name.trim().is_empty() => Some(CompanyInfo {
                 name,
@@ -83,8 +91,28 @@ impl LlmAnalysis {
             }),
             _ => None,
         };
+        // Empresa del board o de schema.org: manda sobre la del modelo
+        if let Some(name) = facts.company.clone() {
+            match company.as_mut() {
+                Some(c) => c.name = name,
+                None => {
+                    company = Some(CompanyInfo {
+                        name,
+                        description: None,
+                        industry: None,
+                        size: None,
+                        website: None,
+                        linkedin_url: None,
+                    })
+                }
+            }
+        }
 
-        let job_type = parse_job_type(self.job_type.as_deref()).unwrap_or(JobType::FullTime);
+        let job_type = facts
+            .job_type
+            .clone()
+            .or_else(|| self.job_type.as_deref().and_then(JobType::from_label))
+            .unwrap_or(JobType::FullTime);
         let exp = parse_experience(self.experience_level.as_deref())
             .unwrap_or(criteria.experience_level.clone());
 
@@ -95,40 +123,66 @@ impl LlmAnalysis {
             missing: t.missing,
         }).unwrap_or_default();
 
+        // Salario determinista: campos nativos del board o texto de la oferta
+        let salary_range = facts
+            .salary
+            .clone()
+            .or_else(|| parse_salary_text(&raw.html_content));
+        let salary_normalized = salary_range
+            .as_ref()
+            .and_then(|r| r.normalized_yearly(&criteria.target_currency));
+
         AnalyzedJobPosting {
             id: raw.id.clone(),
-            title: self.title.unwrap_or_else(|| "(sin título)".to_string()),
+            title: facts
+                .title
+                .clone()
+                .or_else(|| self.title.filter(|t| !t.trim().is_empty()))
+                .unwrap_or_else(|| "(sin título)".to_string()),
             company,
-            description: self.description.unwrap_or_else(|| "".to_string()),
-            salary_normalized: self.salary_normalized,
+            description: self
+                .description
+                .filter(|d| !d.trim().is_empty())
+                .or_else(|| facts.description.as_deref().map(summary))
+                .unwrap_or_default(),
+            salary_normalized,
             red_flags: self.red_flags.unwrap_or_default(),
             skills_analysis,
             requirements: self.requirements.unwrap_or_default(),
             responsibilities: self.responsibilities.unwrap_or_default(),
-            skills: self.skills.unwrap_or_default(),
-            salary_range: None,
-            location: self.location.unwrap_or_else(|| "Remote".to_string()),
-            is_remote: self.is_remote.unwrap_or(true),
+            skills: self.skills.filter(|s| !s.is_empty()).unwrap_or_else(|| facts.tags.clone()),
+            salary_range,
+            location: facts
+                .location
+                .clone()
+                .or(self.location)
+                .unwrap_or_else(|| "Remote".to_string()),
+            is_remote: facts.is_remote.or(self.is_remote).unwrap_or(true),
             job_type,
             experience_level: exp,
             url: raw.url.clone(),
-            posted_date: None,
+            posted_date: facts.posted_at,
+            valid_through: facts.valid_through,
             match_score,
             match_reasons: self.match_reasons.unwrap_or_default(),
+            sources: vec![raw.sighting()],
         }
     }
 }
 
-fn parse_job_type(s: Option<&str>) -> Option<JobType> {
-    let v = s?.trim().to_lowercase();
-    match v.as_str() {
-        "fulltime" | "full_time" | "full-time" => Some(JobType::FullTime),
-        "parttime" | "part_time" | "part-time" => Some(JobType::PartTime),
-        "contract" => Some(JobType::Contract),
-        "freelance" => Some(JobType::Freelance),
-        "internship" => Some(JobType::Internship),
-        _ => None,
+/// Resumen de la descripción del board: su primer párrafo, recortado.
+fn summary(description: &str) -> String {
+    let first = description
+        .split("\n\n")
+        .map(str::trim)
+        .find(|p| !p.is_empty() && !p.starts_with('#'))
+        .unwrap_or_default();
+    if first.chars().count() <= SUMMARY_CHARS {
+        return first.to_string();
     }
+    let mut out: String = first.chars().take(SUMMARY_CHARS).collect();
+    out.push('…');
+    out
 }
 
 fn parse_experience(s: Option<&str>) -> Option<ExperienceLevel> {
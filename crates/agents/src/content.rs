@@ -0,0 +1,309 @@
+//! Texto limpio de una oferta para el LLM: el contenido de `RawJobPosting`
+//! puede ser una página HTML completa, un `<item>` RSS/Atom o el JSON de una
+//! API. Se quita el boilerplate (scripts, estilos, navegación, pies), se
+//! localiza el cuerpo de la oferta y se convierte a texto tipo markdown que
+//! conserva títulos y listas; los JSON se aplanan en campos etiquetados.
+
+use scraper::{ElementRef, Html, Node, Selector};
+use serde_json::Value;
+
+use crate::feed::parse_feed;
+
+/// Texto mínimo para aceptar un candidato como cuerpo de la oferta.
+const MIN_BODY_CHARS: usize = 200;
+
+/// Contenedores habituales del cuerpo de una oferta, del más específico al
+/// más genérico (schema.org, WWR, Python.org, Remote.co, WordPress).
+const BODY_SELECTORS: &[&str] = &[
+    "[itemprop='description']",
+    ".lis-container__job__content__description",
+    "#job-listing-show-container",
+    ".job-description",
+    ".job_description",
+    "#job-description",
+    ".job-details",
+    ".job-content",
+    ".entry-content",
+    "article",
+    "[role='main']",
+    "main",
+];
+
+/// Elementos que nunca forman parte de la oferta.
+const SKIP_TAGS: &[&str] = &[
+    "script", "style", "noscript", "template", "svg", "iframe", "nav", "header", "footer", "aside", "form",
+    "button", "select", "dialog", "head",
+];
+
+const BLOCK_TAGS: &[&str] = &[
+    "p", "div", "section", "article", "main", "ul", "ol", "dl", "dt", "dd", "table", "tr", "blockquote", "pre",
+    "figure", "figcaption", "address", "details", "summary",
+];
+
+/// Claves JSON sin valor para el análisis (imágenes, slugs).
+const SKIP_JSON_KEYS: &[&str] = &["logo", "company_logo", "company_logo_url", "companyLogo", "image", "thumbnail", "icon", "slug"];
+
+/// Valores JSON a partir de los que el campo va en bloque, al final.
+const LONG_FIELD_CHARS: usize = 160;
+const MAX_JSON_DEPTH: usize = 4;
+
+/// Texto limpio de `content` (HTML, item RSS/Atom o JSON).
+pub fn job_text(content: &str) -> String {
+    let trimmed = content.trim_start();
+    if trimmed.starts_with('{') || trimmed.starts_with('[') {
+        if let Ok(json) = serde_json::from_str::<Value>(trimmed) {
+            return json_text(&json);
+        }
+    }
+    if trimmed.starts_with("<item") || trimmed.starts_with("<entry") {
+        if let Some(item) = parse_feed(trimmed).into_iter().next() {
+            let mut lines = Vec::new();
+            if let Some(title) = &item.title {
+                lines.push(format!("# {}", title.trim()));
+            }
+            if let Some(author) = &item.author {
+                lines.push(format!("Author: {}", author.trim()));
+            }
+            if let Some(date) = item.pub_date {
+                lines.push(format!("Published: {}", date.format("%Y-%m-%d")));
+            }
+            if !item.categories.is_empty() {
+                lines.push(format!("Categories: {}", item.categories.join(", ")));
+            }
+            if let Some(body) = item.body() {
+                lines.push(String::new());
+                lines.push(html_text(body));
+            }
+            return lines.join("\n").trim().to_string();
+        }
+    }
+    html_text(content)
+}
+
+/// Texto tipo markdown del cuerpo principal de un documento o fragmento HTML.
+pub fn html_text(html: &str) -> String {
+    let doc = Html::parse_document(html);
+    let mut out = String::new();
+    match main_element(&doc) {
+        Some(main) => {
+            // El título suele quedar fuera del contenedor de la descripción
+            let h1 = Selector::parse("h1").ok().and_then(|s| doc.select(&s).next());
+            if let Some(h1) = h1.filter(|h| !h.ancestors().any(|a| a.id() == main.id())) {
+                out.push_str("# ");
+                render_children(h1, &mut out, 0);
+                out.push_str("\n\n");
+            }
+            render_children(main, &mut out, 0);
+        }
+        None => render_children(doc.root_element(), &mut out, 0),
+    }
+    tidy(&out)
+}
+
+/// Texto plano de un campo que puede venir en HTML (descripciones de APIs y feeds).
+pub(crate) fn plain_text(text: &str) -> String {
+    if text.contains('<') && text.contains('>') {
+        html_text(text)
+    } else {
+        text.trim().to_string()
+    }
+}
+
+/// Primer contenedor conocido con texto suficiente.
+pub(crate) fn main_element(doc: &Html) -> Option<ElementRef<'_>> {
+    BODY_SELECTORS.iter().find_map(|css| {
+        let sel = Selector::parse(css).ok()?;
+        doc.select(&sel).find(|el| text_len(el) >= MIN_BODY_CHARS)
+    })
+}
+
+fn text_len(el: &ElementRef) -> usize {
+    el.text().flat_map(str::split_whitespace).map(|w| w.chars().count() + 1).sum()
+}
+
+fn render_children(el: ElementRef, out: &mut String, depth: usize) {
+    let mut ordinal = 0;
+    for child in el.children() {
+        match child.value() {
+            Node::Text(text) => push_inline(out, text),
+            Node::Element(_) => {
+                if let Some(child) = ElementRef::wrap(child) {
+                    if child.value().name() == "li" {
+                        ordinal += 1;
+                    }
+                    render_element(child, out, depth, ordinal);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn render_element(el: ElementRef, out: &mut String, depth: usize, ordinal: usize) {
+    let name = el.value().name();
+    if SKIP_TAGS.contains(&name) || el.value().attr("hidden").is_some() || el.value().attr("aria-hidden") == Some("true") {
+        return;
+    }
+    match name {
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+            let level = name[1..].parse::<usize>().unwrap_or(2);
+            out.push_str("\n\n");
+            out.push_str(&"#".repeat(level));
+            out.push(' ');
+            render_children(el, out, depth);
+            out.push_str("\n\n");
+        }
+        "li" => {
+            let ordered = el
+                .parent()
+                .and_then(ElementRef::wrap)
+                .is_some_and(|p| p.value().name() == "ol");
+            if !out.ends_with('\n') {
+                out.push('\n');
+            }
+            out.push_str(&"  ".repeat(depth.saturating_sub(1)));
+            if ordered {
+                out.push_str(&format!("{}. ", ordinal));
+            } else {
+                out.push_str("- ");
+            }
+            // `<li><p>…</p></li>`: el texto va en la misma línea que la viñeta
+            let mut inner = String::new();
+            render_children(el, &mut inner, depth);
+            out.push_str(inner.trim());
+            out.push('\n');
+        }
+        "ul" | "ol" => {
+            out.push('\n');
+            render_children(el, out, depth + 1);
+            out.push('\n');
+        }
+        "br" => out.push('\n'),
+        "hr" => out.push_str("\n\n---\n\n"),
+        "td" | "th" => {
+            render_children(el, out, depth);
+            out.push_str(" | ");
+        }
+        "img" => {
+            if let Some(alt) = el.value().attr("alt").filter(|a| !a.trim().is_empty()) {
+                push_inline(out, alt);
+            }
+        }
+        _ if BLOCK_TAGS.contains(&name) => {
+            out.push_str("\n\n");
+            render_children(el, out, depth);
+            out.push_str("\n\n");
+        }
+        _ => render_children(el, out, depth),
+    }
+}
+
+/// Texto en línea con los espacios colapsados.
+fn push_inline(out: &mut String, text: &str) {
+    if text.trim().is_empty() {
+        if !text.is_empty() && !out.ends_with([' ', '\n']) && !out.is_empty() {
+            out.push(' ');
+        }
+        return;
+    }
+    if text.starts_with(char::is_whitespace) && !out.ends_with([' ', '\n']) && !out.is_empty() {
+        out.push(' ');
+    }
+    out.push_str(&text.split_whitespace().collect::<Vec<_>>().join(" "));
+    if text.ends_with(char::is_whitespace) {
+        out.push(' ');
+    }
+}
+
+/// Líneas recortadas, sin separadores de celda colgando y como mucho una
+/// línea en blanco seguida.
+fn tidy(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut blank = true;
+    for line in text.lines() {
+        let line = line.trim_end().trim_end_matches(" |").trim_end();
+        let content = line.trim_start();
+        if content.is_empty() || content == "-" {
+            if !blank {
+                out.push('\n');
+                blank = true;
+            }
+            continue;
+        }
+        // Conserva la sangría de las listas anidadas
+        if content.starts_with("- ") || content.split_once(". ").is_some_and(|(n, _)| n.parse::<u32>().is_ok()) {
+            out.push_str(line);
+        } else {
+            out.push_str(content);
+        }
+        out.push('\n');
+        blank = false;
+    }
+    out.trim().to_string()
+}
+
+/// Aplana un JSON en `campo: valor`; los valores largos (descripciones, a
+/// menudo HTML) van al final como bloques con su etiqueta.
+fn json_text(json: &Value) -> String {
+    let mut short = Vec::new();
+    let mut long = Vec::new();
+    match json {
+        Value::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                flatten_json(&format!("[{}]", i), item, 0, &mut short, &mut long);
+            }
+        }
+        _ => flatten_json("", json, 0, &mut short, &mut long),
+    }
+    let mut out = short.join("\n");
+    for (label, text) in long {
+        out.push_str(&format!("\n\n## {}\n{}", label, text));
+    }
+    out.trim().to_string()
+}
+
+fn flatten_json(label: &str, value: &Value, depth: usize, short: &mut Vec<String>, long: &mut Vec<(String, String)>) {
+    match value {
+        Value::Object(map) if depth < MAX_JSON_DEPTH => {
+            for (key, v) in map {
+                if SKIP_JSON_KEYS.contains(&key.as_str()) {
+                    continue;
+                }
+                let label = if label.is_empty() { key.clone() } else { format!("{}.{}", label, key) };
+                flatten_json(&label, v, depth + 1, short, long);
+            }
+        }
+        Value::Array(items) if items.iter().all(|v| !v.is_object() && !v.is_array()) => {
+            let joined: Vec<String> = items.iter().filter_map(scalar_text).collect();
+            if !joined.is_empty() {
+                short.push(format!("{}: {}", label, joined.join(", ")));
+            }
+        }
+        Value::Array(items) if depth < MAX_JSON_DEPTH => {
+            for (i, v) in items.iter().enumerate() {
+                flatten_json(&format!("{}[{}]", label, i), v, depth + 1, short, long);
+            }
+        }
+        Value::String(s) if s.contains('<') && s.contains('>') => {
+            let text = html_text(s);
+            if !text.is_empty() {
+                long.push((label.to_string(), text));
+            }
+        }
+        Value::String(s) if s.chars().count() > LONG_FIELD_CHARS => long.push((label.to_string(), s.trim().to_string())),
+        _ => {
+            if let Some(text) = scalar_text(value) {
+                short.push(format!("{}: {}", label, text));
+            }
+        }
+    }
+}
+
+fn scalar_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.trim().to_string()).filter(|s| !s.is_empty()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
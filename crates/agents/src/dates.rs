@@ -0,0 +1,77 @@
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use serde_json::Value;
+
+/// Campos de fecha de publicación en las APIs de los boards
+/// (RemoteOK `epoch`/`date`, Arbeitnow `created_at`, Jobicy `pubDate`, ...).
+const DATE_KEYS: &[&str] = &[
+    "epoch",
+    "date",
+    "created_at",
+    "pubDate",
+    "publication_date",
+    "published_at",
+    "posted_at",
+    "datePosted",
+];
+
+/// Epoch a partir del cual interpretamos el número como milisegundos.
+const EPOCH_MILLIS_THRESHOLD: i64 = 100_000_000_000;
+/// 2000-01-01T00:00:00Z: un epoch anterior no es una fecha de publicación
+/// sino otro número ("2024", un id...).
+const MIN_EPOCH_SECS: i64 = 946_684_800;
+
+/// Fecha de publicación de un item JSON del board, en UTC.
+pub fn posted_at_from_json(item: &Value) -> Option<DateTime<Utc>> {
+    DATE_KEYS.iter().find_map(|k| date_from_value(item.get(*k)?))
+}
+
+/// Fecha a partir de un valor JSON: epoch numérico o texto en cualquier
+/// formato admitido por [`parse_posted_date`].
+pub fn date_from_value(value: &Value) -> Option<DateTime<Utc>> {
+    match value {
+        Value::Number(n) => n.as_i64().and_then(from_epoch),
+        Value::String(s) => parse_posted_date(s),
+        _ => None,
+    }
+}
+
+/// Parsea una fecha de publicación en los formatos habituales de feeds y APIs:
+/// RFC 2822 (`<pubDate>` RSS), RFC 3339/ISO 8601, `YYYY-MM-DD HH:MM:SS`,
+/// `YYYY-MM-DD` o un epoch en segundos/milisegundos (desde el año 2000).
+pub fn parse_posted_date(raw: &str) -> Option<DateTime<Utc>> {
+    let s = raw.trim();
+    if s.is_empty() {
+        return None;
+    }
+    if let Ok(n) = s.parse::<i64>() {
+        return from_epoch(n);
+    }
+    if let Ok(d) = DateTime::parse_from_rfc3339(s) {
+        return Some(d.with_timezone(&Utc));
+    }
+    if let Ok(d) = DateTime::parse_from_rfc2822(s) {
+        return Some(d.with_timezone(&Utc));
+    }
+    // Sin zona horaria: asumimos UTC
+    for fmt in ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%d %H:%M"] {
+        if let Ok(d) = NaiveDateTime::parse_from_str(s, fmt) {
+            return Some(Utc.from_utc_datetime(&d));
+        }
+    }
+    NaiveDate::parse_from_str(s.get(..10)?, "%Y-%m-%d")
+        .ok()
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .map(|d| Utc.from_utc_datetime(&d))
+}
+
+fn from_epoch(n: i64) -> Option<DateTime<Utc>> {
+    let secs = if n >= EPOCH_MILLIS_THRESHOLD { n / 1000 } else { n };
+    if secs < MIN_EPOCH_SECS {
+        return None;
+    }
+    if n >= EPOCH_MILLIS_THRESHOLD {
+        DateTime::from_timestamp_millis(n)
+    } else {
+        DateTime::from_timestamp(n, 0)
+    }
+}
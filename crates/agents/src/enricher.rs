@@ -15,6 +15,12 @@ impl EnricherAgent {
     }
 }
 
+impl Default for EnricherAgent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[async_trait]
 impl Agent for EnricherAgent {
     async fn process(&self, msg: AgentMessage) -> Result<AgentMessage, AgentError> {
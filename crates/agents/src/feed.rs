@@ -0,0 +1,353 @@
+use chrono::{DateTime, Utc};
+
+use crate::dates::parse_posted_date;
+
+/// Entrada de un feed RSS 2.0 o Atom, con los campos que usan los scrapers.
+#[derive(Debug, Clone, Default)]
+pub struct FeedItem {
+    pub title: Option<String>,
+    pub link: Option<String>,
+    /// `<guid>` (RSS) o `<id>` (Atom).
+    pub guid: Option<String>,
+    /// `<pubDate>`, `<published>`, `<updated>` o `<dc:date>`.
+    pub pub_date: Option<DateTime<Utc>>,
+    pub categories: Vec<String>,
+    /// `<description>` (RSS) o `<summary>` (Atom), HTML ya desescapado.
+    pub description: Option<String>,
+    /// `<content:encoded>` (RSS) o `<content>` (Atom).
+    pub content: Option<String>,
+    /// `<dc:creator>`, `<author>` o `<author><name>`.
+    pub author: Option<String>,
+    /// Fragmento XML original del item.
+    pub raw: String,
+}
+
+impl FeedItem {
+    /// Cuerpo más completo disponible: contenido completo o, si no, descripción.
+    pub fn body(&self) -> Option<&str> {
+        self.content.as_deref().or(self.description.as_deref())
+    }
+}
+
+/// Extrae los `<item>` (RSS) o `<entry>` (Atom) de un documento. Parser
+/// tolerante: ignora prólogo, comentarios y namespaces desconocidos, respeta
+/// CDATA y decodifica entidades; un XML mal cerrado devuelve lo leído hasta ahí.
+pub fn parse_feed(xml: &str) -> Vec<FeedItem> {
+    let mut items = Vec::new();
+    let mut current: Option<OpenItem> = None;
+    // Elementos abiertos dentro del item actual
+    let mut stack: Vec<OpenElement> = Vec::new();
+
+    for token in Tokenizer::new(xml) {
+        match token {
+            Token::Start { name, attrs, self_closing, start, end } => {
+                let local = local_name(&name);
+                if current.is_none() {
+                    if (local == "item" || local == "entry") && !self_closing {
+                        current = Some(OpenItem { name, start, item: FeedItem::default() });
+                        stack.clear();
+                    }
+                    continue;
+                }
+                if let Some(parent) = stack.last_mut() {
+                    parent.has_children = true;
+                }
+                let el = OpenElement { name, attrs, inner_start: end, text: String::new(), has_children: false };
+                if self_closing {
+                    let depth = stack.len();
+                    if let Some(open) = current.as_mut() {
+                        apply_element(&mut open.item, &stack, depth, el, "");
+                    }
+                } else {
+                    stack.push(el);
+                }
+            }
+            Token::End { name, start, end } => {
+                let Some(open) = current.as_mut() else { continue };
+                if stack.is_empty() {
+                    if name == open.name {
+                        let mut done = current.take().expect("item abierto");
+                        done.item.raw = xml[done.start..end].to_string();
+                        items.push(done.item);
+                    }
+                    continue;
+                }
+                // Cierra hasta el elemento correspondiente (tolera etiquetas sin cerrar)
+                let Some(pos) = stack.iter().rposition(|e| e.name == name) else { continue };
+                while stack.len() > pos + 1 {
+                    stack.pop();
+                }
+                let el = stack.pop().expect("elemento abierto");
+                let depth = stack.len();
+                let inner = if el.has_children { xml[el.inner_start..start].trim() } else { "" };
+                apply_element(&mut open.item, &stack, depth, el, inner);
+            }
+            Token::Text(text) => {
+                if let Some(el) = stack.last_mut() {
+                    el.text.push_str(&text);
+                }
+            }
+        }
+    }
+
+    items
+}
+
+struct OpenItem {
+    name: String,
+    start: usize,
+    item: FeedItem,
+}
+
+struct OpenElement {
+    name: String,
+    attrs: Vec<(String, String)>,
+    inner_start: usize,
+    text: String,
+    has_children: bool,
+}
+
+impl OpenElement {
+    fn attr(&self, key: &str) -> Option<&str> {
+        self.attrs.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str())
+    }
+
+    /// Texto del elemento o, si contiene marcado (XHTML en Atom), el fragmento.
+    fn value(&self, inner: &str) -> Option<String> {
+        let v = if self.has_children && !inner.is_empty() { inner } else { self.text.trim() };
+        Some(v.to_string()).filter(|s| !s.is_empty())
+    }
+}
+
+/// Vuelca un elemento cerrado en el item. `depth` es su profundidad dentro del
+/// item (0 = hijo directo).
+fn apply_element(item: &mut FeedItem, stack: &[OpenElement], depth: usize, el: OpenElement, inner: &str) {
+    // <author><name>..</name></author> en Atom
+    if depth == 1 && local_name(&el.name) == "name" && stack.last().is_some_and(|p| local_name(&p.name) == "author") {
+        if item.author.is_none() {
+            item.author = el.value(inner);
+        }
+        return;
+    }
+    if depth != 0 {
+        return;
+    }
+
+    match el.name.as_str() {
+        "content:encoded" => item.content = el.value(inner).or(item.content.take()),
+        "dc:creator" => item.author = item.author.take().or(el.value(inner)),
+        "dc:date" => item.pub_date = item.pub_date.or_else(|| el.value(inner).as_deref().and_then(parse_posted_date)),
+        _ => match local_name(&el.name) {
+            "title" => item.title = el.value(inner),
+            "link" => {
+                // Atom: <link rel="alternate" href="..."/>; RSS: <link>url</link>
+                let rel = el.attr("rel").unwrap_or("alternate");
+                let href = el.attr("href").map(String::from).or_else(|| el.value(inner));
+                if rel == "alternate" && item.link.is_none() {
+                    item.link = href;
+                }
+            }
+            "guid" | "id" => item.guid = el.value(inner),
+            "pubDate" | "published" => {
+                item.pub_date = el.value(inner).as_deref().and_then(parse_posted_date).or(item.pub_date);
+            }
+            "updated" => {
+                item.pub_date = item.pub_date.or_else(|| el.value(inner).as_deref().and_then(parse_posted_date));
+            }
+            "category" => {
+                if let Some(c) = el.attr("term").map(String::from).or_else(|| el.value(inner)) {
+                    item.categories.push(c);
+                }
+            }
+            "description" | "summary" => item.description = el.value(inner),
+            "content" => item.content = item.content.take().or(el.value(inner)),
+            "author" | "creator" if item.author.is_none() && !el.has_children => {
+                item.author = el.value(inner);
+            }
+            _ => {}
+        },
+    }
+}
+
+fn local_name(name: &str) -> &str {
+    name.rsplit(':').next().unwrap_or(name)
+}
+
+enum Token {
+    Start { name: String, attrs: Vec<(String, String)>, self_closing: bool, start: usize, end: usize },
+    End { name: String, start: usize, end: usize },
+    Text(String),
+}
+
+/// Tokenizador XML mínimo sobre el texto completo.
+struct Tokenizer<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> Tokenizer<'a> {
+    fn new(src: &'a str) -> Self {
+        Self { src, pos: 0 }
+    }
+
+    fn skip_past(&mut self, pat: &str) {
+        self.pos = match self.src[self.pos..].find(pat) {
+            Some(i) => self.pos + i + pat.len(),
+            None => self.src.len(),
+        };
+    }
+}
+
+impl Iterator for Tokenizer<'_> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Token> {
+        loop {
+            let rest = &self.src[self.pos..];
+            if rest.is_empty() {
+                return None;
+            }
+
+            if !rest.starts_with('<') {
+                let len = rest.find('<').unwrap_or(rest.len());
+                self.pos += len;
+                return Some(Token::Text(decode_entities(&rest[..len])));
+            }
+
+            if let Some(cdata) = rest.strip_prefix("<![CDATA[") {
+                let len = cdata.find("]]>").unwrap_or(cdata.len());
+                let text = cdata[..len].to_string();
+                self.pos += "<![CDATA[".len() + len;
+                self.skip_past("]]>");
+                return Some(Token::Text(text));
+            }
+            if rest.starts_with("<!--") {
+                self.skip_past("-->");
+                continue;
+            }
+            if rest.starts_with("<?") {
+                self.skip_past("?>");
+                continue;
+            }
+            if rest.starts_with("<!") {
+                self.skip_past(">");
+                continue;
+            }
+
+            let start = self.pos;
+            let Some(close) = find_tag_end(rest) else {
+                self.pos = self.src.len();
+                return None;
+            };
+            self.pos += close + 1;
+            let inner = &rest[1..close];
+
+            if let Some(name) = inner.strip_prefix('/') {
+                return Some(Token::End { name: name.trim().to_string(), start, end: self.pos });
+            }
+
+            let self_closing = inner.ends_with('/');
+            let inner = inner.trim_end_matches('/');
+            let name_len = inner.find(|c: char| c.is_whitespace()).unwrap_or(inner.len());
+            let name = inner[..name_len].to_string();
+            if name.is_empty() {
+                continue;
+            }
+            return Some(Token::Start {
+                name,
+                attrs: parse_attrs(&inner[name_len..]),
+                self_closing,
+                start,
+                end: self.pos,
+            });
+        }
+    }
+}
+
+/// Posición del `>` que cierra la etiqueta, ignorando los que van entre comillas.
+fn find_tag_end(tag: &str) -> Option<usize> {
+    let mut quote: Option<char> = None;
+    for (i, c) in tag.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '>') => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn parse_attrs(s: &str) -> Vec<(String, String)> {
+    let mut attrs = Vec::new();
+    let mut rest = s.trim_start();
+    while let Some(eq) = rest.find('=') {
+        let key = rest[..eq].trim().to_string();
+        let after = rest[eq + 1..].trim_start();
+        let Some(q) = after.chars().next().filter(|c| *c == '"' || *c == '\'') else { break };
+        let Some(end) = after[1..].find(q) else { break };
+        attrs.push((key, decode_entities(&after[1..1 + end])));
+        rest = after[end + 2..].trim_start();
+    }
+    attrs
+}
+
+/// Decodifica entidades XML, numéricas y las HTML más habituales en feeds.
+pub fn decode_entities(s: &str) -> String {
+    if !s.contains('&') {
+        return s.to_string();
+    }
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let decoded = rest
+            .find(';')
+            .filter(|end| *end <= 10)
+            .and_then(|end| entity(&rest[1..end]).map(|c| (c, end)));
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn entity(name: &str) -> Option<char> {
+    if let Some(num) = name.strip_prefix('#') {
+        let code = match num.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+            None => num.parse().ok()?,
+        };
+        return char::from_u32(code);
+    }
+    let c = match name {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => '\u{a0}',
+        "ndash" => '–',
+        "mdash" => '—',
+        "lsquo" => '‘',
+        "rsquo" => '’',
+        "ldquo" => '“',
+        "rdquo" => '”',
+        "hellip" => '…',
+        "bull" => '•',
+        "euro" => '€',
+        "pound" => '£',
+        "copy" => '©',
+        _ => return None,
+    };
+    Some(c)
+}
@@ -0,0 +1,257 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE, ETAG, LAST_MODIFIED};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use tracing::{info, warn};
+
+use super::client::HttpResponse;
+
+/// Directorio de la caché HTTP (por defecto `data/http_cache`). Vacío la desactiva.
+const KEY_CACHE_DIR: &str = "JOB_HUNTER_HTTP_CACHE_DIR";
+const DEFAULT_CACHE_DIR: &str = "data/http_cache";
+
+/// Entradas sin usar durante más de esto se borran al arrancar.
+const MAX_ENTRY_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Respuesta guardada en disco con sus validadores.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct CachedResponse {
+    url: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    content_type: Option<String>,
+    /// Última vez que el servidor confirmó el cuerpo (200 o 304), en segundos Unix.
+    stored_at: u64,
+    body: String,
+}
+
+impl CachedResponse {
+    pub(crate) fn age(&self) -> Duration {
+        Duration::from_secs(now_secs().saturating_sub(self.stored_at))
+    }
+
+    /// Cabeceras condicionales para revalidar la entrada.
+    pub(crate) fn validators(&self) -> Vec<(&'static str, String)> {
+        let mut headers = Vec::new();
+        if let Some(etag) = &self.etag {
+            headers.push(("If-None-Match", etag.clone()));
+        }
+        if let Some(lm) = &self.last_modified {
+            headers.push(("If-Modified-Since", lm.clone()));
+        }
+        headers
+    }
+
+    pub(crate) fn to_response(&self) -> HttpResponse {
+        let mut headers = HeaderMap::new();
+        let pairs = [(ETAG, &self.etag), (LAST_MODIFIED, &self.last_modified), (CONTENT_TYPE, &self.content_type)];
+        for (name, value) in pairs {
+            if let Some(v) = value.as_deref().and_then(|v| HeaderValue::from_str(v).ok()) {
+                headers.insert(name, v);
+            }
+        }
+        HttpResponse::new(self.url.clone(), StatusCode::OK, headers, self.body.clone())
+    }
+}
+
+/// Caché HTTP en disco de las descargas de los scrapers: un fichero JSON por
+/// petición (URL + cabeceras), con ETag/Last-Modified para revalidar.
+pub struct HttpCache {
+    dir: PathBuf,
+}
+
+impl HttpCache {
+    /// Caché del proceso, o `None` si está desactivada o el directorio no se puede crear.
+    pub fn global() -> Option<&'static HttpCache> {
+        static CACHE: OnceLock<Option<HttpCache>> = OnceLock::new();
+        CACHE
+            .get_or_init(|| {
+                let dir = std::env::var(KEY_CACHE_DIR).unwrap_or_else(|_| DEFAULT_CACHE_DIR.to_string());
+                if dir.trim().is_empty() {
+                    return None;
+                }
+                match HttpCache::open(Path::new(dir.trim())) {
+                    Ok(cache) => Some(cache),
+                    Err(e) => {
+                        warn!("⚠️ Caché HTTP desactivada ({}): {}", dir, e);
+                        None
+                    }
+                }
+            })
+            .as_ref()
+    }
+
+    /// Abre (o crea) la caché en `dir` y purga las entradas viejas.
+    pub fn open(dir: &Path) -> std::io::Result<Self> {
+        std::fs::create_dir_all(dir)?;
+        let cache = Self { dir: dir.to_path_buf() };
+        let purged = cache.purge(MAX_ENTRY_AGE);
+        if purged > 0 {
+            info!("💾 Caché HTTP: {} entradas antiguas eliminadas.", purged);
+        }
+        Ok(cache)
+    }
+
+    /// Clave de una petición: URL más las cabeceras que cambian la respuesta.
+    pub(crate) fn key(url: &str, headers: &[(String, String)]) -> String {
+        let mut sorted: Vec<String> = headers
+            .iter()
+            .map(|(k, v)| format!("{}: {}", k.to_ascii_lowercase(), v))
+            .collect();
+        sorted.sort();
+        let mut hasher = Sha1::new();
+        hasher.update(url.as_bytes());
+        for h in sorted {
+            hasher.update(b"\n");
+            hasher.update(h.as_bytes());
+        }
+        format!("{:x}", hasher.finalize())
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", key))
+    }
+
+    pub(crate) fn load(&self, key: &str) -> Option<CachedResponse> {
+        let content = std::fs::read_to_string(self.path(key)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// Guarda una respuesta 200 (con o sin validadores).
+    pub(crate) fn store(&self, key: &str, res: &HttpResponse) {
+        let header = |name: HeaderName| {
+            res.headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(String::from)
+        };
+        let entry = CachedResponse {
+            url: res.url().to_string(),
+            etag: header(ETAG),
+            last_modified: header(LAST_MODIFIED),
+            content_type: header(CONTENT_TYPE),
+            stored_at: now_secs(),
+            body: res.text().to_string(),
+        };
+        self.write(key, &entry);
+    }
+
+    /// Marca la entrada como recién validada (tras un 304).
+    pub(crate) fn touch(&self, key: &str, entry: &mut CachedResponse) {
+        entry.stored_at = now_secs();
+        self.write(key, entry);
+    }
+
+    fn write(&self, key: &str, entry: &CachedResponse) {
+        let path = self.path(key);
+        // Escritura atómica: fichero temporal propio de esta escritura + rename,
+        // para que dos peticiones (o dos procesos) con la misma clave no se pisen
+        static TMP_SEQ: AtomicU64 = AtomicU64::new(0);
+        let seq = TMP_SEQ.fetch_add(1, Ordering::Relaxed);
+        let tmp = path.with_extension(format!("{}.{}.tmp", std::process::id(), seq));
+        let result = serde_json::to_vec(entry)
+            .map_err(std::io::Error::other)
+            .and_then(|bytes| std::fs::write(&tmp, bytes))
+            .and_then(|_| std::fs::rename(&tmp, &path));
+        if let Err(e) = result {
+            warn!("⚠️ Caché HTTP: no se pudo guardar {}: {}", entry.url, e);
+        }
+    }
+
+    /// Borra las entradas no modificadas en `max_age`. Devuelve cuántas.
+    fn purge(&self, max_age: Duration) -> usize {
+        let Ok(entries) = std::fs::read_dir(&self.dir) else { return 0 };
+        let now = SystemTime::now();
+        entries
+            .flatten()
+            .filter(|e| {
+                e.metadata()
+                    .and_then(|m| m.modified())
+                    .ok()
+                    .and_then(|t| now.duration_since(t).ok())
+                    .is_some_and(|age| age > max_age)
+            })
+            .filter(|e| std::fs::remove_file(e.path()).is_ok())
+            .count()
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(body: &str) -> HttpResponse {
+        let mut headers = HeaderMap::new();
+        headers.insert(ETAG, HeaderValue::from_static("\"v1\""));
+        headers.insert(LAST_MODIFIED, HeaderValue::from_static("Tue, 02 Jul 2024 10:00:00 GMT"));
+        HttpResponse::new("https://example.com/jobs".into(), StatusCode::OK, headers, body.to_string())
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("job_hunter_cache_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn stores_validators_and_body() {
+        let dir = temp_dir("store");
+        let cache = HttpCache::open(&dir).unwrap();
+        let key = HttpCache::key("https://example.com/jobs", &[]);
+        cache.store(&key, &response("hola"));
+
+        let entry = cache.load(&key).expect("entrada");
+        assert_eq!(entry.to_response().text(), "hola");
+        assert!(entry.age() < Duration::from_secs(5));
+        assert_eq!(
+            entry.validators(),
+            [
+                ("If-None-Match", "\"v1\"".to_string()),
+                ("If-Modified-Since", "Tue, 02 Jul 2024 10:00:00 GMT".to_string())
+            ]
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    /// Escrituras simultáneas de la misma clave no se pisan el temporal.
+    #[test]
+    fn concurrent_writes_leave_a_valid_entry() {
+        let dir = temp_dir("concurrent");
+        let cache = HttpCache::open(&dir).unwrap();
+        let key = HttpCache::key("https://example.com/jobs", &[]);
+        std::thread::scope(|s| {
+            for i in 0..8 {
+                let (cache, key) = (&cache, &key);
+                s.spawn(move || {
+                    for _ in 0..20 {
+                        cache.store(key, &response(&format!("body {i}")));
+                    }
+                });
+            }
+        });
+
+        assert!(cache.load(&key).expect("entrada").to_response().text().starts_with("body "));
+        let files: Vec<_> = std::fs::read_dir(&dir).unwrap().flatten().map(|e| e.file_name()).collect();
+        assert_eq!(files.len(), 1, "{files:?}");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn key_depends_on_headers_but_not_their_order() {
+        let h = |pairs: &[(&str, &str)]| pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect::<Vec<_>>();
+        let a = HttpCache::key("https://x.test", &h(&[("Accept", "json"), ("X-Page", "2")]));
+        let b = HttpCache::key("https://x.test", &h(&[("x-page", "2"), ("accept", "json")]));
+        let c = HttpCache::key("https://x.test", &h(&[("X-Page", "3")]));
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+}
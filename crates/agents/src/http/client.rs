@@ -0,0 +1,360 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use job_hunter_core::{AgentError, SourceSettings};
+use reqwest::header::HeaderMap;
+use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
+use tracing::{debug, info, warn};
+
+use super::cache::HttpCache;
+use super::proxy::{ProxyEntry, ProxyPool};
+use super::rate_limit::HostRateLimits;
+use super::retry::{retry_delay, RetryPolicy};
+use super::robots::RobotsCache;
+use super::tape::{HttpTape, TapeMode};
+use super::emit_log;
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+const POOL_MAX_IDLE_PER_HOST: usize = 8;
+
+/// Proxies distintos a probar en una misma petición si el proxy falla al conectar.
+const MAX_PROXY_ATTEMPTS: usize = 3;
+
+/// Clave del pool de clientes: un `reqwest::Client` (con su pool de
+/// conexiones) por fuente, user agent, timeout y proxy.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct ClientKey {
+    source: String,
+    user_agent: String,
+    timeout: Duration,
+    proxy: Option<String>,
+}
+
+/// Cliente pooled del proceso para `key`; se construye la primera vez.
+fn pooled_client(key: &ClientKey, proxy: Option<&ProxyEntry>) -> Result<reqwest::Client, AgentError> {
+    static CLIENTS: OnceLock<Mutex<HashMap<ClientKey, reqwest::Client>>> = OnceLock::new();
+    let mut clients = CLIENTS.get_or_init(Mutex::default).lock().unwrap();
+    if let Some(client) = clients.get(key) {
+        return Ok(client.clone());
+    }
+
+    let mut builder = reqwest::Client::builder()
+        .user_agent(&key.user_agent)
+        .timeout(key.timeout)
+        .connect_timeout(CONNECT_TIMEOUT.min(key.timeout))
+        .pool_idle_timeout(POOL_IDLE_TIMEOUT)
+        .pool_max_idle_per_host(POOL_MAX_IDLE_PER_HOST);
+    if let Some(p) = proxy {
+        builder = builder.proxy(p.proxy());
+    }
+    let client = builder.build()?;
+    clients.insert(key.clone(), client.clone());
+    Ok(client)
+}
+
+/// Acceso HTTP de una fuente: aplica su user agent, timeout, la cuota por
+/// host (`delay_ms` entre peticiones), su política de reintentos, la caché en
+/// disco y, con `use_proxy`, el pool de proxies compartido. Los
+/// `reqwest::Client` se reutilizan entre ejecuciones, así que crearlo es barato.
+#[derive(Debug, Clone)]
+pub struct SourceClient {
+    source: String,
+    user_agent: String,
+    timeout: Duration,
+    use_proxy: bool,
+    interval: Duration,
+    robots: bool,
+    ignore_robots: bool,
+    retry: RetryPolicy,
+    cache_max_age: Duration,
+}
+
+impl SourceClient {
+    pub fn new(cfg: &SourceSettings) -> Self {
+        Self {
+            source: cfg.source.slug().to_string(),
+            user_agent: cfg.user_agent.clone(),
+            timeout: DEFAULT_TIMEOUT,
+            use_proxy: cfg.use_proxy,
+            interval: Duration::from_millis(cfg.delay_ms),
+            robots: false,
+            ignore_robots: cfg.ignore_robots,
+            retry: RetryPolicy::from_settings(cfg),
+            cache_max_age: Duration::from_secs(cfg.cache_max_age_secs),
+        }
+    }
+
+    /// Comprueba robots.txt antes de cada petición (scrapers HTML), salvo que
+    /// la fuente lo desactive con `ignore_robots`.
+    pub fn with_robots(mut self) -> Self {
+        if self.ignore_robots {
+            warn!("⚠️ [{}] robots.txt ignorado por configuración (ignore_robots).", self.source);
+        } else {
+            self.robots = true;
+        }
+        self
+    }
+
+    pub(crate) fn user_agent(&self) -> &str {
+        &self.user_agent
+    }
+
+    pub(crate) fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Turno para una carga que no pasa por reqwest (navegador headless):
+    /// robots.txt, si está activo, y la cuota del host.
+    pub async fn acquire(&self, url: &str) -> Result<(), AgentError> {
+        if self.robots {
+            RobotsCache::global().check(self, url).await?;
+        }
+        HostRateLimits::global().until_ready(url, &self.source, self.interval).await;
+        Ok(())
+    }
+
+    /// Timeout total por petición (30s por defecto).
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn get(&self, url: &str) -> SourceRequest<'_> {
+        SourceRequest {
+            client: self,
+            url: url.to_string(),
+            headers: Vec::new(),
+        }
+    }
+
+    fn key(&self, proxy: Option<&ProxyEntry>) -> ClientKey {
+        ClientKey {
+            source: self.source.clone(),
+            user_agent: self.user_agent.clone(),
+            timeout: self.timeout,
+            proxy: proxy.map(|p| p.url().to_string()),
+        }
+    }
+}
+
+/// Petición GET en construcción sobre un [`SourceClient`].
+pub struct SourceRequest<'a> {
+    client: &'a SourceClient,
+    url: String,
+    headers: Vec<(String, String)>,
+}
+
+impl SourceRequest<'_> {
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Envía la petición y lee el cuerpo completo. Si la fuente respeta
+    /// robots.txt y la URL está prohibida, falla sin llegar a pedirla.
+    ///
+    /// Una respuesta cacheada más reciente que `cache_max_age_secs` se sirve
+    /// sin red; si es más antigua se revalida con `If-None-Match` /
+    /// `If-Modified-Since` y un 304 reutiliza el cuerpo guardado.
+    pub async fn send(mut self) -> Result<HttpResponse, AgentError> {
+        if self.client.robots {
+            RobotsCache::global().check(self.client, &self.url).await?;
+        }
+
+        // Con grabadora activa la caché no interviene: todo pasa por la grabación
+        let cache = if HttpTape::current().is_some() { None } else { HttpCache::global() };
+        let Some(cache) = cache else {
+            return self.send_with_retries().await;
+        };
+        let key = HttpCache::key(&self.url, &self.headers);
+        let mut cached = cache.load(&key);
+        if let Some(entry) = &cached {
+            if entry.age() < self.client.cache_max_age {
+                debug!("💾 [{}] Caché vigente: {}", self.client.source, self.url);
+                return Ok(entry.to_response());
+            }
+            for (name, value) in entry.validators() {
+                if !self.headers.iter().any(|(k, _)| k.eq_ignore_ascii_case(name)) {
+                    self.headers.push((name.to_string(), value));
+                }
+            }
+        }
+
+        let res = self.send_with_retries().await?;
+        match cached.as_mut() {
+            Some(entry) if res.status() == StatusCode::NOT_MODIFIED => {
+                debug!("💾 [{}] 304, se reutiliza la caché: {}", self.client.source, self.url);
+                cache.touch(&key, entry);
+                Ok(entry.to_response())
+            }
+            _ => {
+                if res.status() == StatusCode::OK {
+                    cache.store(&key, &res);
+                }
+                Ok(res)
+            }
+        }
+    }
+
+    /// Envío con la política de reintentos de la fuente: ante 429/5xx o
+    /// errores de red transitorios reintenta respetando `Retry-After`; los
+    /// reintentos van al log de la UI. Solo GET, que es idempotente.
+    async fn send_with_retries(&self) -> Result<HttpResponse, AgentError> {
+        let policy = self.client.retry;
+        let mut retries = 0;
+        loop {
+            let result = self.dispatch().await;
+            let next = retry_delay(&policy, retries + 1, &result).filter(|_| retries < policy.max_retries);
+            let Some((wait, reason)) = next else {
+                if retries > 0 {
+                    let outcome = match &result {
+                        Ok(res) if res.status().is_success() => "OK".to_string(),
+                        Ok(res) => format!("HTTP {}", res.status().as_u16()),
+                        Err(e) => e.to_string(),
+                    };
+                    let msg = format!("🔁 [{}] {} tras {} reintentos: {}", self.client.source, self.url, retries, outcome);
+                    info!("{}", msg);
+                    emit_log("info", msg);
+                }
+                return result;
+            };
+
+            retries += 1;
+            let msg = format!(
+                "🔁 [{}] Reintento {}/{} de {} en {:.1}s ({})",
+                self.client.source,
+                retries,
+                policy.max_retries,
+                self.url,
+                wait.as_secs_f64(),
+                reason
+            );
+            warn!("{}", msg);
+            emit_log("warn", msg);
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Un único envío sin robots.txt ni reintentos. En modo `replay` se sirve
+    /// la grabación sin tocar la red; en `record` se graba la respuesta.
+    pub(super) async fn dispatch(&self) -> Result<HttpResponse, AgentError> {
+        let tape = HttpTape::current();
+        if let Some(tape) = tape.as_deref().filter(|t| t.mode() == TapeMode::Replay) {
+            let rec = tape
+                .replay("GET", &self.url, None)
+                .ok_or_else(|| AgentError::Scraping(tape.miss_message("GET", &self.url)))?;
+            let status = StatusCode::from_u16(rec.status).unwrap_or(StatusCode::OK);
+            return Ok(HttpResponse::new(rec.url.clone(), status, rec.header_map(), rec.body));
+        }
+
+        let res = self.dispatch_live().await?;
+        if let Some(tape) = tape.as_deref().filter(|t| t.mode() == TapeMode::Record) {
+            tape.record("GET", &self.url, None, res.status().as_u16(), res.headers(), res.text());
+        }
+        Ok(res)
+    }
+
+    /// Envío real. Con `use_proxy` rota por el pool: un fallo de conexión
+    /// penaliza al proxy y se prueba con el siguiente.
+    async fn dispatch_live(&self) -> Result<HttpResponse, AgentError> {
+        if !self.client.use_proxy {
+            let client = pooled_client(&self.client.key(None), None)?;
+            return self.send_with(&client).await;
+        }
+
+        let pool = ProxyPool::global();
+        if pool.is_empty() {
+            return Err(AgentError::Scraping(format!(
+                "[{}] use_proxy activo pero no hay proxies configurados (JOB_HUNTER_PROXIES)",
+                self.client.source
+            )));
+        }
+
+        let attempts = pool.len().min(MAX_PROXY_ATTEMPTS);
+        let mut last_err = None;
+        for _ in 0..attempts {
+            let Some(entry) = pool.next() else { break };
+            let client = pooled_client(&self.client.key(Some(&entry)), Some(&entry))?;
+            match self.send_with(&client).await {
+                Ok(res) if res.status() == StatusCode::PROXY_AUTHENTICATION_REQUIRED => {
+                    warn!("⚠️ [{}] Proxy {} rechaza las credenciales (407).", self.client.source, entry.display());
+                    pool.report_failure(&entry);
+                    last_err = Some(AgentError::Scraping(format!("Proxy {}: HTTP 407", entry.display())));
+                }
+                Ok(res) => {
+                    pool.report_success(&entry);
+                    return Ok(res);
+                }
+                Err(AgentError::Network(e)) if e.is_connect() || e.is_timeout() => {
+                    warn!("⚠️ [{}] Fallo vía proxy {}: {}", self.client.source, entry.display(), e);
+                    pool.report_failure(&entry);
+                    last_err = Some(AgentError::Network(e));
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Err(last_err.unwrap_or_else(|| AgentError::Scraping("Sin proxies disponibles".into())))
+    }
+
+    async fn send_with(&self, client: &reqwest::Client) -> Result<HttpResponse, AgentError> {
+        HostRateLimits::global()
+            .until_ready(&self.url, &self.client.source, self.client.interval)
+            .await;
+
+        let mut req = client.get(&self.url);
+        for (k, v) in &self.headers {
+            req = req.header(k.as_str(), v.as_str());
+        }
+        let res = req.send().await?;
+        let status = res.status();
+        let headers = res.headers().clone();
+        let url = res.url().to_string();
+        let body = res.text().await?;
+        Ok(HttpResponse { url, status, headers, body })
+    }
+}
+
+/// Respuesta HTTP ya leída entera.
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    url: String,
+    status: StatusCode,
+    headers: HeaderMap,
+    body: String,
+}
+
+impl HttpResponse {
+    pub(crate) fn new(url: String, status: StatusCode, headers: HeaderMap, body: String) -> Self {
+        Self { url, status, headers, body }
+    }
+
+    /// URL final (tras redirecciones).
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    pub fn text(&self) -> &str {
+        &self.body
+    }
+
+    pub fn into_text(self) -> String {
+        self.body
+    }
+
+    pub fn json<T: DeserializeOwned>(&self) -> Result<T, AgentError> {
+        serde_json::from_str(&self.body)
+            .map_err(|e| AgentError::Scraping(format!("Error parseando JSON: {}", e)))
+    }
+}
@@ -0,0 +1,36 @@
+//! Capa HTTP compartida por los scrapers: clientes pooled por fuente, pool
+//! de proxies, rate limiting por host, robots.txt, reintentos, caché en disco
+//! y grabación/reproducción del tráfico (también el de los proveedores LLM).
+
+use std::sync::OnceLock;
+
+use tokio::sync::broadcast;
+
+pub mod cache;
+mod client;
+pub mod proxy;
+pub mod rate_limit;
+pub mod retry;
+pub mod robots;
+pub mod tape;
+
+pub use cache::HttpCache;
+pub use client::{HttpResponse, SourceClient, SourceRequest};
+pub use proxy::{ProxyEntry, ProxyPool};
+pub use rate_limit::HostRateLimits;
+pub use retry::RetryPolicy;
+pub use robots::{RobotsCache, RobotsTxt};
+pub use tape::{HttpTape, Recording, TapeMode};
+
+static WS_TX: OnceLock<broadcast::Sender<String>> = OnceLock::new();
+
+/// Canal WebSocket del proceso al que la capa HTTP reporta reintentos.
+pub fn set_ws_tx(tx: broadcast::Sender<String>) {
+    let _ = WS_TX.set(tx);
+}
+
+fn emit_log(level: &str, msg: impl Into<String>) {
+    if let Some(tx) = WS_TX.get() {
+        let _ = tx.send(serde_json::json!({"type":"log", "payload": {"level": level, "msg": msg.into()}}).to_string());
+    }
+}
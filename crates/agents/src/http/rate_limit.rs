@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use tokio::time::Instant;
+use tracing::debug;
+use url::Url;
+
+/// Intervalo mínimo entre peticiones a un mismo host, aunque la fuente
+/// tenga `delay_ms = 0`.
+const MIN_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Turnos de un host.
+struct HostSlot {
+    /// Intervalo más restrictivo pedido por las reservas aún pendientes.
+    interval: Duration,
+    /// Primer instante libre para la siguiente petición.
+    next: Instant,
+}
+
+/// Rate limiter del proceso, uno por host. Todas las peticiones de los
+/// scrapers pasan por aquí, así que varias ejecuciones simultáneas (o varias
+/// fuentes contra el mismo board) comparten los turnos del host.
+///
+/// La cuota sale del `delay_ms` de cada fuente (una petición por intervalo).
+/// Mientras haya turnos reservados, el host mantiene el intervalo más
+/// restrictivo de las fuentes que lo comparten; cuando la cola se vacía vuelve
+/// al de la siguiente petición, así que una ejecución posterior más laxa no
+/// hereda la cuota de otra. El `Crawl-delay` de robots.txt (y `MIN_INTERVAL`)
+/// es un suelo que se aplica en cada reserva y no se guarda en el host.
+#[derive(Default)]
+pub struct HostRateLimits {
+    hosts: Mutex<HashMap<String, HostSlot>>,
+    crawl_delays: Mutex<HashMap<String, Duration>>,
+}
+
+impl HostRateLimits {
+    pub fn global() -> &'static HostRateLimits {
+        static LIMITS: OnceLock<HostRateLimits> = OnceLock::new();
+        LIMITS.get_or_init(HostRateLimits::default)
+    }
+
+    /// Espera el turno de `url` con la cuota de `source` (`interval` entre peticiones).
+    pub async fn until_ready(&self, url: &str, source: &str, interval: Duration) {
+        let Some(host) = host_of(url) else { return };
+        let crawl_delay = self.crawl_delays.lock().unwrap().get(&host).copied().unwrap_or_default();
+        let now = Instant::now();
+        let turn = self.reserve(&host, interval, crawl_delay.max(MIN_INTERVAL), now);
+        if turn > now {
+            debug!("⏳ [{}] Esperando turno para {}", source, host);
+            tokio::time::sleep_until(turn).await;
+        }
+    }
+
+    /// Fija el `Crawl-delay` declarado por el host (`None` lo elimina).
+    pub fn set_crawl_delay(&self, host: &str, delay: Option<Duration>) {
+        let mut delays = self.crawl_delays.lock().unwrap();
+        match delay.filter(|d| !d.is_zero()) {
+            Some(d) => delays.insert(host.to_ascii_lowercase(), d),
+            None => delays.remove(&host.to_ascii_lowercase()),
+        };
+    }
+
+    /// Reserva el siguiente turno de `host` y devuelve cuándo empieza. El
+    /// turno siguiente queda a `max(intervalo, floor)`.
+    fn reserve(&self, host: &str, interval: Duration, floor: Duration, now: Instant) -> Instant {
+        let mut hosts = self.hosts.lock().unwrap();
+        let slot = hosts
+            .entry(host.to_string())
+            .or_insert(HostSlot { interval, next: now });
+        // Sin turnos pendientes el intervalo anterior ya no aplica
+        slot.interval = if now > slot.next { interval } else { slot.interval.max(interval) };
+        let turn = slot.next.max(now);
+        slot.next = turn + slot.interval.max(floor);
+        turn
+    }
+}
+
+/// Host de una URL en minúsculas (clave del limitador).
+pub fn host_of(url: &str) -> Option<String> {
+    Url::parse(url).ok()?.host_str().map(|h| h.to_ascii_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Instantes (en ms desde el inicio) en que se concede cada petición.
+    async fn turns(limits: &HostRateLimits, calls: &[(&str, &str, u64)]) -> Vec<u128> {
+        let start = Instant::now();
+        let mut out = Vec::new();
+        for (url, source, ms) in calls {
+            limits.until_ready(url, source, Duration::from_millis(*ms)).await;
+            out.push(start.elapsed().as_millis());
+        }
+        out
+    }
+
+    /// Dos fuentes sobre el mismo host comparten un limitador con el intervalo
+    /// más estricto, también para la fuente más laxa y aunque vuelva a pedir el suyo.
+    #[tokio::test(start_paused = true)]
+    async fn sources_on_same_host_share_strictest_limiter() {
+        let limits = HostRateLimits::default();
+        let got = turns(
+            &limits,
+            &[
+                ("https://jobs.example.com/a", "fast", 300),
+                ("https://jobs.example.com/b", "slow", 1_000),
+                ("https://jobs.example.com/c", "fast", 300),
+                ("https://jobs.example.com/d", "fast", 300),
+            ],
+        )
+        .await;
+        assert_eq!(got, [0, 300, 1_300, 2_300]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn hosts_are_independent_and_floors_apply() {
+        let limits = HostRateLimits::default();
+        limits.set_crawl_delay("slow.example.com", Some(Duration::from_secs(2)));
+        let got = turns(
+            &limits,
+            &[
+                ("https://a.example.com/", "a", 0),
+                ("https://a.example.com/", "a", 0),
+                ("https://b.example.com/", "b", 0),
+                ("https://slow.example.com/", "s", 0),
+                ("https://slow.example.com/", "s", 0),
+            ],
+        )
+        .await;
+        // `MIN_INTERVAL` en a.example.com, b.example.com sin espera y el Crawl-delay en slow
+        assert_eq!(got, [0, 200, 200, 200, 2_200]);
+    }
+
+    /// Una ejecución posterior con `delay_ms` menor (o sin Crawl-delay) no se
+    /// queda con la cuota más estricta de la anterior.
+    #[tokio::test(start_paused = true)]
+    async fn later_laxer_run_is_not_stuck_at_stricter_interval() {
+        let limits = HostRateLimits::default();
+        limits.set_crawl_delay("jobs.example.com", Some(Duration::from_secs(3)));
+        let strict = [("https://jobs.example.com/a", "s", 1_000), ("https://jobs.example.com/b", "s", 1_000)];
+        assert_eq!(turns(&limits, &strict).await, [0, 3_000]);
+
+        tokio::time::sleep(Duration::from_secs(10)).await;
+        limits.set_crawl_delay("jobs.example.com", None);
+        assert_eq!(turns(&limits, &strict).await, [0, 1_000]);
+
+        tokio::time::sleep(Duration::from_secs(10)).await;
+        let lax = [("https://jobs.example.com/a", "s", 300), ("https://jobs.example.com/b", "s", 300)];
+        assert_eq!(turns(&limits, &lax).await, [0, 300]);
+    }
+}
@@ -0,0 +1,169 @@
+use std::time::{Duration, SystemTime};
+
+use job_hunter_core::{AgentError, SourceSettings};
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::StatusCode;
+
+use super::client::HttpResponse;
+
+/// Tope del backoff exponencial.
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+/// Un `Retry-After` mayor que esto no se espera: se devuelve la respuesta.
+/// Muy por debajo del timeout de scraping del orquestador (120 s), para que
+/// con varios reintentos la rama termine con el 429/503 y no como timeout.
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(30);
+
+/// Política de reintentos de una fuente. Solo se aplica a GET (idempotentes),
+/// que es lo único que envía [`super::SourceClient`].
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub base_delay: Duration,
+}
+
+impl RetryPolicy {
+    pub fn from_settings(cfg: &SourceSettings) -> Self {
+        Self {
+            max_retries: cfg.max_retries,
+            base_delay: Duration::from_millis(cfg.retry_backoff_ms),
+        }
+    }
+
+    /// Espera antes del reintento `retry` (1, 2, ...): `base * 2^(retry-1)`
+    /// con jitter entre la mitad y el total, hasta `MAX_BACKOFF`.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let exp = self
+            .base_delay
+            .saturating_mul(1 << retry.saturating_sub(1).min(16))
+            .min(MAX_BACKOFF);
+        let half = exp / 2;
+        half + half.mul_f64(fastrand::f64())
+    }
+}
+
+/// Espera a aplicar antes de reintentar, o `None` si el resultado es final.
+pub(crate) fn retry_delay(
+    policy: &RetryPolicy,
+    retry: u32,
+    result: &Result<HttpResponse, AgentError>,
+) -> Option<(Duration, String)> {
+    match result {
+        Ok(res) => {
+            let status = res.status();
+            if !is_retryable_status(status) {
+                return None;
+            }
+            let wait = match retry_after(res.headers()) {
+                Some(d) if d > MAX_RETRY_AFTER => return None,
+                Some(d) => d,
+                None => policy.backoff(retry),
+            };
+            Some((wait, format!("HTTP {}", status.as_u16())))
+        }
+        Err(AgentError::Network(e)) if is_transient(e) => Some((policy.backoff(retry), e.to_string())),
+        Err(_) => None,
+    }
+}
+
+fn is_retryable_status(status: StatusCode) -> bool {
+    matches!(status.as_u16(), 408 | 429 | 500 | 502 | 503 | 504)
+}
+
+/// Cortes de conexión, timeouts y errores leyendo el cuerpo.
+fn is_transient(e: &reqwest::Error) -> bool {
+    e.is_timeout() || e.is_connect() || e.is_request() || e.is_body()
+}
+
+/// `Retry-After` en segundos o como fecha HTTP.
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let at = httpdate::parse_http_date(value).ok()?;
+    Some(at.duration_since(SystemTime::now()).unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    fn policy(base_ms: u64) -> RetryPolicy {
+        RetryPolicy { max_retries: 3, base_delay: Duration::from_millis(base_ms) }
+    }
+
+    fn response(status: u16, retry_after: Option<&str>) -> Result<HttpResponse, AgentError> {
+        let mut headers = HeaderMap::new();
+        if let Some(v) = retry_after {
+            headers.insert(RETRY_AFTER, HeaderValue::from_str(v).unwrap());
+        }
+        let status = StatusCode::from_u16(status).unwrap();
+        Ok(HttpResponse::new("https://example.com".into(), status, headers, String::new()))
+    }
+
+    /// `base * 2^(retry-1)` con jitter en [mitad, total], hasta `MAX_BACKOFF`.
+    #[test]
+    fn backoff_doubles_with_jitter_up_to_max() {
+        let p = policy(1_000);
+        for (retry, full) in [(1, 1_000), (2, 2_000), (3, 4_000), (6, 30_000), (40, 30_000)] {
+            for _ in 0..20 {
+                let ms = p.backoff(retry).as_millis();
+                assert!((full / 2..=full).contains(&ms), "retry {retry}: {ms}ms");
+            }
+        }
+        assert_eq!(policy(0).backoff(1), Duration::ZERO);
+    }
+
+    #[test]
+    fn retryable_statuses_use_backoff() {
+        let p = policy(1_000);
+        for status in [408, 429, 500, 502, 503, 504] {
+            let (wait, reason) = retry_delay(&p, 2, &response(status, None)).expect("reintento");
+            assert!((1_000..=2_000).contains(&wait.as_millis()), "{status}: {wait:?}");
+            assert_eq!(reason, format!("HTTP {status}"));
+        }
+        for status in [200, 304, 400, 403, 404, 501] {
+            assert!(retry_delay(&p, 1, &response(status, None)).is_none(), "{status}");
+        }
+    }
+
+    #[test]
+    fn retry_after_overrides_backoff() {
+        let p = policy(1_000);
+        let (wait, _) = retry_delay(&p, 1, &response(429, Some("7"))).unwrap();
+        assert_eq!(wait, Duration::from_secs(7));
+
+        let at = httpdate::fmt_http_date(SystemTime::now() + Duration::from_secs(30));
+        let (wait, _) = retry_delay(&p, 1, &response(503, Some(&at))).unwrap();
+        assert!((28..=30).contains(&wait.as_secs()), "{wait:?}");
+
+        // Fecha ya pasada: reintento inmediato
+        let (wait, _) = retry_delay(&p, 1, &response(503, Some("Tue, 02 Jul 2024 10:00:00 GMT"))).unwrap();
+        assert_eq!(wait, Duration::ZERO);
+
+        // Valor ilegible: backoff normal
+        let (wait, _) = retry_delay(&p, 1, &response(503, Some("pronto"))).unwrap();
+        assert!((500..=1_000).contains(&wait.as_millis()));
+    }
+
+    /// Un `Retry-After` mayor que `MAX_RETRY_AFTER` no se espera.
+    #[test]
+    fn long_retry_after_is_final() {
+        assert!(retry_delay(&policy(1_000), 1, &response(429, Some("3600"))).is_none());
+        assert!(retry_delay(&policy(1_000), 1, &response(429, Some("31"))).is_none());
+        let (wait, _) = retry_delay(&policy(1_000), 1, &response(429, Some("30"))).unwrap();
+        assert_eq!(wait, Duration::from_secs(30));
+    }
+
+    #[tokio::test]
+    async fn transient_network_errors_are_retried() {
+        let p = policy(100);
+        // Puerto cerrado: error de conexión
+        let err = reqwest::get("http://127.0.0.1:1/").await.unwrap_err();
+        let (wait, _) = retry_delay(&p, 1, &Err(AgentError::Network(err))).expect("reintento");
+        assert!(wait <= Duration::from_millis(100));
+
+        assert!(retry_delay(&p, 1, &Err(AgentError::Scraping("JSON roto".into()))).is_none());
+    }
+}
@@ -0,0 +1,330 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use job_hunter_core::AgentError;
+use tracing::{info, warn};
+use url::Url;
+
+use super::client::SourceClient;
+use super::rate_limit::HostRateLimits;
+
+/// Vigencia de un robots.txt descargado.
+const ROBOTS_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+/// Vigencia del veredicto cuando el robots.txt no se pudo leer (5xx o red).
+const ROBOTS_ERROR_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Reglas `Allow`/`Disallow` de un grupo de robots.txt.
+#[derive(Debug, Clone)]
+struct Rule {
+    allow: bool,
+    pattern: String,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Group {
+    agents: Vec<String>,
+    rules: Vec<Rule>,
+    crawl_delay: Option<Duration>,
+}
+
+/// robots.txt de un host ya parseado (RFC 9309 + `Crawl-delay`).
+#[derive(Debug, Clone, Default)]
+pub struct RobotsTxt {
+    groups: Vec<Group>,
+    /// Sin acceso al fichero (5xx o error de red): todo prohibido.
+    disallow_all: bool,
+}
+
+impl RobotsTxt {
+    /// Sin robots.txt (404 y demás 4xx): todo permitido.
+    pub fn allow_all() -> Self {
+        Self::default()
+    }
+
+    pub fn disallow_all() -> Self {
+        Self { groups: vec![], disallow_all: true }
+    }
+
+    pub fn parse(text: &str) -> Self {
+        let mut groups: Vec<Group> = Vec::new();
+        let mut current = Group::default();
+        // `true` mientras se leen líneas `User-agent` seguidas
+        let mut in_agents = false;
+
+        for line in text.lines() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            let Some((key, value)) = line.split_once(':') else { continue };
+            let value = value.trim();
+            match key.trim().to_ascii_lowercase().as_str() {
+                "user-agent" => {
+                    if !in_agents && !current.agents.is_empty() {
+                        groups.push(std::mem::take(&mut current));
+                    }
+                    current.agents.push(value.to_ascii_lowercase());
+                    in_agents = true;
+                }
+                "allow" | "disallow" if !current.agents.is_empty() => {
+                    in_agents = false;
+                    // `Disallow:` vacío no restringe nada
+                    if !value.is_empty() {
+                        current.rules.push(Rule {
+                            allow: key.trim().eq_ignore_ascii_case("allow"),
+                            pattern: value.to_string(),
+                        });
+                    }
+                }
+                "crawl-delay" if !current.agents.is_empty() => {
+                    in_agents = false;
+                    current.crawl_delay = value
+                        .parse::<f64>()
+                        .ok()
+                        .filter(|s| s.is_finite() && *s > 0.0)
+                        .map(Duration::from_secs_f64);
+                }
+                _ => {}
+            }
+        }
+        if !current.agents.is_empty() {
+            groups.push(current);
+        }
+        Self { groups, disallow_all: false }
+    }
+
+    /// Grupos que aplican a `user_agent`: los de nombre más largo contenido en
+    /// el user agent o, si no hay, los de `*`.
+    fn groups_for(&self, user_agent: &str) -> Vec<&Group> {
+        let ua = user_agent.to_ascii_lowercase();
+        let best = self
+            .groups
+            .iter()
+            .flat_map(|g| g.agents.iter())
+            .filter(|a| a.as_str() != "*" && ua.contains(a.as_str()))
+            .map(|a| a.len())
+            .max();
+        self.groups
+            .iter()
+            .filter(|g| {
+                g.agents.iter().any(|a| match best {
+                    Some(len) => a.len() == len && ua.contains(a.as_str()),
+                    None => a == "*",
+                })
+            })
+            .collect()
+    }
+
+    /// `true` si `path` (ruta + query) se puede rastrear. Gana la regla más
+    /// larga que coincide; a igualdad, `Allow`.
+    pub fn is_allowed(&self, user_agent: &str, path: &str) -> bool {
+        if self.disallow_all {
+            return false;
+        }
+        if path == "/robots.txt" {
+            return true;
+        }
+        self.groups_for(user_agent)
+            .iter()
+            .flat_map(|g| g.rules.iter())
+            .filter(|r| pattern_matches(&r.pattern, path))
+            .max_by_key(|r| (r.pattern.len(), r.allow))
+            .is_none_or(|r| r.allow)
+    }
+
+    pub fn crawl_delay(&self, user_agent: &str) -> Option<Duration> {
+        self.groups_for(user_agent).iter().filter_map(|g| g.crawl_delay).max()
+    }
+}
+
+/// Coincidencia de prefijo con comodines `*` y ancla final `$`.
+fn pattern_matches(pattern: &str, path: &str) -> bool {
+    let (pattern, anchored) = match pattern.strip_suffix('$') {
+        Some(p) => (p, true),
+        None => (pattern, false),
+    };
+    let parts: Vec<&str> = pattern.split('*').collect();
+    let Some(rest) = path.strip_prefix(parts[0]) else { return false };
+    if parts.len() == 1 {
+        return !anchored || rest.is_empty();
+    }
+
+    let mut rest = rest;
+    let last = parts.len() - 1;
+    for (i, part) in parts.iter().enumerate().skip(1) {
+        if i == last && anchored {
+            return rest.ends_with(part);
+        }
+        match rest.find(part) {
+            Some(pos) => rest = &rest[pos + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+struct CachedRobots {
+    expires: Instant,
+    robots: Arc<RobotsTxt>,
+}
+
+/// Caché del proceso de robots.txt por origen (`esquema://host:puerto`).
+#[derive(Default)]
+pub struct RobotsCache {
+    entries: Mutex<HashMap<String, CachedRobots>>,
+}
+
+impl RobotsCache {
+    pub fn global() -> &'static RobotsCache {
+        static CACHE: OnceLock<RobotsCache> = OnceLock::new();
+        CACHE.get_or_init(RobotsCache::default)
+    }
+
+    /// Comprueba que robots.txt permite `url` a la fuente y aplica su
+    /// `Crawl-delay` al rate limiter del host.
+    pub(crate) async fn check(&self, client: &SourceClient, url: &str) -> Result<(), AgentError> {
+        let parsed = Url::parse(url).map_err(|e| AgentError::Scraping(format!("URL inválida {}: {}", url, e)))?;
+        let Some(host) = parsed.host_str() else { return Ok(()) };
+        let origin = parsed.origin().ascii_serialization();
+
+        let robots = match self.cached(&origin) {
+            Some(robots) => robots,
+            None => self.refresh(client, &origin, host).await,
+        };
+
+        let path = match parsed.query() {
+            Some(q) => format!("{}?{}", parsed.path(), q),
+            None => parsed.path().to_string(),
+        };
+        if robots.is_allowed(client.user_agent(), &path) {
+            Ok(())
+        } else {
+            Err(AgentError::Scraping(format!("robots.txt de {} no permite {}", host, path)))
+        }
+    }
+
+    fn cached(&self, origin: &str) -> Option<Arc<RobotsTxt>> {
+        let entries = self.entries.lock().unwrap();
+        entries
+            .get(origin)
+            .filter(|c| c.expires > Instant::now())
+            .map(|c| c.robots.clone())
+    }
+
+    async fn refresh(&self, client: &SourceClient, origin: &str, host: &str) -> Arc<RobotsTxt> {
+        let robots_url = format!("{}/robots.txt", origin);
+        let (robots, ttl) = match client.get(&robots_url).dispatch().await {
+            Ok(res) if res.status().is_success() => (RobotsTxt::parse(res.text()), ROBOTS_TTL),
+            Ok(res) if res.status().is_client_error() => (RobotsTxt::allow_all(), ROBOTS_TTL),
+            failed => {
+                let reason = match failed {
+                    Ok(res) => format!("HTTP {}", res.status()),
+                    Err(e) => e.to_string(),
+                };
+                // RFC 9309: robots.txt inaccesible => no rastrear. Si había una
+                // copia caducada, se sigue usando.
+                let stale = self.entries.lock().unwrap().get(origin).map(|c| c.robots.clone());
+                warn!(
+                    "⚠️ robots.txt de {} inaccesible ({}): {}",
+                    host,
+                    reason,
+                    if stale.is_some() { "se usa la copia anterior" } else { "no se rastrea" }
+                );
+                let robots = stale.map(|r| (*r).clone()).unwrap_or_else(RobotsTxt::disallow_all);
+                (robots, ROBOTS_ERROR_TTL)
+            }
+        };
+
+        let crawl_delay = robots.crawl_delay(client.user_agent());
+        if let Some(d) = crawl_delay {
+            info!("🤖 robots.txt de {} fija Crawl-delay de {:?}.", host, d);
+        }
+        HostRateLimits::global().set_crawl_delay(host, crawl_delay);
+
+        let robots = Arc::new(robots);
+        self.entries.lock().unwrap().insert(
+            origin.to_string(),
+            CachedRobots { expires: Instant::now() + ttl, robots: robots.clone() },
+        );
+        robots
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UA: &str = "Mozilla/5.0 (compatible; JobHunterBot/1.0)";
+
+    #[test]
+    fn longest_match_wins_and_allow_breaks_ties() {
+        let robots = RobotsTxt::parse(
+            "User-agent: *\n\
+             Disallow: /jobs\n\
+             Allow: /jobs/public\n\
+             Disallow: /a/\n\
+             Allow: /a/\n\
+             Disallow:\n",
+        );
+        assert!(!robots.is_allowed(UA, "/jobs"));
+        assert!(!robots.is_allowed(UA, "/jobs/123"));
+        assert!(robots.is_allowed(UA, "/jobs/public/123"));
+        assert!(robots.is_allowed(UA, "/a/b"));
+        assert!(robots.is_allowed(UA, "/about"));
+    }
+
+    #[test]
+    fn wildcards_and_end_anchor() {
+        let robots = RobotsTxt::parse(
+            "User-agent: *\n\
+             Disallow: /*.pdf$\n\
+             Disallow: /search*q=\n\
+             Allow: /search?page=*&q=rust$\n",
+        );
+        assert!(!robots.is_allowed(UA, "/files/cv.pdf"));
+        assert!(robots.is_allowed(UA, "/files/cv.pdf?v=2"));
+        assert!(!robots.is_allowed(UA, "/search?page=2&q=go"));
+        assert!(robots.is_allowed(UA, "/search?page=2&q=rust"));
+        assert!(robots.is_allowed(UA, "/search"));
+    }
+
+    /// Se aplica el grupo del user agent más específico; si no hay, el de `*`.
+    #[test]
+    fn user_agent_groups() {
+        let robots = RobotsTxt::parse(
+            "# comentario\n\
+             User-agent: *\n\
+             Disallow: /\n\
+             \n\
+             User-agent: OtherBot\n\
+             User-agent: JobHunterBot # nosotros\n\
+             Disallow: /private\n\
+             Crawl-delay: 2.5\n",
+        );
+        assert!(robots.is_allowed(UA, "/jobs"));
+        assert!(!robots.is_allowed(UA, "/private/x"));
+        assert!(!robots.is_allowed("curl/8.0", "/jobs"));
+        // robots.txt siempre se puede leer
+        assert!(robots.is_allowed("curl/8.0", "/robots.txt"));
+
+        assert_eq!(robots.crawl_delay(UA), Some(Duration::from_millis(2_500)));
+        assert_eq!(robots.crawl_delay("curl/8.0"), None);
+    }
+
+    #[test]
+    fn crawl_delay_ignores_invalid_values() {
+        for value in ["0", "-1", "abc", "inf"] {
+            let robots = RobotsTxt::parse(&format!("User-agent: *\nCrawl-delay: {value}\n"));
+            assert_eq!(robots.crawl_delay(UA), None, "{value}");
+        }
+        let robots = RobotsTxt::parse("User-agent: *\nCrawl-delay: 10\n");
+        assert_eq!(robots.crawl_delay(UA), Some(Duration::from_secs(10)));
+    }
+
+    #[test]
+    fn missing_and_unreachable_files() {
+        assert!(RobotsTxt::allow_all().is_allowed(UA, "/anything"));
+        assert!(RobotsTxt::parse("").is_allowed(UA, "/anything"));
+        // Reglas fuera de un grupo se ignoran
+        assert!(RobotsTxt::parse("Disallow: /\n").is_allowed(UA, "/anything"));
+        assert!(!RobotsTxt::disallow_all().is_allowed(UA, "/anything"));
+    }
+}
@@ -0,0 +1,259 @@
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock, RwLock};
+
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use tracing::{info, warn};
+use url::Url;
+
+/// `record` graba todo el tráfico HTTP saliente; `replay` lo sirve desde las
+/// grabaciones sin tocar la red. Cualquier otro valor (o ausente): en vivo.
+const KEY_HTTP_MODE: &str = "JOB_HUNTER_HTTP_MODE";
+/// Directorio de grabaciones (por defecto `fixtures/http`).
+const KEY_FIXTURES_DIR: &str = "JOB_HUNTER_FIXTURES_DIR";
+const DEFAULT_FIXTURES_DIR: &str = "fixtures/http";
+
+/// Cabeceras de respuesta que se conservan en la grabación.
+const KEPT_HEADERS: &[&str] = &["content-type", "etag", "last-modified", "retry-after", "location"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TapeMode {
+    Record,
+    Replay,
+}
+
+/// Petición y respuesta grabadas. Nunca se guardan cabeceras de petición
+/// (llevan las API keys de los proveedores LLM) ni el cuerpo de la petición:
+/// el prompt de un LLM incluye el CV del usuario. `record` solo guarda su
+/// SHA-1 en `request_digest`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Recording {
+    pub method: String,
+    pub url: String,
+    /// Cuerpo literal de la petición; solo en fixtures escritos a mano.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_body: Option<String>,
+    /// SHA-1 (hex) del cuerpo de la petición, lo que escribe `record`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_digest: Option<String>,
+    pub status: u16,
+    #[serde(default)]
+    pub headers: Vec<(String, String)>,
+    #[serde(default)]
+    pub body: String,
+    /// Cuerpo en un fichero aparte, relativo a la grabación. Útil en fixtures
+    /// escritos a mano (JSON, RSS o HTML tal cual, sin escapar).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body_file: Option<String>,
+}
+
+impl Recording {
+    pub fn header_map(&self) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (k, v) in &self.headers {
+            if let (Ok(name), Ok(value)) = (HeaderName::from_bytes(k.as_bytes()), HeaderValue::from_str(v)) {
+                map.insert(name, value);
+            }
+        }
+        map
+    }
+
+    /// Lee una grabación y, si tiene `body_file`, carga el cuerpo.
+    fn load(path: &Path) -> Option<Self> {
+        let content = std::fs::read_to_string(path).ok()?;
+        let mut rec: Recording = match serde_json::from_str(&content) {
+            Ok(rec) => rec,
+            Err(e) => {
+                warn!("⚠️ Grabación inválida {}: {}", path.display(), e);
+                return None;
+            }
+        };
+        if let Some(file) = &rec.body_file {
+            let body_path = path.parent().unwrap_or(Path::new(".")).join(file);
+            match std::fs::read_to_string(&body_path) {
+                Ok(body) => rec.body = body,
+                Err(e) => {
+                    warn!("⚠️ Cuerpo de grabación ilegible {}: {}", body_path.display(), e);
+                    return None;
+                }
+            }
+        }
+        Some(rec)
+    }
+
+    fn matches(&self, method: &str, url: &str, body: Option<&str>) -> bool {
+        let same_body = match (&self.request_body, &self.request_digest) {
+            (Some(expected), _) => Some(expected.as_str()) == body,
+            (None, Some(digest)) => body.is_some_and(|b| body_digest(b) == *digest),
+            (None, None) => body.is_none(),
+        };
+        self.method.eq_ignore_ascii_case(method) && self.url == url && same_body
+    }
+}
+
+/// SHA-1 en hexadecimal del cuerpo de una petición.
+fn body_digest(body: &str) -> String {
+    format!("{:x}", Sha1::digest(body.as_bytes()))
+}
+
+/// Grabadora/reproductora del tráfico HTTP de scrapers y proveedores LLM
+/// (Ollama, OpenAI, Anthropic). Cada petición es un JSON en
+/// `<dir>/<host>/<método>_<hash>.json`, con el hash de método + URL + cuerpo.
+/// Al reproducir también vale cualquier otro `<dir>/<host>/*.json` cuyo
+/// método, URL y cuerpo coincidan, así que los fixtures pueden tener nombres legibles.
+#[derive(Debug)]
+pub struct HttpTape {
+    mode: TapeMode,
+    dir: PathBuf,
+}
+
+static TAPE: OnceLock<RwLock<Option<Arc<HttpTape>>>> = OnceLock::new();
+
+fn slot() -> &'static RwLock<Option<Arc<HttpTape>>> {
+    TAPE.get_or_init(|| {
+        let tape = HttpTape::from_env();
+        if let Some(t) = &tape {
+            info!("📼 Modo HTTP {:?} con grabaciones en {}", t.mode, t.dir.display());
+        }
+        RwLock::new(tape.map(Arc::new))
+    })
+}
+
+impl HttpTape {
+    pub fn new(mode: TapeMode, dir: impl Into<PathBuf>) -> Self {
+        Self { mode, dir: dir.into() }
+    }
+
+    fn from_env() -> Option<Self> {
+        let mode = match std::env::var(KEY_HTTP_MODE).ok()?.trim().to_ascii_lowercase().as_str() {
+            "record" => TapeMode::Record,
+            "replay" => TapeMode::Replay,
+            _ => return None,
+        };
+        let dir = std::env::var(KEY_FIXTURES_DIR).unwrap_or_else(|_| DEFAULT_FIXTURES_DIR.to_string());
+        Some(Self::new(mode, dir))
+    }
+
+    /// Grabadora activa del proceso (la de `JOB_HUNTER_HTTP_MODE` salvo que
+    /// se haya instalado otra).
+    pub fn current() -> Option<Arc<HttpTape>> {
+        slot().read().unwrap().clone()
+    }
+
+    /// Sustituye la grabadora del proceso (`None` vuelve a modo en vivo).
+    /// Pensado para tests y herramientas.
+    pub fn install(tape: Option<HttpTape>) {
+        *slot().write().unwrap() = tape.map(Arc::new);
+    }
+
+    pub fn mode(&self) -> TapeMode {
+        self.mode
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn path(&self, method: &str, url: &str, body: Option<&str>) -> PathBuf {
+        let mut hasher = Sha1::new();
+        hasher.update(method.as_bytes());
+        hasher.update(b" ");
+        hasher.update(url.as_bytes());
+        if let Some(b) = body {
+            hasher.update(b"\n");
+            hasher.update(b.as_bytes());
+        }
+        let hash = format!("{:x}", hasher.finalize());
+        let host = Url::parse(url)
+            .ok()
+            .and_then(|u| u.host_str().map(|h| h.replace(':', "_")))
+            .unwrap_or_else(|| "_".to_string());
+        self.dir
+            .join(host)
+            .join(format!("{}_{}.json", method.to_ascii_lowercase(), &hash[..16]))
+    }
+
+    /// Grabación de la petición, o `None` si no existe.
+    pub fn replay(&self, method: &str, url: &str, body: Option<&str>) -> Option<Recording> {
+        let path = self.path(method, url, body);
+        if path.exists() {
+            return Recording::load(&path);
+        }
+        // Fixtures con nombre libre en el directorio del host
+        let mut candidates: Vec<PathBuf> = std::fs::read_dir(path.parent()?)
+            .ok()?
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.extension().is_some_and(|ext| ext == "json"))
+            .collect();
+        candidates.sort();
+        candidates
+            .iter()
+            .filter_map(|p| Recording::load(p))
+            .find(|rec| rec.matches(method, url, body))
+    }
+
+    /// Error estándar cuando en `replay` falta la grabación.
+    pub fn miss_message(&self, method: &str, url: &str) -> String {
+        format!("replay: no hay grabación para {} {} en {}", method, url, self.dir.display())
+    }
+
+    pub fn record(&self, method: &str, url: &str, body: Option<&str>, status: u16, headers: &HeaderMap, response: &str) {
+        let path = self.path(method, url, body);
+        let rec = Recording {
+            method: method.to_string(),
+            url: url.to_string(),
+            request_body: None,
+            request_digest: body.map(body_digest),
+            status,
+            headers: KEPT_HEADERS
+                .iter()
+                .filter_map(|k| Some((k.to_string(), headers.get(*k)?.to_str().ok()?.to_string())))
+                .collect(),
+            body: response.to_string(),
+            body_file: None,
+        };
+        let result = path
+            .parent()
+            .map(std::fs::create_dir_all)
+            .unwrap_or(Ok(()))
+            .and_then(|_| serde_json::to_vec_pretty(&rec).map_err(std::io::Error::other))
+            .and_then(|bytes| std::fs::write(&path, bytes));
+        if let Err(e) = result {
+            warn!("⚠️ No se pudo grabar {} {}: {}", method, url, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `record` no deja el cuerpo (el CV va en el prompt) y `replay` casa por su digest.
+    #[test]
+    fn recorded_bodies_are_digested() {
+        let dir = std::env::temp_dir().join(format!("job_hunter_tape_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let tape = HttpTape::new(TapeMode::Record, &dir);
+        let url = "http://localhost:11434/api/generate";
+        let body = r#"{"prompt":"Candidate:\nJane Doe, jane@example.com, +34 600 000 000"}"#;
+        tape.record("POST", url, Some(body), 200, &HeaderMap::new(), r#"{"response":"{}"}"#);
+
+        let files: Vec<PathBuf> = std::fs::read_dir(dir.join("localhost")).unwrap().flatten().map(|e| e.path()).collect();
+        assert_eq!(files.len(), 1);
+        let saved = std::fs::read_to_string(&files[0]).unwrap();
+        assert!(!saved.contains("Jane") && !saved.contains("request_body"), "{saved}");
+
+        let replay = HttpTape::new(TapeMode::Replay, &dir);
+        assert_eq!(replay.replay("POST", url, Some(body)).unwrap().body, r#"{"response":"{}"}"#);
+        assert!(replay.replay("POST", url, Some("{}")).is_none());
+
+        // También con un nombre de fichero libre, que no lleva el hash del cuerpo
+        std::fs::rename(&files[0], dir.join("localhost/generate.json")).unwrap();
+        assert!(replay.replay("POST", url, Some(body)).is_some());
+        assert!(replay.replay("POST", url, Some("{}")).is_none());
+        assert!(replay.replay("POST", url, None).is_none());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,311 @@
+use job_hunter_core::{SalaryPeriod, SalaryRange};
+use regex::{Captures, Regex};
+use serde_json::Value;
+use std::sync::OnceLock;
+
+/// Parejas (mínimo, máximo) numéricas que exponen las APIs de los boards
+/// (RemoteOK, Himalayas, Jobicy, ...).
+const RANGE_KEYS: &[(&str, &str)] = &[
+    ("salary_min", "salary_max"),
+    ("minSalary", "maxSalary"),
+    ("annualSalaryMin", "annualSalaryMax"),
+    ("salary_from", "salary_to"),
+];
+const CURRENCY_KEYS: &[&str] = &["salary_currency", "salaryCurrency", "currency"];
+const PERIOD_KEYS: &[&str] = &["salary_period", "salaryPeriod", "salary_type"];
+/// Campos de texto libre ("$100k - $120k", "€50.000")
+const TEXT_KEYS: &[&str] = &["salary", "salary_range", "salaryRange", "compensation"];
+/// Caracteres de contexto que se miran alrededor de una cifra en texto libre.
+const CONTEXT_CHARS: usize = 60;
+
+fn salary_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        let cur = r"(?:\b(?:us|c|a)\$|[$€£¥]|\b(?:usd|eur|gbp|chf|cad|aud|nzd|jpy|inr|brl|mxn|pln|sek|nok|dkk|czk|sgd|zar)\b)";
+        let post = r"(?:[$€£¥]|\b(?:usd|eur|gbp|chf|cad|aud|nzd|jpy|inr|brl|mxn|pln|sek|nok|dkk|czk|sgd|zar|euros?|dollars?|d[óo]lares)\b)";
+        let num = r"\d{1,3}(?:[.,\u{a0}\u{202f}]\d{3})+|\d+(?:[.,]\d+)?";
+        Regex::new(&format!(
+            r"(?ix)
+            (?P<cur1>{cur})?\s*(?P<a>{num})\s*(?P<ak>k\b)?
+            (?:\s*(?:-|–|—|to|a|hasta)\s*(?P<cur2>{cur})?\s*(?P<b>{num})\s*(?P<bk>k\b)?)?
+            \s*(?P<cur3>{post})?"
+        ))
+        .expect("salary regex")
+    })
+}
+
+/// Cifras que no son un sueldo: "$5M", "$30 million", "€2bn".
+fn magnitude_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"(?i)^\s*(?:m|mm|mn|million|millones|mill[oó]n|b|bn|billion|mil\s+millones)\b")
+            .expect("magnitude regex")
+    })
+}
+
+/// Contexto de financiación, presupuestos o ayudas ("raised $5M", "$1,500 learning budget").
+fn noise_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(
+            r"(?i)\b(?:fund(?:ing|ed|raise|raising)?|rais(?:e|ed|ing)|series\s+[a-f]|seed|budgets?|stipends?|allowances?|valuation|revenue|invest(?:ment|ors?)|grants?|presupuestos?|financiaci[oó]n|ronda|inversi[oó]n|becas?|ayudas?)\b",
+        )
+        .expect("noise regex")
+    })
+}
+
+/// Palabras que confirman que una cifra sin periodo es un sueldo.
+fn salary_context_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(
+            r"(?i)\b(?:salar(?:y|ies|io|ial)|sueldos?|pay|paid|compensation|wages?|remunera\w*|retribuci\w*|ote|base|tarifa)\b",
+        )
+        .expect("salary context regex")
+    })
+}
+
+fn period_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(
+            r"(?ix)^\s*(?:
+                (?P<h>/\s*(?:h|hr|hour|hora)\b|per\s+hour|an\s+hour|hourly|por\s+hora|la\s+hora)
+              | (?P<d>/\s*(?:d|day|d[íi]a)\b|per\s+day|a\s+day|daily|por\s+d[íi]a|diarios?)
+              | (?P<m>/\s*(?:mo|month|mes)\b|per\s+month|a\s+month|monthly|mensual(?:es)?|al\s+mes)
+              | (?P<y>/\s*(?:y|yr|year|a[ñn]o|annum)\b|per\s+(?:year|annum)|a\s+year|annual(?:ly)?|anual(?:es)?|al\s+a[ñn]o|brutos?|gross|p\.?\s*a\.?)
+            )",
+        )
+        .expect("period regex")
+    })
+}
+
+/// Extrae un `SalaryRange` de texto libre: "$120k–150k/yr", "€60.000 brutos",
+/// "45 USD/h"... Exige un símbolo o código de divisa para evitar falsos
+/// positivos ("5 years", "2024"), descarta cifras de financiación o
+/// presupuesto ("raised $5M", "$1,500 learning budget") y solo deduce el
+/// periodo por magnitud si hay una palabra de sueldo cerca.
+pub fn parse_salary_text(text: &str) -> Option<SalaryRange> {
+    salary_regex()
+        .captures_iter(text)
+        .find_map(|caps| range_from_captures(text, &caps, None))
+}
+
+/// Igual que [`parse_salary_text`] pero para campos que ya sabemos que son un
+/// salario: si no hay divisa se asume `default_currency`.
+pub fn parse_salary_field(text: &str, default_currency: &str) -> Option<SalaryRange> {
+    salary_regex()
+        .captures_iter(text)
+        .find_map(|caps| range_from_captures(text, &caps, Some(default_currency)))
+}
+
+/// Lee el salario de los campos nativos de un item JSON del board.
+pub fn salary_from_json(item: &Value) -> Option<SalaryRange> {
+    let currency = CURRENCY_KEYS
+        .iter()
+        .find_map(|k| item.get(*k).and_then(|v| v.as_str()))
+        .and_then(currency_code)
+        .unwrap_or("USD");
+
+    for (min_key, max_key) in RANGE_KEYS {
+        let min = item.get(*min_key).and_then(json_amount).filter(|v| *v > 0.0);
+        let max = item.get(*max_key).and_then(json_amount).filter(|v| *v > 0.0);
+        if min.is_none() && max.is_none() {
+            continue;
+        }
+        let (min, max) = (min.or(max)?, max.or(min)?);
+        let period = PERIOD_KEYS
+            .iter()
+            .find_map(|k| item.get(*k).and_then(|v| v.as_str()))
+            .and_then(|p| period_from_text(&format!(" {}", p)))
+            .unwrap_or_else(|| period_from_magnitude(min));
+        return Some(build_range(min, max, currency, period));
+    }
+
+    TEXT_KEYS
+        .iter()
+        .filter_map(|k| item.get(*k).and_then(|v| v.as_str()))
+        .find_map(|s| parse_salary_field(s, currency))
+}
+
+/// Salario de APIs que dan mínimo, máximo, divisa e intervalo por separado
+/// (Lever `salaryRange`, Ashby `summaryComponents`).
+pub fn salary_from_bounds(min: &Value, max: &Value, currency: &str, interval: Option<&str>) -> Option<SalaryRange> {
+    let min = json_amount(min).filter(|v| *v > 0.0);
+    let max = json_amount(max).filter(|v| *v > 0.0);
+    let (min, max) = (min.or(max)?, max.or(min)?);
+    let currency = currency_code(currency).unwrap_or("USD");
+    let period = interval
+        .and_then(period_from_interval)
+        .unwrap_or_else(|| period_from_magnitude(min));
+    Some(build_range(min, max, currency, period))
+}
+
+fn range_from_captures(text: &str, caps: &Captures, default_currency: Option<&str>) -> Option<SalaryRange> {
+    let currency = ["cur1", "cur2", "cur3"]
+        .iter()
+        .find_map(|n| caps.name(n))
+        .and_then(|m| currency_code(m.as_str()))
+        .or(default_currency)?;
+
+    let b_k = caps.name("bk").is_some();
+    let mut a = parse_number(caps.name("a")?.as_str())?;
+    let b = match caps.name("b") {
+        Some(m) => {
+            let v = parse_number(m.as_str())?;
+            Some(if b_k { v * 1000.0 } else { v })
+        }
+        None => None,
+    };
+    // "120-150k": el sufijo k del máximo aplica también al mínimo
+    if caps.name("ak").is_some() || (b_k && a < 1000.0) {
+        a *= 1000.0;
+    }
+    if a <= 0.0 {
+        return None;
+    }
+
+    let matched = caps.get(0)?;
+    let rest = &text[matched.end()..];
+    if magnitude_regex().is_match(rest) {
+        return None;
+    }
+    // En texto libre la cifra tiene que parecer un sueldo por su contexto
+    let free_text = default_currency.is_none();
+    if free_text && noise_regex().is_match(&clause(text, matched.start(), matched.end())) {
+        return None;
+    }
+
+    let max = b.unwrap_or(a);
+    let period = match period_from_text(rest) {
+        Some(period) => period,
+        None if free_text && !salary_context_regex().is_match(&around(text, matched.start(), matched.end())) => {
+            return None
+        }
+        None => period_from_magnitude(a.min(max)),
+    };
+    Some(build_range(a, max, currency, period))
+}
+
+/// Texto a `CONTEXT_CHARS` caracteres de la cifra `[start, end)`, a cada lado.
+fn around(text: &str, start: usize, end: usize) -> String {
+    context(&text[..start], &text[end..])
+}
+
+/// Como [`around`] pero sin salir de la frase (o línea) de la cifra.
+fn clause(text: &str, start: usize, end: usize) -> String {
+    const BREAKS: &[&str] = &[". ", "\n", ";", "•", "|"];
+    let before = &text[..start];
+    let after = &text[end..];
+    let from = BREAKS
+        .iter()
+        .filter_map(|b| before.rfind(b).map(|i| i + b.len()))
+        .max()
+        .unwrap_or(0);
+    let to = BREAKS.iter().filter_map(|b| after.find(b)).min().unwrap_or(after.len());
+    context(&before[from..], &after[..to])
+}
+
+fn context(before: &str, after: &str) -> String {
+    let skip = before.chars().count().saturating_sub(CONTEXT_CHARS);
+    let before: String = before.chars().skip(skip).collect();
+    let after: String = after.chars().take(CONTEXT_CHARS).collect();
+    format!("{} {}", before, after)
+}
+
+fn build_range(a: f64, b: f64, currency: &str, period: SalaryPeriod) -> SalaryRange {
+    let (min, max) = if a <= b { (a, b) } else { (b, a) };
+    SalaryRange {
+        min: min.round() as u32,
+        max: max.round() as u32,
+        currency: currency.to_string(),
+        period,
+    }
+}
+
+fn period_from_text(rest: &str) -> Option<SalaryPeriod> {
+    let caps = period_regex().captures(rest)?;
+    if caps.name("h").is_some() {
+        Some(SalaryPeriod::Hourly)
+    } else if caps.name("d").is_some() {
+        Some(SalaryPeriod::Daily)
+    } else if caps.name("m").is_some() {
+        Some(SalaryPeriod::Monthly)
+    } else {
+        Some(SalaryPeriod::Yearly)
+    }
+}
+
+/// Intervalos de las APIs de ATS: "per-year-salary", "1 YEAR", "HOURLY"...
+fn period_from_interval(interval: &str) -> Option<SalaryPeriod> {
+    let i = interval.to_lowercase();
+    if i.contains("hour") {
+        Some(SalaryPeriod::Hourly)
+    } else if i.contains("day") || i.contains("daily") {
+        Some(SalaryPeriod::Daily)
+    } else if i.contains("month") {
+        Some(SalaryPeriod::Monthly)
+    } else if i.contains("year") || i.contains("annual") {
+        Some(SalaryPeriod::Yearly)
+    } else {
+        None
+    }
+}
+
+/// Sin periodo explícito, se deduce por magnitud de la cifra.
+fn period_from_magnitude(amount: f64) -> SalaryPeriod {
+    if amount < 300.0 {
+        SalaryPeriod::Hourly
+    } else if amount < 2_000.0 {
+        SalaryPeriod::Daily
+    } else if amount < 20_000.0 {
+        SalaryPeriod::Monthly
+    } else {
+        SalaryPeriod::Yearly
+    }
+}
+
+/// Código ISO de un símbolo o nombre de moneda conocido.
+pub(crate) fn currency_code(raw: &str) -> Option<&'static str> {
+    let code = match raw.trim().to_lowercase().as_str() {
+        "$" | "us$" | "usd" | "dollar" | "dollars" | "dolares" | "dólares" => "USD",
+        "€" | "eur" | "euro" | "euros" => "EUR",
+        "£" | "gbp" => "GBP",
+        "¥" | "jpy" => "JPY",
+        "c$" | "cad" => "CAD",
+        "a$" | "aud" => "AUD",
+        "chf" => "CHF",
+        "nzd" => "NZD",
+        "inr" => "INR",
+        "brl" => "BRL",
+        "mxn" => "MXN",
+        "pln" => "PLN",
+        "sek" => "SEK",
+        "nok" => "NOK",
+        "dkk" => "DKK",
+        "czk" => "CZK",
+        "sgd" => "SGD",
+        "zar" => "ZAR",
+        _ => return None,
+    };
+    Some(code)
+}
+
+/// "60.000" / "120,000" / "60 000" -> miles; "12.5" / "12,5" -> decimal.
+fn parse_number(raw: &str) -> Option<f64> {
+    let groups: Vec<&str> = raw.split(['.', ',', '\u{a0}', '\u{202f}']).collect();
+    let thousands = groups.len() > 1 && groups[1..].iter().all(|g| g.len() == 3);
+    if thousands {
+        groups.concat().parse().ok()
+    } else {
+        raw.replace(',', ".").parse().ok()
+    }
+}
+
+fn json_amount(v: &Value) -> Option<f64> {
+    match v {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => parse_number(s.trim()),
+        _ => None,
+    }
+}
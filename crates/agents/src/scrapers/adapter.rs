@@ -0,0 +1,300 @@
+use async_trait::async_trait;
+use job_hunter_core::*;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use crate::http::SourceClient;
+
+use super::dedup_by_id;
+use super::details::fetch_details;
+use super::paging::PageLimits;
+use super::query::SearchQuery;
+
+/// Enlaces de más en listados sin texto, por si falla algún detalle.
+const DETAIL_MARGIN: usize = 2;
+
+/// Dónde se aplican las keywords de la búsqueda.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeywordSearch {
+    /// El board busca en servidor: una consulta por keyword.
+    Native,
+    /// Sin búsqueda: se filtra en local sobre los items del listado.
+    Listing,
+    /// Sin búsqueda y el listado solo trae enlaces: se filtra tras el detalle.
+    Details,
+}
+
+/// Estado de la paginación de una consulta.
+#[derive(Debug, Clone, Default)]
+pub struct PageCursor {
+    /// Página actual, desde 0.
+    pub page: usize,
+    /// Items leídos hasta ahora en esta consulta.
+    pub offset: usize,
+    /// Tamaño de página pedido (cupo de la consulta).
+    pub limit: usize,
+    /// URL de la página siguiente si la respuesta anterior la daba.
+    pub next: Option<String>,
+}
+
+/// Ofertas de una página del listado.
+#[derive(Debug, Default)]
+pub struct Listing {
+    pub postings: Vec<RawJobPosting>,
+    /// Enlace a la página siguiente (`links.next`, `next`...).
+    pub next: Option<String>,
+}
+
+impl Listing {
+    pub fn new(postings: Vec<RawJobPosting>) -> Self {
+        Self { postings, next: None }
+    }
+
+    pub fn with_next(mut self, next: Option<String>) -> Self {
+        self.next = next;
+        self
+    }
+}
+
+/// Lo que necesita un adaptador durante una búsqueda: criterios, configuración
+/// efectiva de la fuente y su cliente HTTP.
+pub struct ScrapeContext {
+    pub criteria: Arc<SearchCriteria>,
+    pub settings: SourceSettings,
+    pub client: SourceClient,
+    pub(crate) query: SearchQuery,
+    pub(crate) limits: PageLimits,
+}
+
+/// Ajustes de una fuente declarada en fichero (`sources.json`, `recipes.json`).
+/// Solo se usan si la petición no trae configuración para la fuente.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct FileSettings {
+    pub enabled: bool,
+    pub delay_ms: Option<u64>,
+    pub max_results: Option<u32>,
+    pub max_pages: Option<u32>,
+    pub fetch_details: bool,
+}
+
+impl FileSettings {
+    /// Configuración de `source` en la petición o, si no viene, la por
+    /// defecto con los valores del fichero.
+    pub(crate) fn resolve(self, criteria: &SearchCriteria, source: &JobSource) -> SourceSettings {
+        if let Some(cfg) = criteria.sources_config.iter().find(|s| &s.source == source) {
+            return cfg.clone();
+        }
+        let mut cfg = SourceSettings::defaults(source.clone());
+        cfg.enabled = self.enabled;
+        cfg.delay_ms = self.delay_ms.unwrap_or(cfg.delay_ms);
+        cfg.max_results = self.max_results.unwrap_or(cfg.max_results);
+        cfg.max_pages = self.max_pages.unwrap_or(cfg.max_pages);
+        cfg.fetch_details = self.fetch_details;
+        cfg
+    }
+}
+
+/// Una fuente de ofertas: cómo pedir sus listados, cómo leerlos y, si hace
+/// falta, cómo completar cada oferta con su detalle. El resto (mensajes,
+/// configuración, activación, dedup y cupo) lo pone [`SourceAgent`].
+#[async_trait]
+pub trait JobSourceAdapter: Send + Sync {
+    fn source(&self) -> JobSource;
+
+    /// Nombre corto para los logs ("RemoteOK").
+    fn label(&self) -> &str;
+
+    fn keyword_search(&self) -> KeywordSearch {
+        KeywordSearch::Native
+    }
+
+    /// `true` si el board filtra por nivel de experiencia en la consulta; si no,
+    /// se descartan en local las ofertas cuyo título indica otro nivel.
+    fn level_search(&self) -> bool {
+        false
+    }
+
+    /// Configuración de la petición o, si no viene, la por defecto de la fuente.
+    fn settings(&self, criteria: &SearchCriteria) -> SourceSettings {
+        criteria.source_settings(&self.source())
+    }
+
+    /// Cliente HTTP de la fuente (timeouts, robots.txt).
+    fn client(&self, settings: &SourceSettings) -> SourceClient {
+        SourceClient::new(settings)
+    }
+
+    /// URL de la página `cursor` para la keyword `term`; `None` si no hay más.
+    /// Los adaptadores con `fetch_listing` propio pueden no implementarla.
+    fn page_url(&self, _ctx: &ScrapeContext, _term: Option<&str>, _cursor: &PageCursor) -> Option<String> {
+        None
+    }
+
+    /// Descarga una página del listado.
+    async fn fetch_page(&self, ctx: &ScrapeContext, url: &str) -> Result<String, AgentError> {
+        let res = ctx.client.get(url).send().await?;
+        if !res.status().is_success() {
+            return Err(AgentError::Scraping(format!("HTTP {}", res.status())));
+        }
+        Ok(res.into_text())
+    }
+
+    /// Ofertas de una página del listado (JSON, feed o HTML). Obligatoria
+    /// salvo que el adaptador tenga `fetch_listing` propio.
+    fn parse_items(&self, url: &str, _body: &str) -> Result<Listing, AgentError> {
+        Err(AgentError::Scraping(format!("[{}] Sin parser de listado para {}", self.label(), url)))
+    }
+
+    /// Recorre las consultas y páginas del listado hasta llenar el cupo de
+    /// cada keyword. Si falla una página que no es la primera se conserva lo
+    /// leído hasta ahí.
+    async fn fetch_listing(&self, ctx: &ScrapeContext) -> Result<Vec<RawJobPosting>, AgentError> {
+        let mode = self.keyword_search();
+        let terms = if mode == KeywordSearch::Native { ctx.query.terms() } else { vec![None] };
+        let per_term = match mode {
+            KeywordSearch::Native => ctx.limits.per_term(terms.len()),
+            // El filtro local descarta parte: se lee el cupo completo
+            KeywordSearch::Listing => ctx.limits.max_results,
+            KeywordSearch::Details => ctx.limits.max_results + DETAIL_MARGIN,
+        };
+
+        if mode == KeywordSearch::Native && !ctx.query.overflow().is_empty() {
+            info!(
+                "🔎 [{}] Solo se consultan {} keywords; {:?} se buscan en el listado general.",
+                self.label(),
+                terms.len() - 1,
+                ctx.query.overflow()
+            );
+        }
+
+        let mut postings = Vec::new();
+        for term in terms {
+            let mut cursor = PageCursor { limit: per_term, ..Default::default() };
+            let mut found = 0;
+            for page in 0..ctx.limits.max_pages {
+                cursor.page = page;
+                let Some(url) = self.page_url(ctx, term, &cursor) else { break };
+                info!("📡 [{}] Descargando listado: {}", self.label(), url);
+
+                let body = match self.fetch_page(ctx, &url).await {
+                    Ok(body) => body,
+                    Err(e) if page == 0 => return Err(e),
+                    Err(e) => {
+                        warn!("⚠️ [{}] Fin de paginación en página {}: {}", self.label(), page + 1, e);
+                        break;
+                    }
+                };
+                let listing = self.parse_items(&url, &body)?;
+                if listing.postings.is_empty() {
+                    break;
+                }
+                cursor.offset += listing.postings.len();
+                cursor.next = listing.next;
+
+                for posting in listing.postings {
+                    if found >= per_term {
+                        break;
+                    }
+                    let wanted = match (mode, term) {
+                        (KeywordSearch::Listing, _) => ctx.query.matches(&posting),
+                        (KeywordSearch::Native, None) => ctx.query.matches_overflow(&posting),
+                        _ => true,
+                    };
+                    if !wanted {
+                        continue;
+                    }
+                    postings.push(posting);
+                    found += 1;
+                }
+                if found >= per_term {
+                    break;
+                }
+            }
+        }
+        Ok(postings)
+    }
+
+    /// Completa las ofertas con su página de detalle. Por defecto solo las
+    /// que son un teaser y si la fuente tiene `fetch_details`.
+    async fn fetch_details(
+        &self,
+        ctx: &ScrapeContext,
+        mut postings: Vec<RawJobPosting>,
+    ) -> Result<Vec<RawJobPosting>, AgentError> {
+        postings.truncate(ctx.limits.max_results);
+        if ctx.settings.fetch_details {
+            fetch_details(&ctx.client, self.label(), &mut postings).await;
+        }
+        Ok(postings)
+    }
+}
+
+/// Agente de scraping para cualquier [`JobSourceAdapter`].
+pub struct SourceAgent {
+    adapter: Arc<dyn JobSourceAdapter>,
+    name: String,
+}
+
+impl SourceAgent {
+    pub fn new(adapter: impl JobSourceAdapter + 'static) -> Self {
+        Self::from_adapter(Arc::new(adapter))
+    }
+
+    pub fn from_adapter(adapter: Arc<dyn JobSourceAdapter>) -> Self {
+        let name = format!("scraper_{}", adapter.source().slug());
+        Self { adapter, name }
+    }
+
+    async fn scrape(&self, criteria: Arc<SearchCriteria>) -> Result<Vec<RawJobPosting>, AgentError> {
+        let adapter = &self.adapter;
+        let settings = adapter.settings(&criteria);
+        if !settings.enabled {
+            info!("ℹ️ [{}] Agente desactivado por configuración.", adapter.label());
+            return Ok(vec![]);
+        }
+
+        let ctx = ScrapeContext {
+            client: adapter.client(&settings),
+            query: SearchQuery::from_criteria(&criteria),
+            limits: PageLimits::from_settings(&settings),
+            criteria,
+            settings,
+        };
+        let mut postings = adapter.fetch_listing(&ctx).await?;
+        dedup_by_id(&mut postings);
+        let mut postings = adapter.fetch_details(&ctx, postings).await?;
+        if adapter.keyword_search() == KeywordSearch::Details {
+            ctx.query.retain_matching(adapter.label(), &mut postings);
+        }
+        if !adapter.level_search() {
+            ctx.query.retain_level(adapter.label(), &mut postings);
+        }
+        postings.truncate(ctx.limits.max_results);
+        Ok(postings)
+    }
+}
+
+#[async_trait]
+impl Agent for SourceAgent {
+    async fn process(&self, msg: AgentMessage) -> Result<AgentMessage, AgentError> {
+        if let AgentMessage::StartScraping(criteria) = msg {
+            let postings = self.scrape(criteria).await?;
+            info!("📂 [{}] Éxito: {} ofertas extraídas.", self.adapter.label(), postings.len());
+            Ok(AgentMessage::RawJobsScraped(postings))
+        } else {
+            Err(AgentError::Scraping("Msg inválido".into()))
+        }
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn source(&self) -> Option<JobSource> {
+        Some(self.adapter.source())
+    }
+
+    fn source_settings(&self, criteria: &SearchCriteria) -> Option<SourceSettings> {
+        Some(self.adapter.settings(criteria))
+    }
+}
@@ -0,0 +1,369 @@
+use async_trait::async_trait;
+use job_hunter_core::*;
+use serde_json::Value;
+use std::collections::HashMap;
+use tracing::{info, warn};
+
+use reqwest::StatusCode;
+
+use super::adapter::{JobSourceAdapter, KeywordSearch, ScrapeContext};
+use super::{json_native_id, json_str, load_json_config, parse_json};
+use crate::content::plain_text;
+use crate::dates::date_from_value;
+use crate::feed::decode_entities;
+use crate::salary::{parse_salary_text, salary_from_bounds};
+
+/// Fichero JSON con la watchlist por ATS (por defecto `watchlist.json`).
+const KEY_WATCHLIST_FILE: &str = "JOB_HUNTER_WATCHLIST_FILE";
+const DEFAULT_WATCHLIST_FILE: &str = "watchlist.json";
+
+/// Plataformas de selección (ATS) con board público en JSON por empresa.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ats {
+    Greenhouse,
+    Lever,
+    Ashby,
+    Workable,
+}
+
+impl Ats {
+    pub const ALL: [Ats; 4] = [Ats::Greenhouse, Ats::Lever, Ats::Ashby, Ats::Workable];
+
+    pub fn source(self) -> JobSource {
+        match self {
+            Ats::Greenhouse => JobSource::Greenhouse,
+            Ats::Lever => JobSource::Lever,
+            Ats::Ashby => JobSource::Ashby,
+            Ats::Workable => JobSource::Workable,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Ats::Greenhouse => "Greenhouse",
+            Ats::Lever => "Lever",
+            Ats::Ashby => "Ashby",
+            Ats::Workable => "Workable",
+        }
+    }
+
+    /// Prefijo y sufijo de la URL del board: `{prefijo}{slug}{sufijo}`.
+    fn endpoint(self) -> (&'static str, &'static str) {
+        match self {
+            Ats::Greenhouse => ("https://boards-api.greenhouse.io/v1/boards/", "/jobs?content=true"),
+            Ats::Lever => ("https://api.lever.co/v0/postings/", "?mode=json"),
+            Ats::Ashby => ("https://api.ashbyhq.com/posting-api/job-board/", "?includeCompensation=true"),
+            Ats::Workable => ("https://apply.workable.com/api/v1/widget/accounts/", "?details=true"),
+        }
+    }
+
+    fn board_url(self, slug: &str) -> String {
+        let (prefix, suffix) = self.endpoint();
+        format!("{}{}{}", prefix, urlencoding::encode(slug), suffix)
+    }
+
+    /// Ofertas del board de `company`.
+    fn parse_board(self, body: &str, company: &WatchedCompany) -> Result<Vec<RawJobPosting>, AgentError> {
+        let json: Value = parse_json(body)?;
+        let (items, board_name) = match self {
+            // Lever devuelve directamente el array de ofertas
+            Ats::Lever => (&json, None),
+            // Workable da el nombre de la cuenta junto a las ofertas
+            Ats::Workable => (&json["jobs"], json_str(&json, &["name"])),
+            Ats::Greenhouse | Ats::Ashby => (&json["jobs"], None),
+        };
+        let Some(items) = items.as_array() else {
+            return Err(AgentError::Scraping("Board sin lista de ofertas".into()));
+        };
+
+        let postings = items
+            .iter()
+            .filter_map(|job| {
+                let api_name = board_name.clone().or_else(|| json_str(job, &["company_name"]));
+                let company = company.display_name(api_name);
+                match self {
+                    Ats::Greenhouse => greenhouse_posting(job, company),
+                    Ats::Lever => lever_posting(job, company),
+                    Ats::Ashby => ashby_posting(job, company),
+                    Ats::Workable => workable_posting(job, company),
+                }
+            })
+            .collect();
+        Ok(postings)
+    }
+}
+
+/// Entrada de la watchlist: `slug` o `slug:Nombre visible`.
+#[derive(Debug, Clone)]
+pub struct WatchedCompany {
+    pub slug: String,
+    pub name: Option<String>,
+}
+
+impl WatchedCompany {
+    pub fn parse(entry: &str) -> Option<Self> {
+        let (slug, name) = match entry.split_once(':') {
+            Some((slug, name)) => (slug, Some(name.trim().to_string()).filter(|n| !n.is_empty())),
+            None => (entry, None),
+        };
+        let slug = slug.trim().to_string();
+        (!slug.is_empty()).then_some(Self { slug, name })
+    }
+
+    /// Nombre de la watchlist, si no el que da el board, si no el slug legible.
+    fn display_name(&self, board_name: Option<String>) -> String {
+        self.name.clone().or(board_name).unwrap_or_else(|| humanize(&self.slug))
+    }
+}
+
+/// "acme-corp" -> "Acme Corp"
+fn humanize(slug: &str) -> String {
+    slug.split(['-', '_', '.'])
+        .filter(|w| !w.is_empty())
+        .map(|w| {
+            let mut chars = w.chars();
+            chars
+                .next()
+                .map(|c| c.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Watchlist por defecto leída de `JOB_HUNTER_WATCHLIST_FILE`: slugs por ATS
+/// (`{ "greenhouse": ["gitlab"], "lever": ["netflix:Netflix"] }`). Ver
+/// `watchlist.example.json`. Se usa si la petición no trae `companies`.
+pub fn load_watchlist() -> HashMap<String, Vec<String>> {
+    let Some((_, watchlist)) = load_json_config::<HashMap<String, Vec<String>>>(KEY_WATCHLIST_FILE, DEFAULT_WATCHLIST_FILE) else {
+        return HashMap::new();
+    };
+    for (ats, companies) in &watchlist {
+        info!("👀 Watchlist de {} cargada: {} empresas", ats, companies.len());
+    }
+    watchlist
+}
+
+/// Board público de un ATS para las empresas de la watchlist. La empresa
+/// viene ya rellena en cada oferta y las keywords se filtran en local.
+pub struct AtsSource {
+    ats: Ats,
+    /// Empresas por defecto (watchlist.json) si la petición no trae `companies`.
+    watchlist: Vec<String>,
+}
+
+impl AtsSource {
+    pub fn new(ats: Ats) -> Self {
+        Self { ats, watchlist: Vec::new() }
+    }
+
+    pub fn with_watchlist(mut self, companies: Vec<String>) -> Self {
+        self.watchlist = companies;
+        self
+    }
+}
+
+#[async_trait]
+impl JobSourceAdapter for AtsSource {
+    fn source(&self) -> JobSource {
+        self.ats.source()
+    }
+
+    fn label(&self) -> &str {
+        self.ats.label()
+    }
+
+    fn keyword_search(&self) -> KeywordSearch {
+        KeywordSearch::Listing
+    }
+
+    /// Sin empresas que vigilar la fuente queda desactivada.
+    fn settings(&self, criteria: &SearchCriteria) -> SourceSettings {
+        let mut settings = criteria.source_settings(&self.source());
+        if settings.companies.is_empty() {
+            settings.companies = self.watchlist.clone();
+        }
+        if settings.companies.is_empty() {
+            settings.enabled = false;
+        }
+        settings
+    }
+
+    /// Recorre el board de cada empresa (una "página" por empresa) con un
+    /// cupo repartido entre ellas. Un 404 (slug mal escrito, empresa que
+    /// cambió de ATS) solo se avisa; un board caído no invalida el resto y la
+    /// fuente solo falla si ningún board responde por errores de red o parseo.
+    async fn fetch_listing(&self, ctx: &ScrapeContext) -> Result<Vec<RawJobPosting>, AgentError> {
+        let companies: Vec<WatchedCompany> =
+            ctx.settings.companies.iter().filter_map(|c| WatchedCompany::parse(c)).collect();
+        let per_company = ctx.limits.per_term(companies.len());
+
+        let mut postings = Vec::new();
+        let mut last_error = None;
+        let mut loaded = 0;
+        for company in &companies {
+            let url = self.ats.board_url(&company.slug);
+            info!("📡 [{}] Descargando board de {}: {}", self.label(), company.slug, url);
+
+            let board = match ctx.client.get(&url).send().await {
+                Ok(res) if res.status() == StatusCode::NOT_FOUND => {
+                    warn!("⚠️ [{}] '{}' no tiene board en {} (404): se omite.", self.label(), company.slug, self.label());
+                    continue;
+                }
+                Ok(res) if !res.status().is_success() => Err(AgentError::Scraping(format!("HTTP {}", res.status()))),
+                Ok(res) => self.ats.parse_board(res.text(), company),
+                Err(e) => Err(e),
+            };
+            match board {
+                Ok(board) => {
+                    loaded += 1;
+                    postings.extend(board.into_iter().filter(|p| ctx.query.matches(p)).take(per_company));
+                }
+                Err(e) => {
+                    warn!("⚠️ [{}] Board '{}' no disponible: {}", self.label(), company.slug, e);
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        match last_error {
+            Some(e) if loaded == 0 => Err(e),
+            _ => Ok(postings),
+        }
+    }
+}
+
+// --- MAPEO DE CAMPOS POR ATS ---
+
+/// Texto no vacío en `item[key]`.
+fn text(item: &Value, key: &str) -> Option<String> {
+    item.get(key)
+        .and_then(|v| v.as_str())
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// Nombres de una lista de objetos (`departments`, `offices`...).
+fn names(item: &Value, key: &str) -> Vec<String> {
+    item.get(key)
+        .and_then(|v| v.as_array())
+        .map(|a| a.iter().filter_map(|v| text(v, "name")).collect())
+        .unwrap_or_default()
+}
+
+/// `Some(true)` si la ubicación indica remoto; si no, no se sabe.
+fn remote_hint(location: Option<&str>) -> Option<bool> {
+    location
+        .filter(|l| contains_term(l, "remote") || contains_term(l, "remoto"))
+        .map(|_| true)
+}
+
+/// Greenhouse: `content` es HTML escapado con entidades.
+fn greenhouse_posting(job: &Value, company: String) -> Option<RawJobPosting> {
+    let url = text(job, "absolute_url")?;
+    let location = job.get("location").and_then(|l| text(l, "name"));
+    let description = text(job, "content").map(|c| plain_text(&decode_entities(&c)));
+    let posted_at = ["first_published", "updated_at"]
+        .iter()
+        .find_map(|k| date_from_value(job.get(*k)?));
+
+    Some(
+        RawJobPosting::new(JobSource::Greenhouse, json_native_id(job, "id").as_deref(), &url, job.to_string())
+            .with_details(text(job, "title"), Some(company), Some(url.clone()))
+            .with_description(description, names(job, "departments"))
+            .with_posted_at(posted_at)
+            .with_location(location.clone(), remote_hint(location.as_deref())),
+    )
+}
+
+/// Lever: la descripción viene troceada en `descriptionPlain`, `lists` y `additionalPlain`.
+fn lever_posting(job: &Value, company: String) -> Option<RawJobPosting> {
+    let url = text(job, "hostedUrl")?;
+    let categories = job.get("categories").cloned().unwrap_or(Value::Null);
+
+    let mut sections: Vec<String> = text(job, "descriptionPlain").into_iter().collect();
+    for list in job.get("lists").and_then(|v| v.as_array()).into_iter().flatten() {
+        let items = text(list, "content").map(|c| plain_text(&c)).unwrap_or_default();
+        sections.push(format!("{}\n{}", text(list, "text").unwrap_or_default(), items).trim().to_string());
+    }
+    sections.extend(text(job, "additionalPlain"));
+    let description = Some(sections.join("\n\n"));
+
+    let tags = ["team", "department"].iter().filter_map(|k| text(&categories, k)).collect();
+    let location = text(&categories, "location");
+    let is_remote = match text(job, "workplaceType").as_deref() {
+        Some("remote") => Some(true),
+        Some("onsite") | Some("hybrid") => Some(false),
+        _ => remote_hint(location.as_deref()),
+    };
+    let salary = job.get("salaryRange").and_then(|s| {
+        let currency = text(s, "currency").unwrap_or_default();
+        salary_from_bounds(&s["min"], &s["max"], &currency, text(s, "interval").as_deref())
+    });
+
+    Some(
+        RawJobPosting::new(JobSource::Lever, json_native_id(job, "id").as_deref(), &url, job.to_string())
+            .with_details(text(job, "text"), Some(company), text(job, "applyUrl"))
+            .with_description(description, tags)
+            .with_salary(salary)
+            .with_posted_at(job.get("createdAt").and_then(date_from_value))
+            .with_location(location, is_remote)
+            .with_job_type(text(&categories, "commitment").and_then(|c| JobType::from_label(&c))),
+    )
+}
+
+/// Ashby: compensación estructurada en `compensation.summaryComponents`.
+fn ashby_posting(job: &Value, company: String) -> Option<RawJobPosting> {
+    let url = text(job, "jobUrl")?;
+    let description = text(job, "descriptionPlain").or_else(|| text(job, "descriptionHtml").map(|h| plain_text(&h)));
+    let tags = ["department", "team"].iter().filter_map(|k| text(job, k)).collect();
+    let location = text(job, "location");
+    let is_remote = job
+        .get("isRemote")
+        .and_then(|v| v.as_bool())
+        .or_else(|| remote_hint(location.as_deref()));
+
+    let compensation = job.get("compensation").cloned().unwrap_or(Value::Null);
+    let salary = compensation
+        .get("summaryComponents")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter(|c| text(c, "compensationType").as_deref() == Some("Salary"))
+        .find_map(|c| {
+            let currency = text(c, "currencyCode").unwrap_or_default();
+            salary_from_bounds(&c["minValue"], &c["maxValue"], &currency, text(c, "interval").as_deref())
+        })
+        .or_else(|| text(&compensation, "compensationTierSummary").and_then(|s| parse_salary_text(&s)));
+
+    Some(
+        RawJobPosting::new(JobSource::Ashby, json_native_id(job, "id").as_deref(), &url, job.to_string())
+            .with_details(text(job, "title"), Some(company), text(job, "applyUrl"))
+            .with_description(description, tags)
+            .with_salary(salary)
+            .with_posted_at(job.get("publishedAt").and_then(date_from_value))
+            .with_location(location, is_remote)
+            .with_job_type(text(job, "employmentType").and_then(|t| JobType::from_label(&t))),
+    )
+}
+
+/// Workable: ubicación repartida en `city`/`state`/`country`.
+fn workable_posting(job: &Value, company: String) -> Option<RawJobPosting> {
+    let url = text(job, "url").or_else(|| text(job, "shortlink"))?;
+    let location: Vec<String> = ["city", "state", "country"].iter().filter_map(|k| text(job, k)).collect();
+    let location = (!location.is_empty()).then(|| location.join(", "));
+    let is_remote = job
+        .get("telecommuting")
+        .and_then(|v| v.as_bool())
+        .or_else(|| remote_hint(location.as_deref()));
+    let tags = ["department", "function"].iter().filter_map(|k| text(job, k)).collect();
+
+    Some(
+        RawJobPosting::new(JobSource::Workable, json_native_id(job, "shortcode").as_deref(), &url, job.to_string())
+            .with_details(text(job, "title"), Some(company), text(job, "application_url"))
+            .with_description(text(job, "description").map(|d| plain_text(&d)), tags)
+            .with_posted_at(["published_on", "created_at"].iter().find_map(|k| date_from_value(job.get(*k)?)))
+            .with_location(location, is_remote)
+            .with_job_type(text(job, "employment_type").and_then(|t| JobType::from_label(&t))),
+    )
+}
@@ -0,0 +1,370 @@
+use async_trait::async_trait;
+use job_hunter_core::*;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use tracing::{info, warn};
+
+use super::adapter::{FileSettings, JobSourceAdapter, KeywordSearch, Listing, PageCursor, ScrapeContext};
+use super::paging::next_link;
+use super::{feed_listing, load_json_list, parse_json, tag_list, with_json_details};
+use crate::content::plain_text;
+use crate::dates::date_from_value;
+use crate::salary::parse_salary_field;
+
+/// Fichero JSON con las fuentes declarativas (por defecto `sources.json`).
+const KEY_SOURCES_FILE: &str = "JOB_HUNTER_SOURCES_FILE";
+const DEFAULT_SOURCES_FILE: &str = "sources.json";
+
+/// Definición de un board configurable sin recompilar. Ver `sources.example.json`.
+///
+/// La `url` admite los marcadores `{keyword}`, `{page}`, `{offset}` y `{limit}`.
+/// Si no contiene `{keyword}` el board se trata como "sin búsqueda" y se
+/// filtra en local por keywords.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CustomSourceDef {
+    /// Identificador de la fuente (`JobSource::Custom(name)`).
+    pub name: String,
+    pub url: String,
+    #[serde(default)]
+    pub format: CustomFormat,
+    /// Ruta (con puntos) al array de ofertas en la respuesta JSON; vacía = raíz.
+    #[serde(default)]
+    pub items_path: String,
+    #[serde(default)]
+    pub fields: FieldMap,
+    #[serde(default)]
+    pub pagination: Pagination,
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub delay_ms: Option<u64>,
+    #[serde(default)]
+    pub max_results: Option<u32>,
+    #[serde(default)]
+    pub max_pages: Option<u32>,
+    /// Descargar la página de cada oferta si el item solo trae un resumen.
+    #[serde(default)]
+    pub fetch_details: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CustomFormat {
+    #[default]
+    Json,
+    /// RSS 2.0 o Atom: los campos salen del propio feed.
+    Rss,
+}
+
+/// Rutas (con puntos) a cada campo dentro de un item JSON. Los campos sin
+/// ruta se buscan en las claves habituales de los boards.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct FieldMap {
+    pub url: String,
+    pub id: Option<String>,
+    pub title: Option<String>,
+    pub company: Option<String>,
+    pub date: Option<String>,
+    pub salary: Option<String>,
+    pub description: Option<String>,
+    /// Lista de tags o texto separado por comas.
+    pub tags: Option<String>,
+    /// Divisa por defecto de `salary` cuando el texto no la indica.
+    pub salary_currency: String,
+}
+
+impl Default for FieldMap {
+    fn default() -> Self {
+        Self {
+            url: "url".to_string(),
+            id: None,
+            title: None,
+            company: None,
+            date: None,
+            salary: None,
+            description: None,
+            tags: None,
+            salary_currency: "USD".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Pagination {
+    #[default]
+    None,
+    /// `{page}` en la URL, empezando en `start`.
+    Page {
+        #[serde(default = "default_first_page")]
+        start: u32,
+    },
+    /// `{offset}` en la URL: número de items ya leídos.
+    Offset,
+    /// URL de la siguiente página en la respuesta (`links.next` / `next` si no se indica).
+    NextLink {
+        #[serde(default)]
+        path: Option<String>,
+    },
+}
+
+fn default_enabled() -> bool {
+    true
+}
+fn default_first_page() -> u32 {
+    1
+}
+
+/// Lee las fuentes declarativas de `JOB_HUNTER_SOURCES_FILE` (o `sources.json`):
+/// una lista o `{ "sources": [...] }`.
+pub fn load_custom_sources() -> Vec<CustomSourceDef> {
+    let Some((path, defs)) = load_json_list::<CustomSourceDef>(KEY_SOURCES_FILE, DEFAULT_SOURCES_FILE, "sources") else {
+        return vec![];
+    };
+
+    defs.into_iter()
+        .filter_map(|mut d| {
+            d.name = slugify(&d.name);
+            if d.name.is_empty() || d.url.trim().is_empty() {
+                warn!("⚠️ Fuente personalizada sin nombre o URL en {}, se ignora.", path);
+                return None;
+            }
+            info!("🧩 Fuente personalizada cargada: {} ({:?})", d.name, d.format);
+            Some(d)
+        })
+        .collect()
+}
+
+pub(super) fn slugify(name: &str) -> String {
+    name.trim()
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect::<String>()
+        .trim_matches('_')
+        .to_string()
+}
+
+/// Valor en `path` ("data.jobs", "company.name", "items.0.url").
+fn value_at<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.')
+        .filter(|p| !p.is_empty())
+        .try_fold(value, |v, key| match key.parse::<usize>() {
+            Ok(i) if v.is_array() => v.get(i),
+            _ => v.get(key),
+        })
+}
+
+fn text_at(item: &Value, path: Option<&str>) -> Option<String> {
+    match value_at(item, path?)? {
+        Value::String(s) => Some(s.trim().to_string()).filter(|s| !s.is_empty()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// Adaptador para una fuente declarada en configuración.
+pub struct CustomSource {
+    def: CustomSourceDef,
+    source: JobSource,
+}
+
+impl CustomSource {
+    pub fn new(def: CustomSourceDef) -> Self {
+        Self {
+            source: JobSource::Custom(def.name.clone()),
+            def,
+        }
+    }
+
+    fn build_url(&self, term: Option<&str>, page: u32, offset: usize, limit: usize) -> String {
+        let keyword = term.map(|t| urlencoding::encode(t).into_owned()).unwrap_or_default();
+        self.def
+            .url
+            .replace("{keyword}", &keyword)
+            .replace("{page}", &page.to_string())
+            .replace("{offset}", &offset.to_string())
+            .replace("{limit}", &limit.to_string())
+    }
+
+    fn posting_from_item(&self, item: &Value) -> Option<RawJobPosting> {
+        let f = &self.def.fields;
+        let url = text_at(item, Some(&f.url))?;
+        let native_id = text_at(item, f.id.as_deref());
+
+        // Claves habituales primero; las rutas configuradas tienen prioridad
+        let mut posting = with_json_details(
+            RawJobPosting::new(self.source.clone(), native_id.as_deref(), url, item.to_string()),
+            item,
+        );
+        if f.title.is_some() || f.company.is_some() {
+            let title = text_at(item, f.title.as_deref()).or(posting.normalized.title.clone());
+            let company = text_at(item, f.company.as_deref()).or(posting.normalized.company.clone());
+            let apply_url = posting.normalized.apply_url.clone();
+            posting = posting.with_details(title, company, apply_url);
+        }
+        if f.description.is_some() || f.tags.is_some() {
+            let description = text_at(item, f.description.as_deref())
+                .map(|d| plain_text(&d))
+                .or(posting.normalized.description.clone());
+            let tags = match f.tags.as_deref().and_then(|p| value_at(item, p)) {
+                Some(v) => tag_list(v),
+                None => posting.normalized.tags.clone(),
+            };
+            posting = posting.with_description(description, tags);
+        }
+        if let Some(date) = f.date.as_deref().and_then(|p| value_at(item, p)).and_then(date_from_value) {
+            posting = posting.with_posted_at(Some(date));
+        }
+        if let Some(salary) = text_at(item, f.salary.as_deref()).and_then(|s| parse_salary_field(&s, &f.salary_currency)) {
+            posting = posting.with_salary(Some(salary));
+        }
+        Some(posting)
+    }
+}
+
+#[async_trait]
+impl JobSourceAdapter for CustomSource {
+    fn source(&self) -> JobSource {
+        self.source.clone()
+    }
+
+    fn label(&self) -> &str {
+        &self.def.name
+    }
+
+    fn keyword_search(&self) -> KeywordSearch {
+        if self.def.url.contains("{keyword}") {
+            KeywordSearch::Native
+        } else {
+            KeywordSearch::Listing
+        }
+    }
+
+    /// Configuración de la petición o, si no viene, la del fichero.
+    fn settings(&self, criteria: &SearchCriteria) -> SourceSettings {
+        let file = FileSettings {
+            enabled: self.def.enabled,
+            delay_ms: self.def.delay_ms,
+            max_results: self.def.max_results,
+            max_pages: self.def.max_pages,
+            fetch_details: self.def.fetch_details,
+        };
+        file.resolve(criteria, &self.source)
+    }
+
+    fn page_url(&self, _ctx: &ScrapeContext, term: Option<&str>, cursor: &PageCursor) -> Option<String> {
+        // Los feeds no paginan
+        if cursor.page > 0 && self.def.format == CustomFormat::Rss {
+            return None;
+        }
+        match &self.def.pagination {
+            Pagination::None if cursor.page > 0 => None,
+            Pagination::NextLink { .. } if cursor.page > 0 => cursor.next.clone(),
+            Pagination::Page { start } => Some(self.build_url(term, start + cursor.page as u32, cursor.offset, cursor.limit)),
+            _ => Some(self.build_url(term, 1, cursor.offset, cursor.limit)),
+        }
+    }
+
+    async fn fetch_page(&self, ctx: &ScrapeContext, url: &str) -> Result<String, AgentError> {
+        let mut req = ctx.client.get(url);
+        for (k, v) in &self.def.headers {
+            req = req.header(k.as_str(), v.as_str());
+        }
+        let res = req.send().await?;
+        if !res.status().is_success() {
+            return Err(AgentError::Scraping(format!("HTTP {}", res.status())));
+        }
+        Ok(res.into_text())
+    }
+
+    fn parse_items(&self, _url: &str, body: &str) -> Result<Listing, AgentError> {
+        if self.def.format == CustomFormat::Rss {
+            return Ok(feed_listing(self.source.clone(), body));
+        }
+        let json: Value = parse_json(body)?;
+        // Un `items_path` que no lleva a un array es un error de configuración,
+        // no un listado vacío; `null` sí cuenta como página sin ofertas
+        let items = match value_at(&json, &self.def.items_path) {
+            Some(Value::Array(items)) => items.as_slice(),
+            Some(Value::Null) => &[],
+            _ => {
+                return Err(AgentError::Scraping(format!(
+                    "items_path '{}' no es un array en la respuesta de {}",
+                    self.def.items_path, self.def.name
+                )))
+            }
+        };
+        let next = match &self.def.pagination {
+            Pagination::NextLink { path: Some(p) } => text_at(&json, Some(p)).filter(|u| u.starts_with("http")),
+            Pagination::NextLink { path: None } => next_link(&json),
+            _ => None,
+        };
+        let postings = items.iter().filter_map(|item| self.posting_from_item(item)).collect();
+        Ok(Listing::new(postings).with_next(next))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn source(url: &str) -> CustomSource {
+        CustomSource::new(CustomSourceDef {
+            name: "test".into(),
+            url: url.into(),
+            format: CustomFormat::Json,
+            items_path: "data.items".into(),
+            fields: FieldMap::default(),
+            pagination: Pagination::None,
+            headers: HashMap::new(),
+            enabled: true,
+            delay_ms: None,
+            max_results: None,
+            max_pages: None,
+            fetch_details: false,
+        })
+    }
+
+    #[test]
+    fn value_at_paths() {
+        let v = json!({ "data": { "items": [{ "url": "a" }, { "url": "b" }] }, "n": 3 });
+        assert_eq!(value_at(&v, "data.items.1.url"), Some(&json!("b")));
+        assert_eq!(value_at(&v, "n"), Some(&json!(3)));
+        assert_eq!(value_at(&v, ""), Some(&v));
+        assert_eq!(value_at(&v, "data.missing"), None);
+        assert_eq!(value_at(&v, "data.items.5"), None);
+        // Un índice solo se aplica a arrays
+        assert_eq!(value_at(&json!({ "0": "x" }), "0"), Some(&json!("x")));
+
+        assert_eq!(text_at(&v, Some("n")).as_deref(), Some("3"));
+        assert_eq!(text_at(&v, Some("data")), None);
+        assert_eq!(text_at(&v, None), None);
+    }
+
+    #[test]
+    fn build_url_placeholders() {
+        let s = source("https://x.test/jobs?q={keyword}&page={page}&from={offset}&size={limit}");
+        assert_eq!(s.build_url(Some("rust & go"), 2, 40, 20), "https://x.test/jobs?q=rust%20%26%20go&page=2&from=40&size=20");
+        assert_eq!(s.build_url(None, 1, 0, 10), "https://x.test/jobs?q=&page=1&from=0&size=10");
+        assert_eq!(source("https://x.test/feed").build_url(Some("rust"), 3, 0, 10), "https://x.test/feed");
+    }
+
+    #[test]
+    fn wrong_items_path_is_an_error() {
+        let s = source("https://x.test/jobs");
+        let err = s.parse_items("https://x.test/jobs", r#"{"jobs": [{"url": "https://x.test/1"}]}"#).unwrap_err();
+        assert!(err.to_string().contains("items_path 'data.items'"), "{err}");
+        assert!(s.parse_items("", r#"{"data": {"items": {"url": "x"}}}"#).is_err());
+
+        let empty = s.parse_items("", r#"{"data": {"items": null}}"#).unwrap();
+        assert!(empty.postings.is_empty());
+        let listing = s.parse_items("", r#"{"data": {"items": [{"url": "https://x.test/1"}]}}"#).unwrap();
+        assert_eq!(listing.postings.len(), 1);
+    }
+}
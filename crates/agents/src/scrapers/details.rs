@@ -0,0 +1,59 @@
+use job_hunter_core::RawJobPosting;
+use scraper::Html;
+use tracing::{info, warn};
+
+use crate::content::{html_text, job_text, main_element};
+use crate::http::SourceClient;
+use crate::structured::with_structured_data;
+
+/// Por debajo de estos caracteres de texto visible el contenido se considera
+/// un teaser y, si la fuente lo pide, se descarga el detalle.
+const MIN_CONTENT_CHARS: usize = 600;
+
+/// HTML del bloque principal de una página de detalle.
+fn main_job_body(html: &str) -> Option<String> {
+    let doc = Html::parse_document(html);
+    main_element(&doc).map(|el| el.html())
+}
+
+/// Sustituye el contenido de las ofertas que son solo un teaser por el cuerpo
+/// de su página de detalle. Las descargas pasan por robots.txt, el rate
+/// limiter del host y la caché de `client`; si una falla se conserva el teaser.
+pub(crate) async fn fetch_details(client: &SourceClient, label: &str, postings: &mut [RawJobPosting]) {
+    let client = client.clone().with_robots();
+    let (mut short, mut fetched) = (0, 0);
+
+    for posting in postings.iter_mut() {
+        if job_text(&posting.html_content).chars().count() >= MIN_CONTENT_CHARS {
+            continue;
+        }
+        short += 1;
+
+        let res = match client.get(&posting.url).header("Accept", "text/html,application/xhtml+xml").send().await {
+            Ok(res) if res.status().is_success() => res,
+            Ok(res) => {
+                warn!("⚠️ [{}] Detalle HTTP {}: {}", label, res.status(), posting.url);
+                continue;
+            }
+            Err(e) => {
+                warn!("⚠️ [{}] Detalle no disponible ({}): {}", label, e, posting.url);
+                continue;
+            }
+        };
+        // El JSON-LD suele ir en <head>: se lee de la página completa antes de recortarla
+        *posting = with_structured_data(posting.clone(), res.text());
+        match main_job_body(res.text()) {
+            Some(body) => {
+                // El detalle completo sustituye a la descripción del teaser
+                posting.normalized.description = Some(html_text(&body));
+                posting.html_content = body;
+                fetched += 1;
+            }
+            None => warn!("⚠️ [{}] Sin cuerpo reconocible en el detalle: {}", label, posting.url),
+        }
+    }
+
+    if short > 0 {
+        info!("🔎 [{}] Detalle descargado para {}/{} ofertas cortas.", label, fetched, short);
+    }
+}
@@ -1,92 +1,447 @@
 use async_trait::async_trait;
+use headless_chrome::{Browser, LaunchOptions, Tab};
 use job_hunter_core::*;
-use headless_chrome::{Browser, LaunchOptions};
-use std::sync::Arc;
+use scraper::{ElementRef, Html, Selector};
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, OnceLock};
 use std::time::Duration;
-use governor::{Quota, RateLimiter};
-use governor::clock::DefaultClock;
-use governor::state::{InMemoryState, NotKeyed};
-use nonzero_ext::nonzero;
-use tracing::{info, error};
-use uuid::Uuid;
-use chrono::Utc;
+use tracing::{info, warn};
+use url::Url;
 
-type AgentRateLimiter = RateLimiter<NotKeyed, InMemoryState, DefaultClock>;
+use super::adapter::{FileSettings, JobSourceAdapter, KeywordSearch, Listing, PageCursor, ScrapeContext};
+use super::custom::slugify;
+use super::load_json_list;
+use crate::http::SourceClient;
+use crate::structured::with_structured_data;
 
-pub struct DynamicScraperAgent {
-    limiter: Arc<AgentRateLimiter>,
+/// Fichero JSON con las recetas de sitios dinámicos (por defecto `recipes.json`).
+const KEY_RECIPES_FILE: &str = "JOB_HUNTER_RECIPES_FILE";
+const DEFAULT_RECIPES_FILE: &str = "recipes.json";
+/// Ruta del ejecutable de Chrome/Chromium si no se detecta solo.
+const KEY_CHROME_PATH: &str = "JOB_HUNTER_CHROME_PATH";
+/// El navegador compartido se cierra tras este tiempo sin uso (se relanza al volver a necesitarlo).
+const BROWSER_IDLE_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// Receta de un sitio que solo se puede leer con JavaScript (SPA). Ver
+/// `recipes.example.json`.
+///
+/// Si `start_url` contiene `{keyword}` se abre una vez por keyword; si no, se
+/// abre una vez y se filtra en local por keywords.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SiteRecipe {
+    /// Identificador de la fuente (`JobSource::Custom(name)`).
+    pub name: String,
+    pub start_url: String,
+    /// Elemento que indica que el listado ya se ha renderizado.
+    pub wait_selector: String,
+    /// Pasos tras cargar el listado: scroll infinito, botones "load more"...
+    #[serde(default)]
+    pub actions: Vec<RecipeAction>,
+    /// Enlaces a cada oferta en el listado (`a` o un contenedor con un `a[href]`).
+    pub job_link_selector: String,
+    /// Bloque con el contenido de la oferta en la página de detalle.
+    pub detail_selector: String,
+    #[serde(default)]
+    pub title_selector: Option<String>,
+    #[serde(default)]
+    pub company_selector: Option<String>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub delay_ms: Option<u64>,
+    #[serde(default)]
+    pub max_results: Option<u32>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RecipeAction {
+    /// Baja hasta el final de la página `times` veces.
+    Scroll {
+        #[serde(default = "default_times")]
+        times: u32,
+        #[serde(default = "default_pause_ms")]
+        pause_ms: u64,
+    },
+    /// Pulsa `selector` hasta `times` veces; para cuando el botón desaparece.
+    Click {
+        selector: String,
+        #[serde(default = "default_times")]
+        times: u32,
+        #[serde(default = "default_pause_ms")]
+        pause_ms: u64,
+    },
+    /// Espera a que aparezca `selector`.
+    WaitFor { selector: String },
+    Sleep { ms: u64 },
+}
+
+fn default_enabled() -> bool {
+    true
+}
+fn default_times() -> u32 {
+    1
+}
+fn default_pause_ms() -> u64 {
+    800
+}
+
+/// Lee las recetas de `JOB_HUNTER_RECIPES_FILE` (o `recipes.json`): una lista
+/// o `{ "recipes": [...] }`.
+pub fn load_site_recipes() -> Vec<SiteRecipe> {
+    let Some((path, recipes)) = load_json_list::<SiteRecipe>(KEY_RECIPES_FILE, DEFAULT_RECIPES_FILE, "recipes") else {
+        return vec![];
+    };
+
+    recipes
+        .into_iter()
+        .filter_map(|mut r| {
+            r.name = slugify(&r.name);
+            if let Err(e) = r.validate() {
+                warn!("⚠️ Receta '{}' inválida en {}: {}", r.name, path, e);
+                return None;
+            }
+            info!("🧭 Receta dinámica cargada: {}", r.name);
+            Some(r)
+        })
+        .collect()
+}
+
+impl SiteRecipe {
+    fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() || self.start_url.trim().is_empty() {
+            return Err("sin nombre o start_url".into());
+        }
+        for sel in [&self.wait_selector, &self.job_link_selector, &self.detail_selector]
+            .into_iter()
+            .chain(self.title_selector.as_ref())
+            .chain(self.company_selector.as_ref())
+        {
+            Selector::parse(sel).map_err(|e| format!("selector '{}': {}", sel, e))?;
+        }
+        Ok(())
+    }
+}
+
+// --- NAVEGADOR COMPARTIDO (pool blocking) ---
+
+/// Navegador headless del proceso, lanzado la primera vez que se necesita.
+/// `headless_chrome` es síncrono: todo su uso va por `spawn_blocking`.
+fn shared_browser() -> anyhow::Result<Browser> {
+    static BROWSER: OnceLock<Mutex<Option<Browser>>> = OnceLock::new();
+    let mut slot = BROWSER.get_or_init(Mutex::default).lock().unwrap();
+    if let Some(browser) = slot.as_ref() {
+        // Sigue vivo si responde; si no (crash o cierre por inactividad), se relanza
+        if browser.get_version().is_ok() {
+            return Ok(browser.clone());
+        }
+    }
+
+    info!("🌐 [Dynamic] Lanzando navegador headless...");
+    let options = LaunchOptions {
+        headless: true,
+        sandbox: false,
+        idle_browser_timeout: BROWSER_IDLE_TIMEOUT,
+        path: std::env::var(KEY_CHROME_PATH).ok().map(PathBuf::from),
+        ..Default::default()
+    };
+    let browser = Browser::new(options)?;
+    *slot = Some(browser.clone());
+    Ok(browser)
+}
+
+/// Carga de una página en una pestaña nueva del navegador compartido.
+struct PageLoad {
+    url: String,
+    user_agent: String,
+    timeout: Duration,
+    wait_selector: String,
+    actions: Vec<RecipeAction>,
+}
+
+impl PageLoad {
+    /// Renderiza la página en el pool blocking y devuelve su HTML.
+    async fn render(self) -> Result<String, AgentError> {
+        let url = self.url.clone();
+        tokio::task::spawn_blocking(move || self.render_blocking())
+            .await
+            .map_err(|e| AgentError::Scraping(format!("Tarea del navegador abortada: {}", e)))?
+            .map_err(|e| AgentError::Scraping(format!("Navegador en {}: {:#}", url, e)))
+    }
+
+    fn render_blocking(&self) -> anyhow::Result<String> {
+        let tab = shared_browser()?.new_tab()?;
+        let result = self.drive(&tab);
+        let _ = tab.close(false);
+        result
+    }
+
+    fn drive(&self, tab: &Arc<Tab>) -> anyhow::Result<String> {
+        tab.set_default_timeout(self.timeout);
+        tab.set_user_agent(&self.user_agent, None, None)?;
+        tab.navigate_to(&self.url)?.wait_until_navigated()?;
+        tab.wait_for_element(&self.wait_selector)?;
+
+        for action in &self.actions {
+            match action {
+                RecipeAction::Scroll { times, pause_ms } => {
+                    for _ in 0..*times {
+                        tab.evaluate("window.scrollTo(0, document.body.scrollHeight)", false)?;
+                        std::thread::sleep(Duration::from_millis(*pause_ms));
+                    }
+                }
+                RecipeAction::Click { selector, times, pause_ms } => {
+                    for _ in 0..*times {
+                        // Sin botón (ya no hay más que cargar): siguiente paso
+                        let Ok(button) = tab.find_element(selector) else { break };
+                        button.click()?;
+                        std::thread::sleep(Duration::from_millis(*pause_ms));
+                    }
+                }
+                RecipeAction::WaitFor { selector } => {
+                    tab.wait_for_element(selector)?;
+                }
+                RecipeAction::Sleep { ms } => std::thread::sleep(Duration::from_millis(*ms)),
+            }
+        }
+        tab.get_content()
+    }
+}
+
+/// Enlaces absolutos (sin repetir) de `selector` en el HTML del listado.
+fn extract_links(base: &Url, html: &str, selector: &str) -> Vec<String> {
+    let doc = Html::parse_document(html);
+    let (Ok(sel), Ok(a_sel)) = (Selector::parse(selector), Selector::parse("a[href]")) else {
+        return vec![];
+    };
+
+    let mut seen = HashSet::new();
+    let mut links = Vec::new();
+    for el in doc.select(&sel) {
+        let href = el
+            .value()
+            .attr("href")
+            .or_else(|| el.select(&a_sel).next().and_then(|a| a.value().attr("href")));
+        let Some(abs) = href.and_then(|h| base.join(h).ok()) else { continue };
+        if !matches!(abs.scheme(), "http" | "https") {
+            continue;
+        }
+        let abs = abs.to_string();
+        if seen.insert(abs.clone()) {
+            links.push(abs);
+        }
+    }
+    links
+}
+
+fn first_match<'a>(doc: &'a Html, selector: Option<&str>) -> Option<ElementRef<'a>> {
+    let sel = Selector::parse(selector?).ok()?;
+    doc.select(&sel).next()
 }
 
-impl DynamicScraperAgent {
-    pub fn new() -> Self {
-        // Máximo 5 peticiones cada 60s
-        let quota = Quota::with_period(Duration::from_secs(60)).unwrap().allow_burst(nonzero!(5u32));
+fn element_text(el: ElementRef<'_>) -> Option<String> {
+    let text = el.text().collect::<Vec<_>>().join(" ");
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    Some(text).filter(|t| !t.is_empty())
+}
+
+/// Fuente con navegador headless para una [`SiteRecipe`]: abre el listado,
+/// ejecuta sus acciones y devuelve una oferta por cada página de detalle.
+pub struct DynamicSource {
+    recipe: SiteRecipe,
+    source: JobSource,
+}
+
+impl DynamicSource {
+    pub fn new(recipe: SiteRecipe) -> Self {
         Self {
-            limiter: Arc::new(RateLimiter::direct(quota)),
+            source: JobSource::Custom(recipe.name.clone()),
+            recipe,
         }
     }
 
-    async fn scrape_spa_site(&self, url: &str, wait_selector: &str) -> anyhow::Result<String> {
-        self.limiter.until_ready().await;
+    fn page(&self, client: &SourceClient, url: &str, wait_selector: &str, actions: Vec<RecipeAction>) -> PageLoad {
+        PageLoad {
+            url: url.to_string(),
+            user_agent: client.user_agent().to_string(),
+            timeout: client.timeout(),
+            wait_selector: wait_selector.to_string(),
+            actions,
+        }
+    }
+}
+
+#[async_trait]
+impl JobSourceAdapter for DynamicSource {
+    fn source(&self) -> JobSource {
+        self.source.clone()
+    }
+
+    fn label(&self) -> &str {
+        &self.recipe.name
+    }
 
-        info!("🌐 [Dynamic] Lanzando navegador headless para: {}", url);
+    fn keyword_search(&self) -> KeywordSearch {
+        if self.recipe.start_url.contains("{keyword}") {
+            KeywordSearch::Native
+        } else {
+            KeywordSearch::Details
+        }
+    }
 
-        let options = LaunchOptions {
-            headless: true,
-            sandbox: false,
-            idle_browser_timeout: Duration::from_secs(30),
+    /// Configuración de la petición o, si no viene, la de la receta.
+    fn settings(&self, criteria: &SearchCriteria) -> SourceSettings {
+        let file = FileSettings {
+            enabled: self.recipe.enabled,
+            delay_ms: self.recipe.delay_ms,
+            max_results: self.recipe.max_results,
             ..Default::default()
         };
+        file.resolve(criteria, &self.source)
+    }
+
+    fn client(&self, settings: &SourceSettings) -> SourceClient {
+        SourceClient::new(settings).with_robots()
+    }
+
+    // Las recetas no paginan: el "cargar más" va en las acciones
+    fn page_url(&self, _ctx: &ScrapeContext, term: Option<&str>, cursor: &PageCursor) -> Option<String> {
+        if cursor.page > 0 {
+            return None;
+        }
+        let keyword = term.map(|t| urlencoding::encode(t).into_owned()).unwrap_or_default();
+        Some(self.recipe.start_url.replace("{keyword}", &keyword))
+    }
 
-        let browser = Browser::new(options)?;
-        let tab = browser.new_tab()?;
+    async fn fetch_page(&self, ctx: &ScrapeContext, url: &str) -> Result<String, AgentError> {
+        ctx.client.acquire(url).await?;
+        info!("🌐 [{}] Renderizando listado: {}", self.recipe.name, url);
+        self.page(&ctx.client, url, &self.recipe.wait_selector, self.recipe.actions.clone())
+            .render()
+            .await
+    }
 
-        tab.navigate_to(url)?;
-        
-        info!("⏳ [Dynamic] Esperando selector: {}", wait_selector);
-        // Espera máxima 10s por defecto en headless_chrome
-        tab.wait_for_element(wait_selector)?;
+    fn parse_items(&self, url: &str, html: &str) -> Result<Listing, AgentError> {
+        let base = Url::parse(url).map_err(|e| AgentError::Scraping(format!("URL inválida {}: {}", url, e)))?;
+        let postings = extract_links(&base, html, &self.recipe.job_link_selector)
+            .into_iter()
+            .map(|link| RawJobPosting::new(self.source.clone(), None, link, ""))
+            .collect();
+        Ok(Listing::new(postings))
+    }
 
-        // Scroll
-        tab.evaluate("window.scrollTo(0, document.body.scrollHeight)", false)?;
-        std::thread::sleep(Duration::from_millis(1000)); 
+    /// Una oferta por página de detalle.
+    async fn fetch_details(
+        &self,
+        ctx: &ScrapeContext,
+        links: Vec<RawJobPosting>,
+    ) -> Result<Vec<RawJobPosting>, AgentError> {
+        if links.is_empty() {
+            // El listado cargó pero el selector no encuentra nada: receta desfasada
+            return Err(AgentError::Scraping(format!(
+                "{}: ningún enlace coincide con '{}'",
+                self.recipe.name, self.recipe.job_link_selector
+            )));
+        }
 
-        let content = tab.get_content()?;
-        
-        Ok(content)
+        let mut postings = Vec::new();
+        for link in links.into_iter().map(|p| p.url) {
+            if postings.len() >= ctx.limits.max_results {
+                break;
+            }
+            if let Err(e) = ctx.client.acquire(&link).await {
+                warn!("⚠️ [{}] Saltando {}: {}", self.recipe.name, link, e);
+                continue;
+            }
+            let html = match self.page(&ctx.client, &link, &self.recipe.detail_selector, vec![]).render().await {
+                Ok(html) => html,
+                Err(e) => {
+                    warn!("⚠️ [{}] Saltando detalle por error: {}", self.recipe.name, e);
+                    continue;
+                }
+            };
+
+            let doc = Html::parse_document(&html);
+            let Some(detail) = first_match(&doc, Some(&self.recipe.detail_selector)) else {
+                warn!("⚠️ [{}] {} sin '{}'", self.recipe.name, link, self.recipe.detail_selector);
+                continue;
+            };
+            let title = first_match(&doc, self.recipe.title_selector.as_deref().or(Some("h1"))).and_then(element_text);
+            let company = first_match(&doc, self.recipe.company_selector.as_deref()).and_then(element_text);
+            let posting =
+                RawJobPosting::new(self.source.clone(), None, link, detail.html()).with_details(title, company, None);
+            postings.push(with_structured_data(posting, &html));
+        }
+        Ok(postings)
     }
 }
 
-#[async_trait]
-impl Agent for DynamicScraperAgent {
-    async fn process(&self, msg: AgentMessage) -> Result<AgentMessage, AgentError> {
-        match msg {
-            AgentMessage::StartScraping(_criteria) => {
-                // Configura aquí URLs de prueba
-                let target_url = "https://example.com/jobs"; 
-                
-                match self.scrape_spa_site(target_url, "body").await {
-                    Ok(html) => {
-                        let posting = RawJobPosting {
-                            id: Uuid::new_v4().to_string(),
-                            source: JobSource::Custom("DynamicSPA".to_string()),
-                            url: target_url.to_string(),
-                            html_content: html,
-                            scraped_at: Utc::now(),
-                        };
-                        Ok(AgentMessage::RawJobsScraped(vec![posting]))
-                    },
-                    Err(e) => {
-                        error!("❌ [Dynamic] Error: {}", e);
-                        Ok(AgentMessage::RawJobsScraped(vec![]))
-                    }
-                }
-            }
-            _ => Err(AgentError::Scraping("Mensaje no soportado".into())),
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RENDERED: &str = include_str!("../../tests/fixtures/dynamic/jobs-rendered.html");
+
+    fn recipe() -> SiteRecipe {
+        serde_json::from_value(serde_json::json!({
+            "name": "local_spa",
+            "start_url": "https://spa.example.com/jobs/list.html",
+            "wait_selector": "li.job-card",
+            "job_link_selector": ".job-card",
+            "detail_selector": "article.job-detail",
+            "company_selector": ".company"
+        }))
+        .unwrap()
+    }
+
+    /// Enlaces absolutos y sin repetir: relativos resueltos contra la página,
+    /// contenedores sin `href` propio, y sin esquemas que no sean http(s).
+    #[test]
+    fn links_from_rendered_listing() {
+        let base = Url::parse("https://spa.example.com/jobs/list.html").unwrap();
+        assert_eq!(
+            extract_links(&base, RENDERED, ".job-card"),
+            [
+                "https://spa.example.com/job-1.html",
+                "https://spa.example.com/jobs/job-2.html?ref=list#apply",
+                "https://jobs.other.test/job-3.html",
+            ]
+        );
+        assert!(extract_links(&base, RENDERED, ".missing").is_empty());
+        assert!(extract_links(&base, RENDERED, "li[").is_empty());
+    }
+
+    #[test]
+    fn parse_items_yields_one_link_posting_each() {
+        let source = DynamicSource::new(recipe());
+        let listing = source.parse_items("https://spa.example.com/jobs/list.html", RENDERED).unwrap();
+        assert_eq!(listing.postings.len(), 3);
+        assert!(listing.next.is_none());
+        for p in &listing.postings {
+            assert_eq!(p.source, JobSource::Custom("local_spa".into()));
+            assert!(p.html_content.is_empty() && p.normalized.title.is_none());
         }
+        assert!(source.parse_items("no es una url", RENDERED).is_err());
     }
 
-    fn name(&self) -> &str {
-        "scraper_dynamic_headless"
+    #[test]
+    fn validate_checks_name_url_and_selectors() {
+        assert!(recipe().validate().is_ok());
+
+        let mut r = recipe();
+        r.name = " ".into();
+        assert_eq!(r.validate().unwrap_err(), "sin nombre o start_url");
+        let mut r = recipe();
+        r.start_url = String::new();
+        assert!(r.validate().is_err());
+
+        let mut r = recipe();
+        r.detail_selector = "article[".into();
+        assert!(r.validate().unwrap_err().starts_with("selector 'article['"));
+        let mut r = recipe();
+        r.company_selector = Some("..company".into());
+        assert!(r.validate().unwrap_err().contains("..company"));
     }
-}
\ No newline at end of file
+}
@@ -1,126 +1,110 @@
-use async_trait::async_trait;
-use chrono::Utc;
 use job_hunter_core::*;
 use std::time::Duration;
-use tokio::time::sleep;
-use tracing::info;
-use uuid::Uuid;
 
-// Macro para generar agentes de APIs JSON repetitivas
-macro_rules! impl_json_scraper {
-    ($struct:ident, $source:expr, $name:expr, $url:expr, $json_path:expr, $url_key:expr) => {
+use crate::http::SourceClient;
+
+use super::adapter::{JobSourceAdapter, KeywordSearch, Listing, PageCursor, ScrapeContext};
+use super::paging::next_link;
+use super::query::with_query;
+use super::{feed_listing, json_native_id, parse_json, with_json_details};
+
+// Macro para generar adaptadores de APIs JSON repetitivas.
+// `$search_param`: parámetro de búsqueda nativo del board (`Some("search")`) o
+// `None` si no tiene búsqueda y hay que filtrar en local.
+// `$limit_param`: parámetro que fija el tamaño de página (`Some("limit")`), si existe.
+macro_rules! impl_json_source {
+    ($struct:ident, $source:expr, $label:expr, $url:expr, $json_path:expr, $url_key:expr, $id_key:expr, $search_param:expr, $limit_param:expr) => {
+        #[derive(Default)]
         pub struct $struct;
-        impl $struct { pub fn new() -> Self { Self } }
-
-        #[async_trait]
-        impl Agent for $struct {
-            async fn process(&self, msg: AgentMessage) -> Result<AgentMessage, AgentError> {
-                if let AgentMessage::StartScraping(criteria) = msg {
-                    // Check enabled
-                    let enabled = criteria.sources_config.iter()
-                        .find(|s| s.source == $source)
-                        .map(|s| s.enabled)
-                        .unwrap_or(true); // Default true si no viene en config
-
-                    if !enabled {
-                        return Ok(AgentMessage::RawJobsScraped(vec![]));
-                    }
-
-                    let client = reqwest::Client::builder()
-                        .user_agent("Mozilla/5.0 (compatible; JobHunterBot/1.0)")
-                        .timeout(Duration::from_secs(20))
-                        .build()?;
-
-                    // Simulamos pequeño delay para ser amables
-                    sleep(Duration::from_millis(500)).await;
-
-                    let res = client.get($url).send().await?;
-                    if !res.status().is_success() {
-                        return Err(AgentError::Scraping(format!("HTTP {}", res.status())));
-                    }
-
-                    let json: serde_json::Value = res.json().await?;
-                    let mut postings = Vec::new();
-
-                    // Navegación básica en el JSON
-                    let root = if $json_path == "" { &json } else { json.get($json_path).unwrap_or(&serde_json::Value::Null) };
-                    
-                    if let Some(arr) = root.as_array() {
-                        for item in arr.iter().take(15) {
-                            if let Some(link) = item.get($url_key).and_then(|v| v.as_str()) {
-                                postings.push(RawJobPosting {
-                                    id: Uuid::new_v4().to_string(),
-                                    source: $source,
-                                    url: link.to_string(),
-                                    html_content: item.to_string(), // Raw JSON as content
-                                    scraped_at: Utc::now(),
-                                });
-                            }
-                        }
-                    }
-
-                    info!("📂 [{}] Extracted {} jobs.", $name, postings.len());
-                    Ok(AgentMessage::RawJobsScraped(postings))
-                } else {
-                    Err(AgentError::Scraping("Invalid msg".into()))
+
+        impl JobSourceAdapter for $struct {
+            fn source(&self) -> JobSource { $source }
+            fn label(&self) -> &str { $label }
+
+            fn keyword_search(&self) -> KeywordSearch {
+                let search_param: Option<&str> = $search_param;
+                if search_param.is_some() { KeywordSearch::Native } else { KeywordSearch::Listing }
+            }
+
+            fn client(&self, settings: &SourceSettings) -> SourceClient {
+                SourceClient::new(settings).with_timeout(Duration::from_secs(20))
+            }
+
+            // Paginación vía `links.next` / `next` si la API la expone
+            fn page_url(&self, _ctx: &ScrapeContext, term: Option<&str>, cursor: &PageCursor) -> Option<String> {
+                if cursor.page > 0 {
+                    return cursor.next.clone();
+                }
+                let search_param: Option<&str> = $search_param;
+                let limit_param: Option<&str> = $limit_param;
+                let mut params: Vec<(&str, String)> = Vec::new();
+                if let (Some(param), Some(t)) = (search_param, term) {
+                    params.push((param, t.to_string()));
                 }
+                if let Some(param) = limit_param {
+                    params.push((param, cursor.limit.to_string()));
+                }
+                Some(with_query($url, &params))
+            }
+
+            fn parse_items(&self, _url: &str, body: &str) -> Result<Listing, AgentError> {
+                let json: serde_json::Value = parse_json(body)?;
+
+                // Navegación básica en el JSON
+                let root = if $json_path == "" { &json } else { json.get($json_path).unwrap_or(&serde_json::Value::Null) };
+
+                let items = root.as_array().map(Vec::as_slice).unwrap_or_default();
+                let postings = items
+                    .iter()
+                    .filter_map(|item| {
+                        let link = item.get($url_key).and_then(|v| v.as_str())?;
+                        let posting = RawJobPosting::new(
+                            $source,
+                            json_native_id(item, $id_key).as_deref(),
+                            link,
+                            item.to_string(), // Raw JSON as content
+                        );
+                        Some(with_json_details(posting, item))
+                    })
+                    .collect();
+                Ok(Listing::new(postings).with_next(next_link(&json)))
             }
-            fn name(&self) -> &str { $name }
         }
     };
 }
 
 // --- IMPLEMENTACIONES JSON ---
-impl_json_scraper!(RemotiveAgent, JobSource::Remotive, "scraper_remotive", "https://remotive.com/api/remote-jobs?limit=15", "jobs", "url");
-impl_json_scraper!(JobicyAgent, JobSource::Jobicy, "scraper_jobicy", "https://jobicy.com/api/v2/remote-jobs?count=15", "jobs", "url");
-impl_json_scraper!(FindWorkAgent, JobSource::FindWork, "scraper_findwork", "https://findwork.dev/api/jobs/", "results", "url");
-impl_json_scraper!(WorkingNomadsAgent, JobSource::WorkingNomads, "scraper_workingnomads", "https://www.workingnomads.com/api/advanced_search", "", "url");
-impl_json_scraper!(VueJobsAgent, JobSource::VueJobs, "scraper_vuejobs", "https://vuejobs.com/api/jobs", "data", "apply_url");
-impl_json_scraper!(CryptoJobsAgent, JobSource::CryptoJobs, "scraper_cryptojobs", "https://cryptojobslist.com/api/jobs", "", "application_url");
-impl_json_scraper!(DevItJobsAgent, JobSource::DevItJobs, "scraper_devitjobs", "https://devitjobs.uk/api/jobsFeed", "", "jobUrl");
-impl_json_scraper!(GolangProjectsAgent, JobSource::GolangProjects, "scraper_golang", "https://golangprojects.com/api/v1/jobs", "jobs", "url");
-
-// --- IMPLEMENTACIONES RSS (Manuales) ---
-
-pub struct PythonOrgAgent;
-impl PythonOrgAgent { pub fn new() -> Self { Self } }
-#[async_trait]
-impl Agent for PythonOrgAgent {
-    async fn process(&self, msg: AgentMessage) -> Result<AgentMessage, AgentError> {
-        if let AgentMessage::StartScraping(c) = msg {
-             if c.sources_config.iter().any(|s| s.source == JobSource::PythonOrg && !s.enabled) { return Ok(AgentMessage::RawJobsScraped(vec![])); }
-             let txt = reqwest::get("https://www.python.org/jobs/feed/rss/").await?.text().await?;
-             let mut posts = vec![];
-             for item in txt.split("<item>").skip(1).take(15) {
-                 if let Some(l) = item.split("<link>").nth(1).and_then(|x| x.split("</link>").next()) {
-                     posts.push(RawJobPosting { id: Uuid::new_v4().to_string(), source: JobSource::PythonOrg, url: l.trim().to_string(), html_content: item.to_string(), scraped_at: Utc::now() });
-                 }
-             }
-             info!("📂 [PythonOrg] Extracted {} jobs.", posts.len());
-             Ok(AgentMessage::RawJobsScraped(posts))
-        } else { Err(AgentError::Scraping("Inv".into())) }
-    }
-    fn name(&self) -> &str { "scraper_pythonorg" }
+impl_json_source!(RemotiveSource, JobSource::Remotive, "Remotive", "https://remotive.com/api/remote-jobs", "jobs", "url", "id", Some("search"), Some("limit"));
+impl_json_source!(JobicySource, JobSource::Jobicy, "Jobicy", "https://jobicy.com/api/v2/remote-jobs", "jobs", "url", "id", Some("tag"), Some("count"));
+impl_json_source!(FindWorkSource, JobSource::FindWork, "FindWork", "https://findwork.dev/api/jobs/", "results", "url", "id", Some("search"), None);
+impl_json_source!(WorkingNomadsSource, JobSource::WorkingNomads, "WorkingNomads", "https://www.workingnomads.com/api/advanced_search", "", "url", "id", None, None);
+impl_json_source!(VueJobsSource, JobSource::VueJobs, "VueJobs", "https://vuejobs.com/api/jobs", "data", "apply_url", "id", None, None);
+impl_json_source!(CryptoJobsSource, JobSource::CryptoJobs, "CryptoJobs", "https://cryptojobslist.com/api/jobs", "", "application_url", "_id", None, None);
+impl_json_source!(DevItJobsSource, JobSource::DevItJobs, "DevItJobs", "https://devitjobs.uk/api/jobsFeed", "", "jobUrl", "_id", None, None);
+impl_json_source!(GolangProjectsSource, JobSource::GolangProjects, "GolangProjects", "https://golangprojects.com/api/v1/jobs", "jobs", "url", "id", None, None);
+
+// Macro para adaptadores de feeds RSS/Atom: basta con la URL del feed.
+macro_rules! impl_feed_source {
+    ($struct:ident, $source:expr, $label:expr, $url:expr) => {
+        #[derive(Default)]
+        pub struct $struct;
+
+        impl JobSourceAdapter for $struct {
+            fn source(&self) -> JobSource { $source }
+            fn label(&self) -> &str { $label }
+            fn keyword_search(&self) -> KeywordSearch { KeywordSearch::Listing }
+
+            fn page_url(&self, _ctx: &ScrapeContext, _term: Option<&str>, cursor: &PageCursor) -> Option<String> {
+                (cursor.page == 0).then(|| $url.to_string())
+            }
+
+            fn parse_items(&self, _url: &str, body: &str) -> Result<Listing, AgentError> {
+                Ok(feed_listing($source, body))
+            }
+        }
+    };
 }
 
-pub struct RemoteCoAgent;
-impl RemoteCoAgent { pub fn new() -> Self { Self } }
-#[async_trait]
-impl Agent for RemoteCoAgent {
-    async fn process(&self, msg: AgentMessage) -> Result<AgentMessage, AgentError> {
-        if let AgentMessage::StartScraping(c) = msg {
-             if c.sources_config.iter().any(|s| s.source == JobSource::RemoteCo && !s.enabled) { return Ok(AgentMessage::RawJobsScraped(vec![])); }
-             let client = reqwest::Client::builder().user_agent("Mozilla/5.0").build()?;
-             let txt = client.get("https://remote.co/remote-jobs/feed/").send().await?.text().await?;
-             let mut posts = vec![];
-             for item in txt.split("<item>").skip(1).take(15) {
-                 if let Some(l) = item.split("<link>").nth(1).and_then(|x| x.split("</link>").next()) {
-                     posts.push(RawJobPosting { id: Uuid::new_v4().to_string(), source: JobSource::RemoteCo, url: l.trim().to_string(), html_content: item.to_string(), scraped_at: Utc::now() });
-                 }
-             }
-             info!("📂 [RemoteCo] Extracted {} jobs.", posts.len());
-             Ok(AgentMessage::RawJobsScraped(posts))
-        } else { Err(AgentError::Scraping("Inv".into())) }
-    }
-    fn name(&self) -> &str { "scraper_remoteco" }
-}
\ No newline at end of file
+// --- IMPLEMENTACIONES RSS/ATOM ---
+impl_feed_source!(PythonOrgSource, JobSource::PythonOrg, "PythonOrg", "https://www.python.org/jobs/feed/rss/");
+impl_feed_source!(RemoteCoSource, JobSource::RemoteCo, "RemoteCo", "https://remote.co/remote-jobs/feed/");
@@ -1,80 +1,65 @@
-use async_trait::async_trait;
-use chrono::Utc;
 use job_hunter_core::*;
-use std::time::Duration;
-use tokio::time::sleep;
-use tracing::info; // CORREGIDO: Eliminado 'warn'
-use uuid::Uuid;
+use serde_json::Value;
 
-pub struct HimalayasAgent;
+use super::adapter::{JobSourceAdapter, Listing, PageCursor, ScrapeContext};
+use super::query::with_query;
+use super::{json_native_id, parse_json, with_json_details};
 
-impl HimalayasAgent {
-    pub fn new() -> Self {
-        Self
-    }
-}
-
-#[async_trait]
-impl Agent for HimalayasAgent {
-    async fn process(&self, msg: AgentMessage) -> Result<AgentMessage, AgentError> {
-        if let AgentMessage::StartScraping(criteria) = msg {
-            // 1. Verificar configuración
-            let default_cfg = SourceSettings {
-                source: JobSource::Himalayas,
-                enabled: true,
-                delay_ms: 1200,
-                user_agent: "Mozilla/5.0".to_string(),
-                use_proxy: false,
-            };
+const FEED_URL: &str = "https://himalayas.app/jobs/api";
+const SEARCH_URL: &str = "https://himalayas.app/jobs/api/search";
 
-            let my_cfg = criteria
-                .sources_config
-                .iter()
-                .find(|s| s.source == JobSource::Himalayas)
-                .unwrap_or(&default_cfg);
+/// Himalayas: búsqueda nativa con `q` por keyword y `seniority` según el nivel
+/// pedido, paginada con `page`. Sin criterios se usa el feed genérico con `offset`.
+#[derive(Default)]
+pub struct HimalayasSource;
 
-            if !my_cfg.enabled {
-                info!("ℹ️ [Himalayas] Agente desactivado por configuración.");
-                return Ok(AgentMessage::RawJobsScraped(vec![]));
-            }
-
-            if my_cfg.delay_ms > 0 {
-                info!("⏳ [Himalayas] Esperando {}ms (Anti-bot)...", my_cfg.delay_ms);
-                sleep(Duration::from_millis(my_cfg.delay_ms)).await;
-            }
+impl JobSourceAdapter for HimalayasSource {
+    fn source(&self) -> JobSource {
+        JobSource::Himalayas
+    }
 
-            let client = reqwest::Client::builder()
-                .user_agent(&my_cfg.user_agent)
-                .timeout(Duration::from_secs(30))
-                .build()?;
+    fn label(&self) -> &str {
+        "Himalayas"
+    }
 
-            let res = client
-                .get("https://himalayas.app/jobs/api?limit=10")
-                .send()
-                .await?
-                .json::<serde_json::Value>()
-                .await?;
+    fn level_search(&self) -> bool {
+        true
+    }
 
-            let mut postings = Vec::new();
-            if let Some(jobs) = res["jobs"].as_array() {
-                for job in jobs {
-                    postings.push(RawJobPosting {
-                        id: Uuid::new_v4().to_string(),
-                        source: JobSource::Himalayas,
-                        url: job["url"].as_str().unwrap_or_default().to_string(),
-                        html_content: job.to_string(),
-                        scraped_at: Utc::now(),
-                    });
-                }
-            }
-            
-            info!("📂 [Himalayas] Éxito: {} ofertas extraídas.", postings.len());
-            Ok(AgentMessage::RawJobsScraped(postings))
-        } else {
-            Err(AgentError::Scraping("Msg inválido".into()))
+    fn page_url(&self, ctx: &ScrapeContext, term: Option<&str>, cursor: &PageCursor) -> Option<String> {
+        let mut params: Vec<(&str, String)> = Vec::new();
+        if let Some(t) = term {
+            params.push(("q", t.to_string()));
+        }
+        if let Some(s) = ctx.query.himalayas_seniority() {
+            params.push(("seniority", s.to_string()));
         }
+        Some(if params.is_empty() {
+            let offset = (cursor.page * cursor.limit).to_string();
+            with_query(FEED_URL, &[("limit", cursor.limit.to_string()), ("offset", offset)])
+        } else {
+            params.push(("page", (cursor.page + 1).to_string()));
+            with_query(SEARCH_URL, &params)
+        })
     }
-    fn name(&self) -> &str {
-        "scraper_himalayas"
+
+    fn parse_items(&self, _url: &str, body: &str) -> Result<Listing, AgentError> {
+        let res: Value = parse_json(body)?;
+        let jobs = res["jobs"].as_array().map(Vec::as_slice).unwrap_or_default();
+        let postings = jobs
+            .iter()
+            .map(|job| {
+                with_json_details(
+                    RawJobPosting::new(
+                        JobSource::Himalayas,
+                        json_native_id(job, "guid").as_deref(),
+                        job["url"].as_str().unwrap_or_default(),
+                        job.to_string(),
+                    ),
+                    job,
+                )
+            })
+            .collect();
+        Ok(Listing::new(postings))
     }
 }
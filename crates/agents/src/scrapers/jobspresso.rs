@@ -1,56 +1,41 @@
 use async_trait::async_trait;
-use chrono::Utc;
 use job_hunter_core::*;
 use scraper::{Html, Selector};
 use std::collections::HashSet;
-use std::time::Duration;
-use tokio::time::sleep;
-use tracing::{error, info, warn};
+use tracing::warn;
 use url::Url;
-use uuid::Uuid;
 
-pub struct JobspressoAgent;
+use crate::http::SourceClient;
+use crate::structured::with_structured_data;
 
-impl JobspressoAgent {
-    pub fn new() -> Self {
-        Self
-    }
+use super::adapter::{JobSourceAdapter, KeywordSearch, Listing, PageCursor, ScrapeContext};
 
-    // Devuelve (enabled, delay_ms, user_agent, use_proxy)
-    fn settings_from_criteria(criteria: &SearchCriteria) -> (bool, u64, String, bool) {
-        if let Some(s) = criteria
-            .sources_config
-            .iter()
-            .find(|s| s.source == JobSource::Jobspresso)
-        {
-            (s.enabled, s.delay_ms, s.user_agent.clone(), s.use_proxy)
-        } else {
-            // Default
-            (true, 1200, "Mozilla/5.0".to_string(), false)
-        }
-    }
+const BASE_URL: &str = "https://jobspresso.co/";
 
-    async fn fetch_html(client: &reqwest::Client, url: &str) -> Result<String, AgentError> {
+/// Jobspresso: WordPress sin API ni búsqueda fiable. El listado (home y
+/// `/page/N/`) solo trae enlaces `/job/`; cada oferta sale de su detalle.
+#[derive(Default)]
+pub struct JobspressoSource;
+
+impl JobspressoSource {
+    async fn fetch_html(client: &SourceClient, url: &str) -> Result<String, AgentError> {
         let res = client
             .get(url)
             .header("Accept", "text/html,application/xhtml+xml")
             .send()
-            .await
-            .map_err(AgentError::Network)?;
+            .await?;
 
         if !res.status().is_success() {
-            let status = res.status();
-            let body = res.text().await.unwrap_or_default();
             return Err(AgentError::Scraping(format!(
                 "Jobspresso HTTP {}: {}",
-                status,
-                body.chars().take(200).collect::<String>()
+                res.status(),
+                res.text().chars().take(200).collect::<String>()
             )));
         }
-        res.text().await.map_err(AgentError::Network)
+        Ok(res.into_text())
     }
 
-    fn extract_job_links(base: &Url, html: &str, max: usize) -> Vec<String> {
+    fn extract_job_links(base: &Url, html: &str) -> Vec<String> {
         let doc = Html::parse_document(html);
         let a_sel = Selector::parse("a").unwrap();
 
@@ -72,104 +57,78 @@ impl JobspressoAgent {
                 let s = abs.to_string();
                 if seen.insert(s.clone()) {
                     out.push(s);
-                    if out.len() >= max {
-                        break;
-                    }
                 }
             }
         }
         out
     }
+}
 
-    async fn scrape_latest(
-        &self,
-        criteria: &SearchCriteria,
-    ) -> Result<Vec<RawJobPosting>, AgentError> {
-        let (enabled, delay_ms, user_agent, _use_proxy) = Self::settings_from_criteria(criteria);
+#[async_trait]
+impl JobSourceAdapter for JobspressoSource {
+    fn source(&self) -> JobSource {
+        JobSource::Jobspresso
+    }
 
-        if !enabled {
-            info!("ℹ️ [Jobspresso] Agente desactivado por configuración.");
-            return Ok(vec![]);
-        }
+    fn label(&self) -> &str {
+        "Jobspresso"
+    }
 
-        let client = reqwest::Client::builder()
-            .user_agent(user_agent)
-            .timeout(Duration::from_secs(30))
-            .build()
-            .map_err(AgentError::Network)?;
+    fn keyword_search(&self) -> KeywordSearch {
+        KeywordSearch::Details
+    }
 
-        if delay_ms > 0 {
-            info!("⏳ [Jobspresso] Esperando {}ms (Anti-bot)...", delay_ms);
-            sleep(Duration::from_millis(delay_ms)).await;
-        }
+    fn client(&self, settings: &SourceSettings) -> SourceClient {
+        SourceClient::new(settings).with_robots()
+    }
 
-        let base = Url::parse("https://jobspresso.co/").expect("base url");
-        info!("🌐 [Jobspresso] Descargando listados (home, sin JS)...");
-        
-        let home_html = match Self::fetch_html(&client, base.as_str()).await {
-            Ok(h) => h,
-            Err(e) => {
-                error!("❌ [Jobspresso] Error conectando a home: {}", e);
-                return Ok(vec![]);
-            }
-        };
+    // Home y, si hace falta, /page/N/ (paginación estándar de WordPress)
+    fn page_url(&self, _ctx: &ScrapeContext, _term: Option<&str>, cursor: &PageCursor) -> Option<String> {
+        Some(match cursor.page {
+            0 => BASE_URL.to_string(),
+            n => format!("{}page/{}/", BASE_URL, n + 1),
+        })
+    }
+
+    async fn fetch_page(&self, ctx: &ScrapeContext, url: &str) -> Result<String, AgentError> {
+        Self::fetch_html(&ctx.client, url).await
+    }
+
+    fn parse_items(&self, _url: &str, body: &str) -> Result<Listing, AgentError> {
+        let base = Url::parse(BASE_URL).expect("base url");
+        let postings = Self::extract_job_links(&base, body)
+            .into_iter()
+            .map(|link| RawJobPosting::new(JobSource::Jobspresso, None, link, ""))
+            .collect();
+        Ok(Listing::new(postings))
+    }
 
-        let links = Self::extract_job_links(&base, &home_html, 12);
+    /// Descarga el detalle de cada enlace; el ritmo lo marca el rate limiter del host.
+    async fn fetch_details(
+        &self,
+        ctx: &ScrapeContext,
+        links: Vec<RawJobPosting>,
+    ) -> Result<Vec<RawJobPosting>, AgentError> {
+        // Sin enlaces la home ha cambiado de markup o hay bloqueo: cuenta como fallo
         if links.is_empty() {
-            warn!("⚠️ [Jobspresso] No se encontraron links /job/. Posible cambio de markup o bloqueo.");
-            return Ok(vec![]);
+            return Err(AgentError::Scraping(
+                "Jobspresso: no se encontraron links /job/ (posible cambio de markup o bloqueo)".into(),
+            ));
         }
 
         let mut postings = Vec::new();
-        for (i, job_url) in links.into_iter().enumerate() {
-            if i > 0 {
-                sleep(Duration::from_millis(300)).await;
+        for link in links {
+            if postings.len() >= ctx.limits.max_results {
+                break;
             }
-
-            match Self::fetch_html(&client, &job_url).await {
-                Ok(detail_html) => postings.push(RawJobPosting {
-                    id: Uuid::new_v4().to_string(),
-                    source: JobSource::Jobspresso,
-                    url: job_url,
-                    html_content: detail_html,
-                    scraped_at: Utc::now(),
-                }),
-                Err(e) => {
-                    warn!("⚠️ [Jobspresso] Saltando detalle por error: {}", e);
-                    continue;
+            match Self::fetch_html(&ctx.client, &link.url).await {
+                Ok(detail_html) => {
+                    let posting = RawJobPosting::new(JobSource::Jobspresso, None, link.url, detail_html.as_str());
+                    postings.push(with_structured_data(posting, &detail_html));
                 }
-            }
-
-            if postings.len() >= 10 {
-                break;
+                Err(e) => warn!("⚠️ [Jobspresso] Saltando detalle por error: {}", e),
             }
         }
-
         Ok(postings)
     }
 }
-
-#[async_trait]
-impl Agent for JobspressoAgent {
-    async fn process(&self, msg: AgentMessage) -> Result<AgentMessage, AgentError> {
-        match msg {
-            AgentMessage::StartScraping(criteria) => match self.scrape_latest(&criteria).await {
-                Ok(postings) => {
-                    if !postings.is_empty() {
-                        info!("📂 [Jobspresso] Encontradas {} ofertas.", postings.len());
-                    }
-                    Ok(AgentMessage::RawJobsScraped(postings))
-                }
-                Err(e) => {
-                    error!("❌ [Jobspresso] Error fatal: {}", e);
-                    Ok(AgentMessage::RawJobsScraped(vec![]))
-                }
-            },
-            _ => Err(AgentError::Scraping("Msg inválido".into())),
-        }
-    }
-
-    fn name(&self) -> &str {
-        "scraper_jobspresso"
-    }
-}
@@ -1,6 +1,202 @@
+pub mod adapter;
 pub mod arbeitnow;
+pub mod ats;
+pub mod custom;
+pub mod dynamic;
 pub mod himalayas;
 pub mod jobspresso;
+pub mod registry;
 pub mod remoteok;
 pub mod weworkremotely;
 pub mod extra_scrapers; // <--- Añadido el módulo de extras
+mod details;
+mod paging;
+mod query;
+
+use job_hunter_core::{AgentError, JobSource, JobType, RawJobPosting};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::collections::HashSet;
+use tracing::warn;
+
+use crate::content::plain_text;
+use crate::dates::posted_at_from_json;
+use crate::feed::{parse_feed, FeedItem};
+use crate::salary::{parse_salary_text, salary_from_json};
+
+use self::adapter::Listing;
+
+// Claves habituales en las APIs JSON de los boards
+const TITLE_KEYS: &[&str] = &["title", "position", "jobTitle", "role", "name"];
+const COMPANY_KEYS: &[&str] = &["company_name", "companyName", "company"];
+const APPLY_KEYS: &[&str] = &["apply_url", "applyUrl", "applicationLink", "application_url", "apply_link"];
+const LOCATION_KEYS: &[&str] = &[
+    "location",
+    "candidate_required_location",
+    "jobGeo",
+    "locationRestrictions",
+    "country",
+];
+const JOB_TYPE_KEYS: &[&str] = &["job_type", "jobType", "employmentType", "job_types", "type"];
+// Descripción completa antes que los extractos
+const DESCRIPTION_KEYS: &[&str] = &["description", "jobDescription", "content", "body", "text", "excerpt", "jobExcerpt"];
+const TAG_KEYS: &[&str] = &["tags", "skills", "keywords", "categories", "category", "category_name", "jobIndustry"];
+/// Nivel de experiencia nativo (`jobLevel` en Jobicy); se guarda como tag.
+const LEVEL_KEYS: &[&str] = &["jobLevel", "seniority", "experience_level"];
+
+/// Id nativo del board en un item JSON (string o número), si lo expone.
+pub(crate) fn json_native_id(item: &Value, key: &str) -> Option<String> {
+    match item.get(key)? {
+        Value::String(s) if !s.trim().is_empty() => Some(s.trim().to_string()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// Primer texto no vacío entre `keys` (si el valor es un objeto, usa su `name`).
+pub(crate) fn json_str(item: &Value, keys: &[&str]) -> Option<String> {
+    keys.iter().find_map(|k| {
+        let v = item.get(*k)?;
+        let s = v.as_str().or_else(|| v.get("name").and_then(|n| n.as_str()))?;
+        Some(s.trim().to_string()).filter(|s| !s.is_empty())
+    })
+}
+
+/// Primer texto no vacío entre `keys`, uniendo con ", " si el valor es una lista.
+fn json_text_or_list(item: &Value, keys: &[&str]) -> Option<String> {
+    keys.iter().find_map(|k| {
+        let s = match item.get(*k)? {
+            Value::Array(items) => items
+                .iter()
+                .filter_map(|v| v.as_str().or_else(|| v.get("name").and_then(|n| n.as_str())))
+                .collect::<Vec<_>>()
+                .join(", "),
+            v => v.as_str().or_else(|| v.get("name").and_then(|n| n.as_str()))?.to_string(),
+        };
+        Some(s.trim().to_string()).filter(|s| !s.is_empty())
+    })
+}
+
+/// Tipo de contrato nativo (si hay varios, el primero reconocible).
+fn json_job_type(item: &Value) -> Option<JobType> {
+    let labels = json_text_or_list(item, JOB_TYPE_KEYS)?;
+    labels.split(',').find_map(JobType::from_label)
+}
+
+/// Descripción en texto plano (las APIs suelen devolverla en HTML).
+fn json_description(item: &Value) -> Option<String> {
+    let text = DESCRIPTION_KEYS.iter().find_map(|k| item.get(*k)?.as_str().filter(|s| !s.trim().is_empty()))?;
+    Some(plain_text(text))
+}
+
+/// Tags de todas las claves habituales.
+fn json_tags(item: &Value) -> Vec<String> {
+    let levels = LEVEL_KEYS
+        .iter()
+        .filter_map(|k| item.get(*k))
+        .flat_map(tag_list)
+        .filter(|l| !l.trim().eq_ignore_ascii_case("any"));
+    TAG_KEYS.iter().filter_map(|k| item.get(*k)).flat_map(tag_list).chain(levels).collect()
+}
+
+/// Tags de un valor JSON: lista, objetos con `name` o texto separado por comas.
+pub(crate) fn tag_list(value: &Value) -> Vec<String> {
+    match value {
+        Value::Array(items) => items
+            .iter()
+            .filter_map(|t| t.as_str().or_else(|| t.get("name").and_then(|n| n.as_str())))
+            .map(str::to_string)
+            .collect(),
+        Value::String(s) => s.split(',').map(str::to_string).collect(),
+        _ => vec![],
+    }
+}
+
+/// Rellena título, empresa, URL de aplicación, descripción, tags, salario,
+/// fecha de publicación, ubicación y tipo de contrato a partir de un item JSON.
+pub(crate) fn with_json_details(posting: RawJobPosting, item: &Value) -> RawJobPosting {
+    posting
+        .with_details(
+            json_str(item, TITLE_KEYS),
+            json_str(item, COMPANY_KEYS),
+            json_str(item, APPLY_KEYS),
+        )
+        .with_description(json_description(item), json_tags(item))
+        .with_salary(salary_from_json(item))
+        .with_posted_at(posted_at_from_json(item))
+        .with_location(
+            json_text_or_list(item, LOCATION_KEYS),
+            item.get("remote").and_then(|v| v.as_bool()),
+        )
+        .with_job_type(json_job_type(item))
+}
+
+/// Elimina repetidos por id (mismo item devuelto por varias keywords),
+/// conservando el orden original.
+pub(crate) fn dedup_by_id(postings: &mut Vec<RawJobPosting>) {
+    let mut seen = HashSet::new();
+    postings.retain(|p| seen.insert(p.id.clone()));
+}
+
+/// Oferta a partir de una entrada de feed RSS/Atom (requiere enlace).
+pub(crate) fn posting_from_feed_item(source: JobSource, item: &FeedItem) -> Option<RawJobPosting> {
+    let link = item.link.as_deref()?;
+    let salary_text = format!("{}\n{}", item.title.as_deref().unwrap_or_default(), item.body().unwrap_or_default());
+    // Las categorías que son un tipo de contrato ("Full-Time") no son tags
+    let tags = item.categories.iter().filter(|c| JobType::from_label(c).is_none()).cloned().collect();
+    Some(
+        RawJobPosting::new(source, item.guid.as_deref(), link, item.raw.as_str())
+            .with_details(item.title.clone(), None, None)
+            .with_description(item.body().map(plain_text), tags)
+            .with_salary(parse_salary_text(&salary_text))
+            .with_posted_at(item.pub_date)
+            .with_job_type(item.categories.iter().find_map(|c| JobType::from_label(c))),
+    )
+}
+
+/// Ofertas de un documento RSS/Atom. Los feeds no admiten búsqueda ni
+/// paginan: se filtran en local por keywords.
+pub(crate) fn feed_listing(source: JobSource, body: &str) -> Listing {
+    let postings = parse_feed(body)
+        .iter()
+        .filter_map(|item| posting_from_feed_item(source.clone(), item))
+        .collect();
+    Listing::new(postings)
+}
+
+/// JSON de una respuesta de API.
+pub(crate) fn parse_json<T: DeserializeOwned>(body: &str) -> Result<T, AgentError> {
+    serde_json::from_str(body).map_err(|e| AgentError::Scraping(format!("Error parseando JSON: {}", e)))
+}
+
+/// Lee el JSON del fichero indicado en la variable `key` (o `default`). Un
+/// fichero ausente no es un error; uno inválido se ignora con un aviso.
+/// Devuelve también la ruta leída, para los logs.
+pub(crate) fn load_json_config<T: DeserializeOwned>(key: &str, default: &str) -> Option<(String, T)> {
+    let path = std::env::var(key).unwrap_or_else(|_| default.to_string());
+    let content = std::fs::read_to_string(&path).ok()?;
+    match serde_json::from_str(&content) {
+        Ok(config) => Some((path, config)),
+        Err(e) => {
+            warn!("⚠️ Configuración ignorada ({}): {}", path, e);
+            None
+        }
+    }
+}
+
+/// Como [`load_json_config`] para una lista de entradas: el fichero es el
+/// array o un objeto con el array en `field` (`{ "sources": [...] }`).
+pub(crate) fn load_json_list<T: DeserializeOwned>(key: &str, default: &str, field: &str) -> Option<(String, Vec<T>)> {
+    let (path, config) = load_json_config::<Value>(key, default)?;
+    let items = match config {
+        Value::Object(mut map) => map.remove(field).unwrap_or(Value::Null),
+        list => list,
+    };
+    match serde_json::from_value(items) {
+        Ok(items) => Some((path, items)),
+        Err(e) => {
+            warn!("⚠️ Configuración ignorada ({}): {}", path, e);
+            None
+        }
+    }
+}
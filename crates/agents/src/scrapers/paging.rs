@@ -0,0 +1,35 @@
+use job_hunter_core::SourceSettings;
+use serde_json::Value;
+
+/// Límites de paginación de una fuente, derivados de su `SourceSettings`.
+pub(crate) struct PageLimits {
+    pub max_results: usize,
+    pub max_pages: usize,
+}
+
+impl PageLimits {
+    pub(crate) fn from_settings(cfg: &SourceSettings) -> Self {
+        Self {
+            max_results: cfg.max_results.max(1) as usize,
+            max_pages: cfg.max_pages.max(1) as usize,
+        }
+    }
+
+    /// Cupo de resultados de cada consulta cuando se reparten entre `terms`
+    /// keywords, para que la primera no agote el total.
+    pub(crate) fn per_term(&self, terms: usize) -> usize {
+        self.max_results.div_ceil(terms.max(1))
+    }
+}
+
+/// URL de la página siguiente en APIs que la exponen: `links.next`
+/// (paginación Laravel/JSON:API) o `next` (Django REST Framework).
+pub(crate) fn next_link(json: &Value) -> Option<String> {
+    json.get("links")
+        .and_then(|l| l.get("next"))
+        .or_else(|| json.get("next"))
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|s| s.starts_with("http"))
+        .map(String::from)
+}
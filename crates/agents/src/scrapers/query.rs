@@ -0,0 +1,267 @@
+use job_hunter_core::{contains_term, ExperienceLevel, RawJobPosting, SearchCriteria};
+use tracing::info;
+use url::Url;
+
+/// Máximo de keywords consultadas por separado contra un board (una petición
+/// por término). El resto se busca en el listado general y se filtra en local.
+const MAX_QUERY_TERMS: usize = 3;
+
+/// Términos del título o tags que delatan el nivel de una oferta, por franja:
+/// inicial (Entry/Junior), media (Mid) y alta (Senior/Lead).
+const LEVEL_TERMS: [&[&str]; 3] = [
+    &["junior", "jr", "entry", "graduate", "intern", "internship", "trainee", "apprentice"],
+    &["mid", "midweight", "intermediate"],
+    &["senior", "sr", "lead", "principal", "staff engineer", "head of"],
+];
+
+/// Traducción de `SearchCriteria` a la sintaxis de búsqueda de cada board.
+pub(crate) struct SearchQuery {
+    keywords: Vec<String>,
+    level: ExperienceLevel,
+}
+
+impl SearchQuery {
+    pub(crate) fn from_criteria(criteria: &SearchCriteria) -> Self {
+        Self {
+            keywords: criteria
+                .keywords
+                .iter()
+                .map(|k| k.trim().to_string())
+                .filter(|k| !k.is_empty())
+                .collect(),
+            level: criteria.experience_level.clone(),
+        }
+    }
+
+    /// Términos a consultar, uno por petición. Sin keywords devuelve `[None]`
+    /// (feed genérico del board). Si hay más de `MAX_QUERY_TERMS`, el último
+    /// término es `None`: el listado general, filtrado con [`Self::matches_overflow`].
+    pub(crate) fn terms(&self) -> Vec<Option<&str>> {
+        if self.keywords.is_empty() {
+            return vec![None];
+        }
+        let mut terms: Vec<Option<&str>> = self
+            .keywords
+            .iter()
+            .take(MAX_QUERY_TERMS)
+            .map(|k| Some(k.as_str()))
+            .collect();
+        if !self.overflow().is_empty() {
+            terms.push(None);
+        }
+        terms
+    }
+
+    /// Keywords que no caben en la consulta al board.
+    pub(crate) fn overflow(&self) -> &[String] {
+        self.keywords.get(MAX_QUERY_TERMS..).unwrap_or_default()
+    }
+
+    /// `true` si la oferta coincide con alguna keyword de [`Self::overflow`]
+    /// (o no las hay).
+    pub(crate) fn matches_overflow(&self, posting: &RawJobPosting) -> bool {
+        let overflow = self.overflow();
+        if overflow.is_empty() {
+            return true;
+        }
+        let text = posting.searchable_text();
+        overflow.iter().any(|k| contains_term(&text, k))
+    }
+
+    /// RemoteOK filtra por tags en minúsculas con guiones ("machine-learning").
+    pub(crate) fn as_tag(term: &str) -> String {
+        term.trim()
+            .to_lowercase()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join("-")
+    }
+
+    /// Nivel de experiencia en la nomenclatura de Himalayas (`seniority=`).
+    pub(crate) fn himalayas_seniority(&self) -> Option<&'static str> {
+        match self.level {
+            ExperienceLevel::Entry | ExperienceLevel::Junior => Some("Entry-level"),
+            ExperienceLevel::Mid => Some("Mid-level"),
+            ExperienceLevel::Senior | ExperienceLevel::Lead => Some("Senior"),
+            ExperienceLevel::Any => None,
+        }
+    }
+
+    /// `true` salvo que el título o los tags de la oferta indiquen una franja de
+    /// experiencia distinta de la pedida. Sin nivel pedido, o si la oferta no
+    /// dice nada (o mezcla franjas), coincide.
+    pub(crate) fn matches_level(&self, posting: &RawJobPosting) -> bool {
+        let Some(wanted) = level_band(&self.level) else { return true };
+        let texts: Vec<&str> = posting
+            .normalized
+            .title
+            .iter()
+            .chain(&posting.normalized.tags)
+            .map(String::as_str)
+            .collect();
+        let found: Vec<usize> = (0..LEVEL_TERMS.len())
+            .filter(|band| LEVEL_TERMS[*band].iter().any(|t| texts.iter().any(|text| contains_term(text, t))))
+            .collect();
+        match found.as_slice() {
+            [band] => *band == wanted,
+            _ => true,
+        }
+    }
+
+    /// Filtrado local por nivel para boards que no lo admiten en la consulta.
+    pub(crate) fn retain_level(&self, label: &str, postings: &mut Vec<RawJobPosting>) {
+        let before = postings.len();
+        postings.retain(|p| self.matches_level(p));
+        if postings.len() < before {
+            info!(
+                "🎚️ [{}] Sin filtro de nivel en servidor: {} de {} ofertas encajan con {:?}.",
+                label,
+                postings.len(),
+                before,
+                self.level
+            );
+        }
+    }
+
+    /// `true` si alguna keyword aparece en título, empresa, descripción o tags.
+    /// Sin keywords todo coincide.
+    pub(crate) fn matches(&self, posting: &RawJobPosting) -> bool {
+        if self.keywords.is_empty() {
+            return true;
+        }
+        let text = posting.searchable_text();
+        self.keywords.iter().any(|k| contains_term(&text, k))
+    }
+
+    /// Filtrado local para boards sin búsqueda en servidor.
+    pub(crate) fn retain_matching(&self, label: &str, postings: &mut Vec<RawJobPosting>) {
+        let before = postings.len();
+        postings.retain(|p| self.matches(p));
+        if postings.len() < before {
+            info!(
+                "🔎 [{}] Sin búsqueda en servidor: {} de {} ofertas coinciden con las keywords.",
+                label,
+                postings.len(),
+                before
+            );
+        }
+    }
+}
+
+/// Franja de `LEVEL_TERMS` de un nivel pedido (`None` para `Any`).
+fn level_band(level: &ExperienceLevel) -> Option<usize> {
+    match level {
+        ExperienceLevel::Entry | ExperienceLevel::Junior => Some(0),
+        ExperienceLevel::Mid => Some(1),
+        ExperienceLevel::Senior | ExperienceLevel::Lead => Some(2),
+        ExperienceLevel::Any => None,
+    }
+}
+
+/// Añade `params` (codificados) a la query de `base`.
+pub(crate) fn with_query<K, V>(base: &str, params: &[(K, V)]) -> String
+where
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    match Url::parse(base) {
+        Ok(mut url) => {
+            if !params.is_empty() {
+                url.query_pairs_mut().extend_pairs(params);
+            }
+            url.to_string()
+        }
+        Err(_) => base.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use job_hunter_core::JobSource;
+
+    fn query(level: ExperienceLevel) -> SearchQuery {
+        SearchQuery::from_criteria(&SearchCriteria { experience_level: level, ..Default::default() })
+    }
+
+    fn posting(title: &str, tags: &[&str]) -> RawJobPosting {
+        RawJobPosting::new(JobSource::Remotive, None, "https://remotive.com/x", "")
+            .with_details(Some(title.into()), None, None)
+            .with_description(None, tags.iter().map(|t| t.to_string()).collect())
+    }
+
+    #[test]
+    fn level_from_title() {
+        let senior = query(ExperienceLevel::Senior);
+        assert!(senior.matches_level(&posting("Senior Rust Engineer", &[])));
+        assert!(senior.matches_level(&posting("Sr. Backend Developer", &[])));
+        assert!(senior.matches_level(&posting("Tech Lead, Payments", &[])));
+        assert!(!senior.matches_level(&posting("Junior Rust Engineer", &[])));
+        assert!(!senior.matches_level(&posting("Software Engineering Intern", &[])));
+
+        let junior = query(ExperienceLevel::Junior);
+        assert!(junior.matches_level(&posting("Jr Frontend Developer", &[])));
+        assert!(!junior.matches_level(&posting("Principal Engineer", &[])));
+        assert!(!query(ExperienceLevel::Mid).matches_level(&posting("Senior Java Developer", &[])));
+    }
+
+    /// Sin pista de nivel, o con franjas mezcladas, la oferta se conserva.
+    #[test]
+    fn unknown_or_mixed_levels_are_kept() {
+        let senior = query(ExperienceLevel::Senior);
+        assert!(senior.matches_level(&posting("Backend Engineer", &[])));
+        assert!(senior.matches_level(&posting("Junior to Senior Developer", &[])));
+        assert!(senior.matches_level(&posting("Seniority-agnostic role", &[])));
+        assert!(query(ExperienceLevel::Any).matches_level(&posting("Junior Developer", &[])));
+    }
+
+    /// El nivel nativo del board (`jobLevel` de Jobicy) llega como tag.
+    #[test]
+    fn level_from_tags() {
+        let tagged = posting("UX Researcher", &["Design", "Midweight"]);
+        assert!(query(ExperienceLevel::Mid).matches_level(&tagged));
+        assert!(!query(ExperienceLevel::Senior).matches_level(&tagged));
+    }
+
+    /// Las claves del item JSON (`html_content` en fuentes JSON) no coinciden
+    /// con las keywords; título, descripción y tags sí.
+    #[test]
+    fn keywords_ignore_json_keys() {
+        let item = serde_json::json!({ "title": "Data Analyst", "salary": "", "remote": true, "location": "EU" });
+        let json = RawJobPosting::new(JobSource::Remotive, None, "https://remotive.com/x", item.to_string())
+            .with_details(Some("Data Analyst".into()), None, None)
+            .with_description(Some("SQL dashboards".into()), vec!["bi".into()]);
+        let keywords = |k: &str| SearchQuery::from_criteria(&SearchCriteria { keywords: vec![k.into()], ..Default::default() });
+
+        for key in ["salary", "remote", "location", "title"] {
+            assert!(!keywords(key).matches(&json), "{key}");
+        }
+        for key in ["analyst", "sql", "bi"] {
+            assert!(keywords(key).matches(&json), "{key}");
+        }
+        // Sin descripción se busca en el contenido
+        assert!(keywords("rust").matches(&RawJobPosting::new(JobSource::Remotive, None, "https://remotive.com/y", "<p>Rust</p>")));
+    }
+
+    /// Las keywords que no caben en la consulta se filtran sobre el listado general.
+    #[test]
+    fn overflow_keywords_fall_back_to_listing() {
+        let q = |keywords: &[&str]| {
+            SearchQuery::from_criteria(&SearchCriteria {
+                keywords: keywords.iter().map(|k| k.to_string()).collect(),
+                ..Default::default()
+            })
+        };
+        assert_eq!(q(&[]).terms(), [None]);
+        assert_eq!(q(&["go", "java"]).terms(), [Some("go"), Some("java")]);
+        assert!(q(&["go", "java", "python"]).overflow().is_empty());
+
+        let many = q(&["go", "java", "python", "elixir", "scala"]);
+        assert_eq!(many.terms(), [Some("go"), Some("java"), Some("python"), None]);
+        assert_eq!(many.overflow(), ["elixir", "scala"]);
+        assert!(many.matches_overflow(&posting("Senior Elixir Engineer", &[])));
+        assert!(many.matches_overflow(&posting("Data Engineer", &["scala"])));
+        assert!(!many.matches_overflow(&posting("Go Developer", &[])));
+        assert!(q(&["go"]).matches_overflow(&posting("Anything", &[])));
+    }
+}
@@ -0,0 +1,118 @@
+use job_hunter_core::{Agent, JobSource, SearchCriteria};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::warn;
+
+use super::adapter::{JobSourceAdapter, SourceAgent};
+use super::arbeitnow::ArbeitnowSource;
+use super::ats::{load_watchlist, Ats, AtsSource};
+use super::custom::{load_custom_sources, CustomSource};
+use super::dynamic::{load_site_recipes, DynamicSource};
+use super::extra_scrapers::*;
+use super::himalayas::HimalayasSource;
+use super::jobspresso::JobspressoSource;
+use super::remoteok::RemoteOkSource;
+use super::weworkremotely::WwrSource;
+
+/// Adaptadores de fuentes disponibles, indexados por `JobSource`.
+#[derive(Default, Clone)]
+pub struct SourceRegistry {
+    adapters: HashMap<JobSource, Arc<dyn JobSourceAdapter>>,
+}
+
+impl SourceRegistry {
+    /// Fuentes integradas. Los boards ATS quedan sin watchlist por defecto:
+    /// solo se activan con `companies` en la petición.
+    pub fn builtin() -> Self {
+        Self::with_watchlist(&HashMap::new())
+    }
+
+    /// Fuentes integradas con la watchlist por defecto de cada ATS.
+    fn with_watchlist(watchlist: &HashMap<String, Vec<String>>) -> Self {
+        let adapters: Vec<Arc<dyn JobSourceAdapter>> = vec![
+            Arc::new(RemoteOkSource),
+            Arc::new(ArbeitnowSource),
+            Arc::new(HimalayasSource),
+            Arc::new(WwrSource),
+            Arc::new(JobspressoSource),
+            Arc::new(RemotiveSource),
+            Arc::new(JobicySource),
+            Arc::new(FindWorkSource),
+            Arc::new(WorkingNomadsSource),
+            Arc::new(VueJobsSource),
+            Arc::new(CryptoJobsSource),
+            Arc::new(DevItJobsSource),
+            Arc::new(GolangProjectsSource),
+            Arc::new(PythonOrgSource),
+            Arc::new(RemoteCoSource),
+        ];
+        let mut registry = Self::default();
+        for adapter in adapters {
+            registry.register(adapter);
+        }
+        // Boards de empresa (watchlist)
+        for ats in Ats::ALL {
+            let companies = watchlist.get(ats.source().slug()).cloned().unwrap_or_default();
+            registry.register(Arc::new(AtsSource::new(ats).with_watchlist(companies)));
+        }
+        registry
+    }
+
+    /// Fuentes integradas (con la watchlist de `watchlist.json`,
+    /// `JOB_HUNTER_WATCHLIST_FILE`) más las declaradas en `sources.json`
+    /// (`JOB_HUNTER_SOURCES_FILE`) y `recipes.json` (`JOB_HUNTER_RECIPES_FILE`).
+    pub fn load() -> Self {
+        let mut registry = Self::with_watchlist(&load_watchlist());
+        for def in load_custom_sources() {
+            registry.register(Arc::new(CustomSource::new(def)));
+        }
+        // Sitios con JavaScript: navegador headless
+        for recipe in load_site_recipes() {
+            registry.register(Arc::new(DynamicSource::new(recipe)));
+        }
+        registry
+    }
+
+    /// Añade `adapter`. Si ya hay una fuente con el mismo slug (mismo nombre
+    /// de agente) se ignora y devuelve `false`.
+    pub fn register(&mut self, adapter: Arc<dyn JobSourceAdapter>) -> bool {
+        let source = adapter.source();
+        if self.adapters.keys().any(|s| s.slug() == source.slug()) {
+            warn!("⚠️ Fuente '{}' duplica un scraper existente, se ignora.", source.slug());
+            return false;
+        }
+        self.adapters.insert(source, adapter);
+        true
+    }
+
+    pub fn get(&self, source: &JobSource) -> Option<&Arc<dyn JobSourceAdapter>> {
+        self.adapters.get(source)
+    }
+
+    pub fn sources(&self) -> impl Iterator<Item = &JobSource> {
+        self.adapters.keys()
+    }
+
+    /// Fuentes activadas en `criteria`.
+    pub fn enabled(&self, criteria: &SearchCriteria) -> Vec<JobSource> {
+        self.adapters
+            .iter()
+            .filter(|(_, a)| a.settings(criteria).enabled)
+            .map(|(s, _)| s.clone())
+            .collect()
+    }
+
+    /// Agente de scraping de `source`.
+    pub fn agent(&self, source: &JobSource) -> Option<Arc<dyn Agent>> {
+        let adapter = self.adapters.get(source)?.clone();
+        Some(Arc::new(SourceAgent::from_adapter(adapter)))
+    }
+
+    /// Un agente por fuente registrada.
+    pub fn agents(&self) -> Vec<Arc<dyn Agent>> {
+        self.adapters
+            .values()
+            .map(|a| Arc::new(SourceAgent::from_adapter(a.clone())) as Arc<dyn Agent>)
+            .collect()
+    }
+}
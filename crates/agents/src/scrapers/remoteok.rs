@@ -1,88 +1,47 @@
-use async_trait::async_trait;
-use chrono::Utc;
 use job_hunter_core::*;
-use std::time::Duration;
-use tokio::time::sleep;
-use tracing::{error, info};
-use uuid::Uuid;
 
-pub struct RemoteOkAgent;
+use super::adapter::{JobSourceAdapter, Listing, PageCursor, ScrapeContext};
+use super::query::{with_query, SearchQuery};
+use super::{json_native_id, parse_json, with_json_details};
 
-impl RemoteOkAgent {
-    pub fn new() -> Self {
-        Self
-    }
-}
-
-#[async_trait]
-impl Agent for RemoteOkAgent {
-    async fn process(&self, msg: AgentMessage) -> Result<AgentMessage, AgentError> {
-        if let AgentMessage::StartScraping(criteria) = msg {
-            // Configuración por defecto si no existe en la UI
-            let default_cfg = SourceSettings {
-                source: JobSource::RemoteOk,
-                enabled: true,
-                delay_ms: 1200,
-                user_agent: "Mozilla/5.0".to_string(),
-                use_proxy: false,
-            };
-
-            let my_cfg = criteria
-                .sources_config
-                .iter()
-                .find(|s| s.source == JobSource::RemoteOk)
-                .unwrap_or(&default_cfg);
-
-            if !my_cfg.enabled {
-                info!("ℹ️ [RemoteOK] Agente desactivado.");
-                return Ok(AgentMessage::RawJobsScraped(vec![]));
-            }
-
-            if my_cfg.delay_ms > 0 {
-                info!("⏳ [RemoteOK] Esperando {}ms (Anti-bot)...", my_cfg.delay_ms);
-                sleep(Duration::from_millis(my_cfg.delay_ms)).await;
-            }
-
-            info!("📡 [RemoteOK] Conectando a la API...");
+const API_URL: &str = "https://remoteok.com/api";
 
-            let client = reqwest::Client::builder()
-                .user_agent(&my_cfg.user_agent)
-                .timeout(Duration::from_secs(30))
-                .build()?;
+/// RemoteOK: API JSON que filtra por tag (`?tag=rust`) y pagina con `offset`.
+#[derive(Default)]
+pub struct RemoteOkSource;
 
-            let response = client.get("https://remoteok.com/api").send().await?;
-
-            if !response.status().is_success() {
-                let status = response.status();
-                error!("❌ [RemoteOK] Error HTTP: {}", status);
-                return Err(AgentError::Scraping(format!("Error HTTP: {}", status)));
-            }
-
-            let jobs: Vec<serde_json::Value> = response.json().await.map_err(|e| {
-                AgentError::Scraping(format!("Error parseando JSON: {}", e))
-            })?;
+impl JobSourceAdapter for RemoteOkSource {
+    fn source(&self) -> JobSource {
+        JobSource::RemoteOk
+    }
 
-            let mut postings = Vec::new();
-            for job in jobs.iter().skip(1).take(10) {
-                if let Some(url) = job["url"].as_str() {
-                    postings.push(RawJobPosting {
-                        id: Uuid::new_v4().to_string(),
-                        source: JobSource::RemoteOk,
-                        url: url.to_string(),
-                        html_content: job.to_string(),
-                        scraped_at: Utc::now(),
-                    });
-                }
-            }
+    fn label(&self) -> &str {
+        "RemoteOK"
+    }
 
-            info!("📂 [RemoteOK] Éxito: {} ofertas extraídas.", postings.len());
-            Ok(AgentMessage::RawJobsScraped(postings))
-        } else {
-            Err(AgentError::Scraping("Mensaje inválido".into()))
+    fn page_url(&self, _ctx: &ScrapeContext, term: Option<&str>, cursor: &PageCursor) -> Option<String> {
+        let mut params = Vec::new();
+        if let Some(t) = term {
+            params.push(("tag", SearchQuery::as_tag(t)));
+        }
+        if cursor.offset > 0 {
+            params.push(("offset", cursor.offset.to_string()));
         }
+        Some(with_query(API_URL, &params))
     }
 
-    fn name(&self) -> &str {
-        "scraper_remoteok"
+    fn parse_items(&self, _url: &str, body: &str) -> Result<Listing, AgentError> {
+        let jobs: Vec<serde_json::Value> = parse_json(body)?;
+        // El primer elemento es el aviso legal de la API
+        let postings = jobs
+            .iter()
+            .filter(|j| j.get("id").is_some())
+            .filter_map(|job| {
+                let url = job["url"].as_str()?;
+                let posting = RawJobPosting::new(JobSource::RemoteOk, json_native_id(job, "id").as_deref(), url, job.to_string());
+                Some(with_json_details(posting, job))
+            })
+            .collect();
+        Ok(Listing::new(postings))
     }
-}
\ No newline at end of file
+}
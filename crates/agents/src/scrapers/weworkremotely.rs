@@ -1,85 +1,49 @@
-use async_trait::async_trait;
-use chrono::Utc;
 use job_hunter_core::*;
-use std::time::Duration;
-use tokio::time::sleep;
-use tracing::info; // CORREGIDO: Eliminado 'warn'
-use uuid::Uuid;
 
-pub struct WwrAgent;
-
-impl WwrAgent {
-    pub fn new() -> Self {
-        Self
-    }
+use super::adapter::{JobSourceAdapter, KeywordSearch, Listing, PageCursor, ScrapeContext};
+use super::feed_listing;
+
+const FEED_URL: &str = "https://weworkremotely.com/remote-jobs.rss";
+
+/// We Work Remotely: feed RSS sin búsqueda.
+#[derive(Default)]
+pub struct WwrSource;
+
+/// En WWR el título viene como "Empresa: Puesto".
+fn split_company(posting: RawJobPosting) -> RawJobPosting {
+    let Some((company, title)) = posting
+        .normalized
+        .title
+        .as_deref()
+        .and_then(|t| t.split_once(": "))
+        .map(|(c, t)| (c.to_string(), t.to_string()))
+    else {
+        return posting;
+    };
+    let apply_url = posting.normalized.apply_url.clone();
+    posting.with_details(Some(title), Some(company), apply_url)
 }
 
-#[async_trait]
-impl Agent for WwrAgent {
-    async fn process(&self, msg: AgentMessage) -> Result<AgentMessage, AgentError> {
-        if let AgentMessage::StartScraping(criteria) = msg {
-            // 1. Verificar configuración
-            let default_cfg = SourceSettings {
-                source: JobSource::WeWorkRemotely,
-                enabled: true,
-                delay_ms: 1200,
-                user_agent: "Mozilla/5.0".to_string(),
-                use_proxy: false,
-            };
-
-            let my_cfg = criteria
-                .sources_config
-                .iter()
-                .find(|s| s.source == JobSource::WeWorkRemotely)
-                .unwrap_or(&default_cfg);
-
-            if !my_cfg.enabled {
-                info!("ℹ️ [WWR] Agente desactivado por configuración.");
-                return Ok(AgentMessage::RawJobsScraped(vec![]));
-            }
-
-            if my_cfg.delay_ms > 0 {
-                info!("⏳ [WWR] Esperando {}ms (Anti-bot)...", my_cfg.delay_ms);
-                sleep(Duration::from_millis(my_cfg.delay_ms)).await;
-            }
+impl JobSourceAdapter for WwrSource {
+    fn source(&self) -> JobSource {
+        JobSource::WeWorkRemotely
+    }
 
-            let client = reqwest::Client::builder()
-                .user_agent(&my_cfg.user_agent)
-                .timeout(Duration::from_secs(30))
-                .build()?;
+    fn label(&self) -> &str {
+        "WWR"
+    }
 
-            let res = client
-                .get("https://weworkremotely.com/remote-jobs.rss")
-                .send()
-                .await?
-                .text()
-                .await?;
+    fn keyword_search(&self) -> KeywordSearch {
+        KeywordSearch::Listing
+    }
 
-            let mut postings = Vec::new();
-            // Parsing simple de RSS para la demo
-            for cap in res.split("<item>").skip(1).take(10) {
-                if let Some(url) = cap
-                    .split("<link>")
-                    .nth(1)
-                    .and_then(|s| s.split("</link>").next())
-                {
-                    postings.push(RawJobPosting {
-                        id: Uuid::new_v4().to_string(),
-                        source: JobSource::WeWorkRemotely,
-                        url: url.trim().to_string(),
-                        html_content: cap.to_string(),
-                        scraped_at: Utc::now(),
-                    });
-                }
-            }
-            
-            info!("📂 [WWR] Éxito: {} ofertas extraídas.", postings.len());
-            Ok(AgentMessage::RawJobsScraped(postings))
-        } else {
-            Err(AgentError::Scraping("Msg inválido".into()))
-        }
+    fn page_url(&self, _ctx: &ScrapeContext, _term: Option<&str>, cursor: &PageCursor) -> Option<String> {
+        (cursor.page == 0).then(|| FEED_URL.to_string())
     }
-    fn name(&self) -> &str {
-        "scraper_wwr"
+
+    fn parse_items(&self, _url: &str, body: &str) -> Result<Listing, AgentError> {
+        let mut listing = feed_listing(self.source(), body);
+        listing.postings = listing.postings.into_iter().map(split_company).collect();
+        Ok(listing)
     }
 }
@@ -0,0 +1,261 @@
+//! Datos estructurados schema.org `JobPosting` de una página de detalle, en
+//! JSON-LD (`<script type="application/ld+json">`) o microdata
+//! (`itemscope itemtype=".../JobPosting"`). Son datos que publica el propio
+//! board, así que para salario, fechas y empresa mandan sobre lo que
+//! deduzca el LLM o el parseo heurístico del texto.
+
+use chrono::{DateTime, Utc};
+use job_hunter_core::{JobType, RawJobPosting, SalaryPeriod, SalaryRange};
+use scraper::{ElementRef, Html, Selector};
+use serde_json::{Map, Value};
+
+use crate::content::plain_text;
+use crate::dates::date_from_value;
+use crate::salary::{currency_code, parse_salary_field};
+
+/// Campos de un `JobPosting` que usa el pipeline.
+#[derive(Debug, Clone, Default)]
+pub struct StructuredJob {
+    pub title: Option<String>,
+    pub company: Option<String>,
+    pub date_posted: Option<DateTime<Utc>>,
+    pub valid_through: Option<DateTime<Utc>>,
+    pub salary: Option<SalaryRange>,
+    pub job_type: Option<JobType>,
+    pub location: Option<String>,
+    pub is_remote: Option<bool>,
+    /// Descripción en texto plano.
+    pub description: Option<String>,
+    pub skills: Vec<String>,
+}
+
+/// Primer `JobPosting` de `html` (JSON-LD y, si no hay, microdata).
+pub fn extract_job_posting(html: &str) -> Option<StructuredJob> {
+    // Atajo barato: la mayoría de contenidos (JSON de APIs, items RSS) no lo llevan
+    if !html.contains("JobPosting") {
+        return None;
+    }
+    let doc = Html::parse_document(html);
+    json_ld(&doc)
+        .or_else(|| microdata(&doc))
+        .map(|v| from_json(&v))
+}
+
+/// Rellena la oferta con los datos estructurados de `html` (la página
+/// completa, no solo el cuerpo). Salario, fechas y empresa sustituyen a los
+/// existentes; título, descripción, skills, ubicación y tipo de contrato solo
+/// se completan.
+pub fn with_structured_data(posting: RawJobPosting, html: &str) -> RawJobPosting {
+    let Some(s) = extract_job_posting(html) else {
+        return posting;
+    };
+    let title = posting.normalized.title.clone().or(s.title);
+    let company = s.company.or(posting.normalized.company.clone());
+    let apply_url = posting.normalized.apply_url.clone();
+    let salary = s.salary.or(posting.normalized.salary.clone());
+    let posted_at = s.date_posted.or(posting.normalized.posted_at);
+    let location = posting.normalized.location.clone().or(s.location);
+    let is_remote = posting.normalized.is_remote.or(s.is_remote);
+    let job_type = posting.normalized.job_type.clone().or(s.job_type);
+    let valid_through = s.valid_through.or(posting.normalized.valid_through);
+    let description = posting.normalized.description.clone().or(s.description);
+    let tags = if posting.normalized.tags.is_empty() { s.skills } else { posting.normalized.tags.clone() };
+    posting
+        .with_details(title, company, apply_url)
+        .with_description(description, tags)
+        .with_salary(salary)
+        .with_posted_at(posted_at)
+        .with_location(location, is_remote)
+        .with_job_type(job_type)
+        .with_valid_through(valid_through)
+}
+
+fn json_ld(doc: &Html) -> Option<Value> {
+    let sel = Selector::parse(r#"script[type="application/ld+json"]"#).ok()?;
+    doc.select(&sel).find_map(|script| {
+        let text = script.text().collect::<String>();
+        let json: Value = serde_json::from_str(text.trim()).ok()?;
+        find_job_posting(&json).cloned()
+    })
+}
+
+/// Busca el objeto `JobPosting` en la raíz, en listas o en `@graph`.
+fn find_job_posting(value: &Value) -> Option<&Value> {
+    match value {
+        Value::Array(items) => items.iter().find_map(find_job_posting),
+        Value::Object(map) => {
+            if is_type(map.get("@type"), "JobPosting") {
+                return Some(value);
+            }
+            map.get("@graph").and_then(find_job_posting)
+        }
+        _ => None,
+    }
+}
+
+fn is_type(value: Option<&Value>, name: &str) -> bool {
+    match value {
+        Some(Value::String(t)) => t.rsplit('/').next() == Some(name),
+        Some(Value::Array(types)) => types.iter().any(|t| is_type(Some(t), name)),
+        _ => false,
+    }
+}
+
+/// Convierte el microdata `JobPosting` al mismo JSON que el JSON-LD.
+fn microdata(doc: &Html) -> Option<Value> {
+    let sel = Selector::parse("[itemscope][itemtype]").ok()?;
+    let scope = doc
+        .select(&sel)
+        .find(|el| el.value().attr("itemtype").is_some_and(|t| t.ends_with("/JobPosting")))?;
+    Some(item_scope(scope))
+}
+
+fn item_scope(scope: ElementRef) -> Value {
+    let mut map = Map::new();
+    collect_props(scope, &mut map);
+    Value::Object(map)
+}
+
+/// `itemprop` descendientes de `el` que pertenecen a su scope (sin entrar en
+/// los `itemscope` anidados, que se convierten en objetos).
+fn collect_props(el: ElementRef, map: &mut Map<String, Value>) {
+    for child in el.children().filter_map(ElementRef::wrap) {
+        let nested = child.value().attr("itemscope").is_some();
+        if let Some(props) = child.value().attr("itemprop") {
+            let value = if nested { item_scope(child) } else { prop_value(child) };
+            for prop in props.split_whitespace() {
+                map.entry(prop.to_string()).or_insert_with(|| value.clone());
+            }
+        }
+        if !nested {
+            collect_props(child, map);
+        }
+    }
+}
+
+fn prop_value(el: ElementRef) -> Value {
+    let v = el.value();
+    let text = v
+        .attr("content")
+        .or_else(|| v.attr("datetime"))
+        .map(str::to_string)
+        .or_else(|| (v.name() == "a" || v.name() == "link").then(|| v.attr("href").map(str::to_string)).flatten())
+        .unwrap_or_else(|| el.text().flat_map(str::split_whitespace).collect::<Vec<_>>().join(" "));
+    Value::String(text)
+}
+
+fn from_json(job: &Value) -> StructuredJob {
+    StructuredJob {
+        title: text(job.get("title")),
+        company: job.get("hiringOrganization").and_then(|o| match o {
+            Value::String(_) => text(Some(o)),
+            _ => text(o.get("name")),
+        }),
+        date_posted: job.get("datePosted").and_then(date_from_value),
+        valid_through: job.get("validThrough").and_then(date_from_value),
+        salary: job.get("baseSalary").and_then(base_salary),
+        job_type: list(job.get("employmentType")).iter().find_map(|t| JobType::from_label(t)),
+        location: location(job),
+        is_remote: job
+            .get("jobLocationType")
+            .and_then(Value::as_str)
+            .map(|t| t.eq_ignore_ascii_case("TELECOMMUTE"))
+            .filter(|remote| *remote),
+        description: text(job.get("description")).map(|d| plain_text(&d)),
+        skills: list(job.get("skills")).iter().flat_map(|s| s.split(',')).map(str::to_string).collect(),
+    }
+}
+
+/// Texto no vacío (los números se aceptan como texto).
+fn text(value: Option<&Value>) -> Option<String> {
+    match value? {
+        Value::String(s) => Some(s.trim().to_string()).filter(|s| !s.is_empty()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// Valor único o lista de valores.
+fn list(value: Option<&Value>) -> Vec<String> {
+    match value {
+        Some(Value::Array(items)) => items.iter().filter_map(|v| text(Some(v))).collect(),
+        v => text(v).into_iter().collect(),
+    }
+}
+
+/// `MonetaryAmount` con `QuantitativeValue` (`minValue`/`maxValue`/`value` y
+/// `unitText`), un número suelto o un texto ("$120k - $150k"). Una moneda
+/// desconocida descarta el salario: el texto de la página no llega a la UI.
+fn base_salary(salary: &Value) -> Option<SalaryRange> {
+    let currency = match text(salary.get("currency")) {
+        Some(raw) => currency_code(&raw)?,
+        None => "USD",
+    };
+    let value = salary.get("value").unwrap_or(salary);
+    let amount = |v: Option<&Value>| match v? {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.replace([',', ' '], "").parse().ok(),
+        _ => None,
+    }
+    .filter(|a: &f64| *a > 0.0);
+
+    let (min, max) = match value {
+        Value::Object(_) => {
+            let exact = amount(value.get("value"));
+            (amount(value.get("minValue")).or(exact), amount(value.get("maxValue")).or(exact))
+        }
+        Value::String(s) if amount(Some(value)).is_none() => return parse_salary_field(s, currency),
+        _ => (amount(Some(value)), amount(Some(value))),
+    };
+    let (min, max) = (min.or(max)?, max.or(min)?);
+
+    let unit = text(value.get("unitText")).or_else(|| text(salary.get("unitText")));
+    let period = match unit.as_deref().map(str::to_uppercase).as_deref() {
+        Some("HOUR") => SalaryPeriod::Hourly,
+        Some("DAY") => SalaryPeriod::Daily,
+        Some("MONTH") => SalaryPeriod::Monthly,
+        _ => SalaryPeriod::Yearly,
+    };
+    let (min, max) = if min <= max { (min, max) } else { (max, min) };
+    Some(SalaryRange {
+        min: min.round() as u32,
+        max: max.round() as u32,
+        currency: currency.to_string(),
+        period,
+    })
+}
+
+/// "Ciudad, Región, País" de cada `jobLocation` o, en remotos, los
+/// requisitos de ubicación del candidato.
+fn location(job: &Value) -> Option<String> {
+    let places: Vec<String> = match job.get("jobLocation") {
+        Some(Value::Array(items)) => items.iter().filter_map(place).collect(),
+        Some(v) => place(v).into_iter().collect(),
+        None => vec![],
+    };
+    if !places.is_empty() {
+        return Some(places.join("; "));
+    }
+    let regions: Vec<String> = match job.get("applicantLocationRequirements") {
+        Some(Value::Array(items)) => items.iter().filter_map(|r| text(r.get("name")).or_else(|| text(Some(r)))).collect(),
+        Some(r) => text(r.get("name")).or_else(|| text(Some(r))).into_iter().collect(),
+        None => vec![],
+    };
+    (!regions.is_empty()).then(|| format!("Remote ({})", regions.join(", ")))
+}
+
+fn place(place: &Value) -> Option<String> {
+    let address = match place.get("address") {
+        Some(a @ Value::Object(_)) => a,
+        Some(a) => return text(Some(a)),
+        None => return text(place.get("name")).or_else(|| text(Some(place))),
+    };
+    let country = address
+        .get("addressCountry")
+        .and_then(|c| text(c.get("name")).or_else(|| text(Some(c))));
+    let parts: Vec<String> = [text(address.get("addressLocality")), text(address.get("addressRegion")), country]
+        .into_iter()
+        .flatten()
+        .collect();
+    (!parts.is_empty()).then(|| parts.join(", "))
+}
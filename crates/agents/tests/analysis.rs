@@ -0,0 +1,38 @@
+//! Los datos normalizados de la fuente mandan sobre lo que devuelve el LLM.
+
+use job_hunter_agents::analyzer::LlmAnalysis;
+use job_hunter_core::*;
+
+#[test]
+fn normalized_facts_win_over_llm_guesses() {
+    let raw = RawJobPosting::new(JobSource::RemoteOk, Some("1"), "https://remoteok.com/remote-jobs/1", "{}")
+        .with_details(Some("Senior Rust Engineer".into()), Some("Acme Systems".into()), None)
+        .with_description(
+            Some("Build low-latency services in Rust.\n\n## Requirements\n- Tokio".into()),
+            vec!["rust".into(), "backend".into()],
+        )
+        .with_location(Some("Worldwide".into()), Some(true))
+        .with_job_type(Some(JobType::Contract));
+    // Respuesta del prompt de puntuación: sin datos factuales salvo errores del modelo
+    let analysis: LlmAnalysis = serde_json::from_value(serde_json::json!({
+        "title": "Rust Dev",
+        "location": "Berlin",
+        "is_remote": false,
+        "match_score": 0.8,
+        "match_reasons": ["Rust"],
+        "red_flags": [],
+        "skills_analysis": { "matching": ["rust"], "missing": ["kafka"] }
+    }))
+    .unwrap();
+    let criteria = SearchCriteria::default();
+    let job = analysis.into_analyzed(&raw, &criteria);
+
+    assert_eq!(job.title, "Senior Rust Engineer");
+    assert_eq!(job.company.map(|c| c.name).as_deref(), Some("Acme Systems"));
+    assert_eq!((job.location.as_str(), job.is_remote), ("Worldwide", true));
+    assert!(matches!(job.job_type, JobType::Contract));
+    assert_eq!(job.description, "Build low-latency services in Rust.");
+    assert_eq!(job.skills, ["rust", "backend"]);
+    assert_eq!(job.skills_analysis.missing, ["kafka"]);
+    assert!((job.match_score - 0.8).abs() < f32::EPSILON);
+}
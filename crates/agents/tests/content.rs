@@ -0,0 +1,91 @@
+//! Texto limpio que recibe el LLM a partir del contenido de cada tipo de fuente.
+
+use job_hunter_agents::content::job_text;
+
+const DETAIL_PAGE: &str = r#"<!DOCTYPE html>
+<html>
+<head>
+  <title>Senior Rust Engineer | Acme</title>
+  <style>body { font-family: sans-serif; } .nav { color: red; }</style>
+  <script>window.dataLayer = [{"event": "page_view"}];</script>
+</head>
+<body>
+  <header><nav><a href="/">Home</a> <a href="/jobs">All jobs</a> <a href="/login">Log in</a></nav></header>
+  <h1>Senior Rust Engineer</h1>
+  <div class="job_description">
+    <p>Acme builds the payments network behind thousands of online stores. We are looking for a
+       <strong>Senior Rust Engineer</strong> to own our settlement services end to end.</p>
+    <h3>What you will do</h3>
+    <ul>
+      <li><p>Design and operate async services with Tokio and PostgreSQL.</p></li>
+      <li>Improve observability across the platform.</li>
+    </ul>
+    <h3>Requirements</h3>
+    <ol>
+      <li>5+ years of backend experience.</li>
+      <li>Fluent written English.</li>
+    </ol>
+  </div>
+  <aside>Similar jobs: Go Developer, Python Developer</aside>
+  <footer>© Acme 2025 · Privacy · Cookies</footer>
+  <script src="/app.js"></script>
+</body>
+</html>"#;
+
+#[test]
+fn html_detail_keeps_main_body_as_markdown() {
+    let text = job_text(DETAIL_PAGE);
+
+    assert!(text.starts_with("# Senior Rust Engineer\n"), "{text}");
+    assert!(text.contains("### What you will do\n\n- Design and operate async services with Tokio and PostgreSQL.\n- Improve observability"), "{text}");
+    assert!(text.contains("### Requirements\n\n1. 5+ years of backend experience.\n2. Fluent written English."), "{text}");
+    assert!(text.contains("a Senior Rust Engineer to own"), "{text}");
+    for noise in ["dataLayer", "font-family", "Log in", "Similar jobs", "Privacy", "app.js"] {
+        assert!(!text.contains(noise), "boilerplate '{noise}' en: {text}");
+    }
+    assert!(text.len() * 2 < DETAIL_PAGE.len());
+}
+
+#[test]
+fn rss_item_unwraps_escaped_description() {
+    let item = r#"<item>
+      <title>Fieldwire: Senior Backend Engineer</title>
+      <link>https://weworkremotely.com/remote-jobs/fieldwire-senior-backend-engineer</link>
+      <pubDate>Thu, 16 Oct 2025 10:00:00 +0000</pubDate>
+      <category>Full-Time</category>
+      <description>&lt;p&gt;Rails and Go services.&lt;/p&gt;&lt;ul&gt;&lt;li&gt;Remote (US)&lt;/li&gt;&lt;/ul&gt;</description>
+    </item>"#;
+    let text = job_text(item);
+
+    assert_eq!(
+        text,
+        "# Fieldwire: Senior Backend Engineer\nPublished: 2025-10-16\nCategories: Full-Time\n\nRails and Go services.\n\n- Remote (US)"
+    );
+}
+
+#[test]
+fn json_item_becomes_labeled_fields() {
+    let item = r#"{
+        "id": 123,
+        "title": "Platform Engineer",
+        "company_logo": "https://cdn.example.com/logo.png",
+        "company": { "name": "Globex", "size": "50-200" },
+        "tags": ["kubernetes", "terraform"],
+        "remote": true,
+        "description": "<p>Run our <b>Kubernetes</b> fleet.</p><ul><li>On-call 1 week/month</li></ul>"
+    }"#;
+    let text = job_text(item);
+
+    for line in [
+        "id: 123",
+        "title: Platform Engineer",
+        "company.name: Globex",
+        "company.size: 50-200",
+        "tags: kubernetes, terraform",
+        "remote: true",
+    ] {
+        assert!(text.lines().any(|l| l == line), "falta '{line}' en: {text}");
+    }
+    assert!(text.ends_with("## description\nRun our Kubernetes fleet.\n\n- On-call 1 week/month"), "{text}");
+    assert!(!text.contains("logo"), "{text}");
+}
@@ -0,0 +1,54 @@
+//! Fuentes declarativas cargadas de `tests/fixtures/custom/sources.json` y
+//! ejecutadas en `replay` contra `tests/fixtures/jobs.example.test/`: cada
+//! tipo de paginación recorre sus páginas y un `items_path` erróneo falla.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use job_hunter_agents::http::{HttpTape, TapeMode};
+use job_hunter_agents::scrapers::custom::{load_custom_sources, CustomSource, CustomSourceDef};
+use job_hunter_agents::SourceAgent;
+use job_hunter_core::*;
+
+fn fixtures() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures")
+}
+
+fn definitions() -> Vec<CustomSourceDef> {
+    std::env::set_var("JOB_HUNTER_SOURCES_FILE", fixtures().join("custom/sources.json"));
+    load_custom_sources()
+}
+
+async fn run(def: CustomSourceDef) -> Result<Vec<RawJobPosting>, AgentError> {
+    HttpTape::install(Some(HttpTape::new(TapeMode::Replay, fixtures())));
+    let agent = SourceAgent::new(CustomSource::new(def));
+    match agent.process(AgentMessage::StartScraping(Arc::new(SearchCriteria::default()))).await? {
+        AgentMessage::RawJobsScraped(postings) => Ok(postings),
+        other => panic!("respuesta inesperada: {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn sources_json_definitions() {
+    let defs = definitions();
+    let names: Vec<&str> = defs.iter().map(|d| d.name.as_str()).collect();
+    assert_eq!(names, ["paged_api", "offset_api", "next_api", "broken_api"]);
+
+    // `{page}` desde `start`, `{offset}` con los items leídos y `meta.next`
+    let pages: [&[u32]; 3] = [&[1, 2, 3], &[4, 5, 6], &[7, 8]];
+    for (def, jobs) in defs.iter().zip(pages) {
+        let postings = run(def.clone()).await.unwrap_or_else(|e| panic!("[{}] {e}", def.name));
+        let got: Vec<&str> = postings.iter().map(|p| p.url.as_str()).collect();
+        let expected: Vec<String> = jobs.iter().map(|n| format!("https://jobs.example.test/jobs/{n}")).collect();
+        assert_eq!(got, expected, "[{}]", def.name);
+        for p in &postings {
+            assert_eq!(p.source, JobSource::Custom(def.name.clone()));
+            assert!(p.normalized.company.as_deref().is_some_and(|c| c.starts_with("Company ")));
+            assert!(p.normalized.posted_at.is_some());
+            assert_eq!(p.normalized.tags, ["rust", "tokio"]);
+        }
+    }
+
+    let err = run(defs[3].clone()).await.unwrap_err();
+    assert!(err.to_string().contains("items_path 'data.jobs' no es un array"), "{err}");
+}
@@ -0,0 +1,52 @@
+//! Fechas de publicación de cada fuente, en UTC.
+
+use chrono::{TimeZone, Utc};
+use job_hunter_agents::dates::{parse_posted_date, posted_at_from_json};
+use serde_json::json;
+
+#[test]
+fn remoteok_epoch() {
+    let item = json!({ "epoch": 1_727_776_800, "date": "2024-10-01T10:00:00+00:00" });
+    assert_eq!(posted_at_from_json(&item), Some(Utc.with_ymd_and_hms(2024, 10, 1, 10, 0, 0).unwrap()));
+
+    // Milisegundos (Lever `createdAt`)
+    let item = json!({ "epoch": 1_727_776_800_000_i64 });
+    assert_eq!(posted_at_from_json(&item), Some(Utc.with_ymd_and_hms(2024, 10, 1, 10, 0, 0).unwrap()));
+}
+
+#[test]
+fn rss_pub_date() {
+    assert_eq!(
+        parse_posted_date("Tue, 01 Oct 2024 12:30:00 +0200"),
+        Some(Utc.with_ymd_and_hms(2024, 10, 1, 10, 30, 0).unwrap())
+    );
+    assert_eq!(
+        parse_posted_date("Tue, 01 Oct 2024 10:30:00 GMT"),
+        Some(Utc.with_ymd_and_hms(2024, 10, 1, 10, 30, 0).unwrap())
+    );
+}
+
+#[test]
+fn arbeitnow_created_at() {
+    // Arbeitnow da `created_at` como epoch en segundos
+    let item = json!({ "created_at": 1_727_776_800 });
+    assert_eq!(posted_at_from_json(&item), Some(Utc.with_ymd_and_hms(2024, 10, 1, 10, 0, 0).unwrap()));
+    assert_eq!(
+        parse_posted_date("2024-10-01 10:00:00"),
+        Some(Utc.with_ymd_and_hms(2024, 10, 1, 10, 0, 0).unwrap())
+    );
+}
+
+/// Un número pequeño no es un epoch: mejor sin fecha que una de 1970 que
+/// `max_age_days` descartaría.
+#[test]
+fn small_numbers_are_not_epochs() {
+    for raw in ["2024", "12345", "0", "-5"] {
+        assert_eq!(parse_posted_date(raw), None, "{raw}");
+    }
+    assert_eq!(posted_at_from_json(&json!({ "date": 2024 })), None);
+    assert_eq!(
+        parse_posted_date("2024-10-01"),
+        Some(Utc.with_ymd_and_hms(2024, 10, 1, 0, 0, 0).unwrap())
+    );
+}
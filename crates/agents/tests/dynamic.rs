@@ -0,0 +1,80 @@
+//! `DynamicSource` contra páginas locales que se pintan con JavaScript
+//! (`tests/fixtures/dynamic/`), servidas por un servidor HTTP mínimo.
+//!
+//! Necesita Chrome/Chromium (o `JOB_HUNTER_CHROME_PATH`):
+//! `cargo test -p job-hunter-agents --test dynamic -- --ignored`.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use job_hunter_agents::scrapers::dynamic::{DynamicSource, SiteRecipe};
+use job_hunter_agents::SourceAgent;
+use job_hunter_core::*;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// Sirve `tests/fixtures/dynamic/` en un puerto libre y devuelve la URL base.
+async fn serve_fixtures() -> String {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/dynamic");
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let dir = dir.clone();
+            tokio::spawn(async move {
+                let mut buf = vec![0u8; 4096];
+                let n = stream.read(&mut buf).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&buf[..n]);
+                let path = request.split_whitespace().nth(1).unwrap_or("/");
+                let file = dir.join(path.trim_start_matches('/'));
+                let response = match std::fs::read_to_string(&file).ok().filter(|_| path.ends_with(".html")) {
+                    Some(body) => format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    ),
+                    None => "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string(),
+                };
+                let _ = stream.write_all(response.as_bytes()).await;
+            });
+        }
+    });
+    base
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[ignore = "requiere Chrome/Chromium"]
+async fn dynamic_recipe_extracts_one_posting_per_detail_page() {
+    let base = serve_fixtures().await;
+    let recipe: SiteRecipe = serde_json::from_value(serde_json::json!({
+        "name": "local_spa",
+        "start_url": format!("{}/jobs.html", base),
+        "wait_selector": "li.job-card",
+        "actions": [
+            { "type": "click", "selector": "#load-more", "times": 3, "pause_ms": 300 },
+            { "type": "scroll" }
+        ],
+        "job_link_selector": "li.job-card",
+        "detail_selector": "article.job-detail",
+        "company_selector": ".company",
+        "delay_ms": 0
+    }))
+    .unwrap();
+    let agent = SourceAgent::new(DynamicSource::new(recipe));
+
+    let criteria = SearchCriteria::default();
+    let postings = match agent.process(AgentMessage::StartScraping(Arc::new(criteria))).await {
+        Ok(AgentMessage::RawJobsScraped(p)) => p,
+        other => panic!("respuesta inesperada: {:?}", other.map(|_| ())),
+    };
+
+    let titles: Vec<_> = postings.iter().filter_map(|p| p.normalized.title.as_deref()).collect();
+    assert_eq!(titles, ["Senior Rust Engineer", "Platform Engineer", "Data Engineer"]);
+    for p in &postings {
+        assert_eq!(p.source, JobSource::Custom("local_spa".into()));
+        assert!(p.url.starts_with(&base) && p.url.ends_with(".html"), "{}", p.url);
+        assert!(p.html_content.starts_with("<article"), "{}", p.html_content);
+        assert!(p.normalized.company.is_some());
+    }
+}
@@ -0,0 +1,125 @@
+//! Parser de feeds RSS/Atom: CDATA, entidades, namespaces y XML roto.
+
+use job_hunter_agents::feed::{decode_entities, parse_feed};
+
+#[test]
+fn rss_item_with_cdata_and_namespaces() {
+    let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0" xmlns:content="http://purl.org/rss/1.0/modules/content/" xmlns:dc="http://purl.org/dc/elements/1.1/">
+  <channel>
+    <title>Feed title (no es un item)</title>
+    <!-- <item><title>comentado</title></item> -->
+    <item>
+      <title><![CDATA[Senior Rust Engineer <Remote>]]></title>
+      <link>https://example.com/jobs/1</link>
+      <guid isPermaLink="false">job-1</guid>
+      <pubDate>Tue, 02 Jul 2024 10:00:00 +0000</pubDate>
+      <category>rust</category>
+      <category><![CDATA[backend]]></category>
+      <description>Resumen &amp; más</description>
+      <content:encoded><![CDATA[<p>Build <b>fast</b> things</p>]]></content:encoded>
+      <dc:creator>Acme &#38; Co</dc:creator>
+    </item>
+  </channel>
+</rss>"#;
+    let items = parse_feed(xml);
+    assert_eq!(items.len(), 1);
+    let item = &items[0];
+    assert_eq!(item.title.as_deref(), Some("Senior Rust Engineer <Remote>"));
+    assert_eq!(item.link.as_deref(), Some("https://example.com/jobs/1"));
+    assert_eq!(item.guid.as_deref(), Some("job-1"));
+    assert_eq!(item.pub_date.map(|d| d.to_rfc3339()).as_deref(), Some("2024-07-02T10:00:00+00:00"));
+    assert_eq!(item.categories, ["rust", "backend"]);
+    assert_eq!(item.description.as_deref(), Some("Resumen & más"));
+    assert_eq!(item.content.as_deref(), Some("<p>Build <b>fast</b> things</p>"));
+    assert_eq!(item.body(), item.content.as_deref());
+    assert_eq!(item.author.as_deref(), Some("Acme & Co"));
+    assert!(item.raw.starts_with("<item>") && item.raw.ends_with("</item>"));
+}
+
+#[test]
+fn entity_encoded_links() {
+    let xml = "<rss><channel><item>\
+        <title>Dev &ndash; Berlin</title>\
+        <link>https://example.com/job?id=7&amp;utm_source=rss</link>\
+        <description>&lt;p&gt;Hola&lt;/p&gt;</description>\
+        </item></channel></rss>";
+    let item = &parse_feed(xml)[0];
+    assert_eq!(item.title.as_deref(), Some("Dev – Berlin"));
+    assert_eq!(item.link.as_deref(), Some("https://example.com/job?id=7&utm_source=rss"));
+    assert_eq!(item.description.as_deref(), Some("<p>Hola</p>"));
+}
+
+#[test]
+fn atom_entries() {
+    let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom">
+  <title>Jobs</title>
+  <entry>
+    <id>tag:example.com,2024:42</id>
+    <title type="text">Platform Engineer</title>
+    <link rel="self" href="https://example.com/api/42"/>
+    <link rel="alternate" type="text/html" href="https://example.com/jobs/42?a=1&amp;b=2"/>
+    <updated>2024-07-03T08:00:00Z</updated>
+    <published>2024-07-01T08:00:00Z</published>
+    <category term="devops"/>
+    <author><name>Globex</name><email>jobs@globex.test</email></author>
+    <summary>Short</summary>
+    <content type="xhtml"><div><p>Full <em>text</em></p></div></content>
+  </entry>
+  <entry>
+    <title>Second</title>
+    <link href="https://example.com/jobs/43"/>
+  </entry>
+</feed>"#;
+    let items = parse_feed(xml);
+    assert_eq!(items.len(), 2);
+    let first = &items[0];
+    assert_eq!(first.guid.as_deref(), Some("tag:example.com,2024:42"));
+    assert_eq!(first.title.as_deref(), Some("Platform Engineer"));
+    assert_eq!(first.link.as_deref(), Some("https://example.com/jobs/42?a=1&b=2"));
+    // `published` tiene prioridad sobre `updated`
+    assert_eq!(first.pub_date.map(|d| d.to_rfc3339()).as_deref(), Some("2024-07-01T08:00:00+00:00"));
+    assert_eq!(first.categories, ["devops"]);
+    assert_eq!(first.author.as_deref(), Some("Globex"));
+    assert_eq!(first.description.as_deref(), Some("Short"));
+    assert_eq!(first.content.as_deref(), Some("<div><p>Full <em>text</em></p></div>"));
+    assert_eq!(items[1].link.as_deref(), Some("https://example.com/jobs/43"));
+}
+
+/// Etiquetas sin cerrar dentro del item no rompen el resto de campos.
+#[test]
+fn malformed_item_keeps_known_fields() {
+    let xml = "<rss><channel><item>\
+        <title>Backend Dev</title>\
+        <description>Sin cerrar <br> en medio</description>\
+        <link>https://example.com/jobs/9</link>\
+        </item></channel></rss>";
+    let items = parse_feed(xml);
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].title.as_deref(), Some("Backend Dev"));
+    assert_eq!(items[0].link.as_deref(), Some("https://example.com/jobs/9"));
+}
+
+/// Un documento truncado devuelve los items completos leídos hasta el corte.
+#[test]
+fn unclosed_input_returns_complete_items() {
+    let xml = "<rss><channel>\
+        <item><title>One</title></item>\
+        <item><title>Two</title><description><![CDATA[truncated";
+    let items = parse_feed(xml);
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].title.as_deref(), Some("One"));
+
+    assert!(parse_feed("<item><title>Half").is_empty());
+    assert!(parse_feed("<item><title attr=\"never closed").is_empty());
+    assert!(parse_feed("").is_empty());
+    assert!(parse_feed("not xml at all").is_empty());
+}
+
+#[test]
+fn entities() {
+    assert_eq!(decode_entities("a &amp; b &lt;c&gt; &quot;d&quot; &apos;e&apos;"), "a & b <c> \"d\" 'e'");
+    assert_eq!(decode_entities("&#233;&#xE9;&#XE9; &euro;5"), "ééé €5");
+    // Entidades desconocidas o sin cerrar se dejan tal cual
+    assert_eq!(decode_entities("AT&T &unknown; &amp"), "AT&T &unknown; &amp");
+}
@@ -0,0 +1,118 @@
+//! Caché HTTP en disco contra un servidor local: respuestas vigentes sin red,
+//! revalidación con ETag / Last-Modified (304) y caducidad por `cache_max_age_secs`.
+
+use std::sync::{Arc, Mutex, OnceLock};
+
+use job_hunter_agents::http::{HttpTape, SourceClient};
+use job_hunter_core::{JobSource, SourceSettings};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// Peticiones recibidas: ruta y si traían cabecera condicional.
+type Hits = Arc<Mutex<Vec<(String, bool)>>>;
+
+/// Caché en un directorio temporal y sin grabadora, aunque el entorno la active.
+fn setup() {
+    static INIT: OnceLock<()> = OnceLock::new();
+    INIT.get_or_init(|| {
+        let dir = std::env::temp_dir().join(format!("job_hunter_http_cache_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::env::set_var("JOB_HUNTER_HTTP_CACHE_DIR", &dir);
+        HttpTape::install(None);
+    });
+}
+
+/// Servidor que responde 200 con validadores y 304 si la petición es condicional.
+async fn server() -> (String, Hits) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    let hits: Hits = Arc::default();
+    let log = hits.clone();
+    tokio::spawn(async move {
+        loop {
+            let Ok((mut stream, _)) = listener.accept().await else { return };
+            let mut buf = vec![0u8; 4096];
+            let n = stream.read(&mut buf).await.unwrap_or(0);
+            let req = String::from_utf8_lossy(&buf[..n]).to_ascii_lowercase();
+            let path = req.split_whitespace().nth(1).unwrap_or("/").to_string();
+            let conditional = if path.starts_with("/etag") {
+                req.contains("if-none-match: \"v1\"")
+            } else {
+                req.contains("if-modified-since: tue, 02 jul 2024 10:00:00 gmt")
+            };
+            log.lock().unwrap().push((path.clone(), conditional));
+
+            let res = if conditional {
+                "HTTP/1.1 304 Not Modified\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string()
+            } else {
+                let validator = if path.starts_with("/etag") {
+                    "ETag: \"v1\""
+                } else {
+                    "Last-Modified: Tue, 02 Jul 2024 10:00:00 GMT"
+                };
+                let body = format!("body of {path}");
+                format!(
+                    "HTTP/1.1 200 OK\r\n{validator}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+            };
+            let _ = stream.write_all(res.as_bytes()).await;
+        }
+    });
+    (base, hits)
+}
+
+fn client(max_age_secs: u64) -> SourceClient {
+    let mut cfg = SourceSettings::defaults(JobSource::Custom("cache_test".into()));
+    cfg.delay_ms = 0;
+    cfg.max_retries = 0;
+    cfg.cache_max_age_secs = max_age_secs;
+    SourceClient::new(&cfg)
+}
+
+async fn get(client: &SourceClient, url: &str) -> String {
+    let res = client.get(url).send().await.expect("petición");
+    assert_eq!(res.status().as_u16(), 200, "{url}");
+    res.into_text()
+}
+
+/// Dentro de `cache_max_age_secs` la respuesta se sirve del disco sin red.
+#[tokio::test]
+async fn fresh_entries_skip_the_network() {
+    setup();
+    let (base, hits) = server().await;
+    let url = format!("{base}/etag/fresh");
+    let client = client(3600);
+
+    assert_eq!(get(&client, &url).await, "body of /etag/fresh");
+    assert_eq!(get(&client, &url).await, "body of /etag/fresh");
+    assert_eq!(hits.lock().unwrap().len(), 1);
+}
+
+/// Caducada la entrada se revalida con `If-None-Match` y el 304 reutiliza el cuerpo.
+#[tokio::test]
+async fn expired_entries_revalidate_with_etag() {
+    setup();
+    let (base, hits) = server().await;
+    let url = format!("{base}/etag/stale");
+    let client = client(0);
+
+    assert_eq!(get(&client, &url).await, "body of /etag/stale");
+    assert_eq!(get(&client, &url).await, "body of /etag/stale");
+    assert_eq!(
+        *hits.lock().unwrap(),
+        [("/etag/stale".to_string(), false), ("/etag/stale".to_string(), true)]
+    );
+}
+
+#[tokio::test]
+async fn expired_entries_revalidate_with_last_modified() {
+    setup();
+    let (base, hits) = server().await;
+    let url = format!("{base}/lm/stale");
+    let client = client(0);
+
+    assert_eq!(get(&client, &url).await, "body of /lm/stale");
+    assert_eq!(get(&client, &url).await, "body of /lm/stale");
+    assert_eq!(*hits.lock().unwrap(), [("/lm/stale".to_string(), false), ("/lm/stale".to_string(), true)]);
+}
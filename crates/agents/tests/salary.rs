@@ -0,0 +1,62 @@
+//! Salarios deterministas a partir de texto libre y campos nativos.
+
+use job_hunter_agents::salary::{parse_salary_field, parse_salary_text, salary_from_json};
+use job_hunter_core::{SalaryPeriod, SalaryRange};
+
+fn range(text: &str) -> Option<(u32, u32, String, SalaryPeriod)> {
+    parse_salary_text(text).map(|SalaryRange { min, max, currency, period }| (min, max, currency, period))
+}
+
+#[test]
+fn free_text_salaries() {
+    let cases: &[(&str, u32, u32, &str, SalaryPeriod)] = &[
+        ("Compensation: $120k–150k/yr plus equity", 120_000, 150_000, "USD", SalaryPeriod::Yearly),
+        ("Ofrecemos €60.000 brutos y horario flexible", 60_000, 60_000, "EUR", SalaryPeriod::Yearly),
+        ("Rate: 45 USD/h, fully remote", 45, 45, "USD", SalaryPeriod::Hourly),
+        ("Sueldo: 3.000 € al mes", 3_000, 3_000, "EUR", SalaryPeriod::Monthly),
+        ("Salary range $90,000 - $110,000", 90_000, 110_000, "USD", SalaryPeriod::Yearly),
+    ];
+    for (text, min, max, currency, period) in cases {
+        let (got_min, got_max, got_currency, got_period) = range(text).unwrap_or_else(|| panic!("sin salario: {text}"));
+        assert_eq!((got_min, got_max), (*min, *max), "{text}");
+        assert_eq!(got_currency, *currency, "{text}");
+        assert_eq!(std::mem::discriminant(&got_period), std::mem::discriminant(period), "{text}");
+    }
+}
+
+/// Financiación, presupuestos y cifras sin contexto de sueldo no son un salario.
+#[test]
+fn free_text_rejects_non_salary_amounts() {
+    for text in [
+        "We raised $5M in Series A funding",
+        "Series B: $30 million raised",
+        "$1,500 learning budget every year",
+        "Annual stipend of $2,000 for your home office",
+        "Backed by investors with a €2bn valuation",
+        "Order the $300 headphones of your choice",
+        "Founded in 2024 with 5 years of runway",
+    ] {
+        assert!(range(text).is_none(), "{text}: {:?}", range(text));
+    }
+}
+
+/// Una cifra descartada no tapa el salario real de la misma oferta.
+#[test]
+fn free_text_skips_noise_before_salary() {
+    let text = "We raised $5M last year. Salary: $120k - $140k depending on experience.";
+    let (min, max, currency, _) = range(text).expect("salario");
+    assert_eq!((min, max, currency.as_str()), (120_000, 140_000, "USD"));
+}
+
+/// En campos que ya son un salario el periodo se deduce por magnitud.
+#[test]
+fn salary_fields_infer_period_from_magnitude() {
+    let r = parse_salary_field("70000 - 85000", "EUR").expect("salario");
+    assert_eq!((r.min, r.max, r.currency.as_str()), (70_000, 85_000, "EUR"));
+    assert!(matches!(r.period, SalaryPeriod::Yearly));
+
+    let item = serde_json::json!({ "salary_min": 100000, "salary_max": 130000 });
+    let r = salary_from_json(&item).expect("salario");
+    assert_eq!((r.min, r.max, r.currency.as_str()), (100_000, 130_000, "USD"));
+    assert!(matches!(r.period, SalaryPeriod::Yearly));
+}
@@ -0,0 +1,256 @@
+//! Tests de conformidad de los scrapers contra respuestas grabadas.
+//!
+//! Cada fuente se ejecuta en modo `replay` sobre `tests/fixtures/<host>/`: un
+//! JSON por petición (`method`, `url`, `status`, `headers`) y el cuerpo tal cual
+//! en `bodies/` vía `body_file`. Para añadir una fuente basta con dejar sus
+//! fixtures ahí (o grabarlas con `JOB_HUNTER_HTTP_MODE=record` y
+//! `JOB_HUNTER_FIXTURES_DIR=crates/agents/tests/fixtures`) y una línea en
+//! `conformance!`. Si falta una grabación, el error indica la URL exacta.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use job_hunter_agents::http::{HttpTape, TapeMode};
+use job_hunter_agents::SourceRegistry;
+use job_hunter_core::*;
+
+/// Búsqueda neutra: sin keywords, una página y cupo holgado.
+fn criteria(source: JobSource) -> SearchCriteria {
+    let mut cfg = SourceSettings::defaults(source);
+    cfg.delay_ms = 0;
+    cfg.max_pages = 1;
+    cfg.max_results = 50;
+    cfg.max_retries = 0;
+    SearchCriteria {
+        sources_config: vec![cfg],
+        ..Default::default()
+    }
+}
+
+/// Agente de una fuente integrada, tal como lo crea el registro.
+fn agent(source: &JobSource) -> Arc<dyn Agent> {
+    SourceRegistry::builtin().agent(source).expect("fuente registrada")
+}
+
+/// Ejecuta `agent` sobre los fixtures.
+async fn run(agent: &dyn Agent, criteria: SearchCriteria) -> Vec<RawJobPosting> {
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    HttpTape::install(Some(HttpTape::new(TapeMode::Replay, fixtures)));
+
+    match agent.process(AgentMessage::StartScraping(Arc::new(criteria))).await {
+        Ok(AgentMessage::RawJobsScraped(p)) => p,
+        Ok(other) => panic!("[{}] respuesta inesperada: {:?}", agent.name(), other),
+        Err(e) => panic!("[{}] {}", agent.name(), e),
+    }
+}
+
+/// Ejecuta la fuente sobre los fixtures y comprueba los campos obligatorios:
+/// id, url, fuente y contenido siempre; `native` son los datos nativos del
+/// board que la fuente debe rellenar (`title`, `company`).
+async fn check(source: JobSource, expected: usize, native: &[&str]) {
+    let agent = agent(&source);
+    let postings = run(agent.as_ref(), criteria(source.clone())).await;
+
+    assert_eq!(postings.len(), expected, "[{}] número de ofertas", agent.name());
+    let mut ids = std::collections::HashSet::new();
+    for p in &postings {
+        let ctx = format!("[{}] {}", agent.name(), p.url);
+        assert_eq!(p.source, source, "{ctx}: fuente");
+        assert!(!p.id.is_empty() && ids.insert(p.id.clone()), "{ctx}: id vacío o duplicado");
+        assert!(p.url.starts_with("https://"), "{ctx}: url");
+        assert!(!p.html_content.trim().is_empty(), "{ctx}: contenido vacío");
+        for field in native {
+            let value = match *field {
+                "title" => &p.normalized.title,
+                "company" => &p.normalized.company,
+                other => panic!("campo desconocido: {other}"),
+            };
+            assert!(value.as_deref().is_some_and(|v| !v.trim().is_empty()), "{ctx}: sin {field}");
+        }
+    }
+}
+
+/// `nombre_test: fuente, ofertas esperadas, [campos nativos]`
+macro_rules! conformance {
+    ($($test:ident: $source:expr, $expected:expr, [$($field:ident),*];)*) => {
+        $(
+            #[tokio::test]
+            async fn $test() {
+                check($source, $expected, &[$(stringify!($field)),*]).await;
+            }
+        )*
+    };
+}
+
+conformance! {
+    remoteok: JobSource::RemoteOk, 3, [title, company];
+    weworkremotely: JobSource::WeWorkRemotely, 2, [title, company];
+    arbeitnow: JobSource::Arbeitnow, 2, [title, company];
+    himalayas: JobSource::Himalayas, 2, [title, company];
+    jobspresso: JobSource::Jobspresso, 2, [];
+    remotive: JobSource::Remotive, 2, [title, company];
+    jobicy: JobSource::Jobicy, 2, [title, company];
+    findwork: JobSource::FindWork, 2, [title, company];
+    workingnomads: JobSource::WorkingNomads, 2, [title, company];
+    vuejobs: JobSource::VueJobs, 2, [title, company];
+    cryptojobs: JobSource::CryptoJobs, 2, [title, company];
+    devitjobs: JobSource::DevItJobs, 2, [title, company];
+    golangprojects: JobSource::GolangProjects, 2, [title, company];
+    pythonorg: JobSource::PythonOrg, 2, [title];
+    remoteco: JobSource::RemoteCo, 2, [title];
+}
+
+/// Los boards sin filtro de nivel en la consulta descartan en local las
+/// ofertas de otro nivel: por título (Remotive) o por `jobLevel` (Jobicy).
+#[tokio::test]
+async fn experience_level_filters_locally() {
+    for (source, level, titles) in [
+        (JobSource::Remotive, ExperienceLevel::Junior, vec!["Growth Marketer"]),
+        (JobSource::Remotive, ExperienceLevel::Senior, vec!["Senior Elixir Engineer", "Growth Marketer"]),
+        (JobSource::Jobicy, ExperienceLevel::Mid, vec!["UX Researcher"]),
+    ] {
+        let criteria = SearchCriteria { experience_level: level, ..criteria(source.clone()) };
+        let postings = run(agent(&source).as_ref(), criteria).await;
+        let got: Vec<&str> = postings.iter().filter_map(|p| p.normalized.title.as_deref()).collect();
+        assert_eq!(got, titles, "{source:?}");
+    }
+}
+
+/// Con más keywords de las que se consultan al board, las que sobran se
+/// buscan en el listado general: «elixir» solo aparece ahí.
+#[tokio::test]
+async fn overflow_keywords_search_the_general_listing() {
+    let keywords = ["golang", "java", "python", "elixir"].map(String::from).to_vec();
+    let criteria = SearchCriteria { keywords, ..criteria(JobSource::Remotive) };
+    let postings = run(agent(&JobSource::Remotive).as_ref(), criteria).await;
+    let got: Vec<&str> = postings.iter().filter_map(|p| p.normalized.title.as_deref()).collect();
+    assert_eq!(got, ["Senior Elixir Engineer"]);
+}
+
+/// Con `fetch_details` los teasers del feed se sustituyen por el cuerpo de la
+/// página de cada oferta (robots.txt y detalles en `weworkremotely.com/`).
+#[tokio::test]
+async fn feed_details_replace_short_teasers() {
+    let mut criteria = criteria(JobSource::WeWorkRemotely);
+    criteria.sources_config[0].fetch_details = true;
+    let postings = run(agent(&JobSource::WeWorkRemotely).as_ref(), criteria).await;
+
+    assert_eq!(postings.len(), 2);
+    for p in &postings {
+        assert!(
+            p.html_content.starts_with("<div class=\"lis-container__job__content__description\">"),
+            "{}: {}",
+            p.url,
+            p.html_content
+        );
+        assert!(p.html_content.contains("Requirements:"), "{}", p.url);
+    }
+}
+
+/// Las APIs JSON rellenan los datos normalizados (descripción en texto plano
+/// y tags incluidos) y los feeds usan sus categorías como tags.
+#[tokio::test]
+async fn sources_fill_normalized_fields() {
+    let postings = run(agent(&JobSource::RemoteOk).as_ref(), criteria(JobSource::RemoteOk)).await;
+    let rust = postings
+        .iter()
+        .find(|p| p.normalized.title.as_deref() == Some("Senior Rust Engineer"))
+        .expect("oferta de Acme");
+    let n = &rust.normalized;
+    assert!(n.is_complete());
+    assert_eq!(n.company.as_deref(), Some("Acme Systems"));
+    assert_eq!(n.description.as_deref(), Some("Build low-latency services in Rust."));
+    assert_eq!(n.tags, ["rust", "backend", "senior"]);
+    assert_eq!(n.salary.as_ref().map(|s| (s.min, s.max)), Some((120_000, 160_000)));
+    assert!(n.posted_at.is_some());
+
+    let postings = run(agent(&JobSource::WeWorkRemotely).as_ref(), criteria(JobSource::WeWorkRemotely)).await;
+    for p in &postings {
+        assert!(p.normalized.is_complete(), "{}", p.url);
+        assert!(p.normalized.description.as_deref().is_some_and(|d| !d.contains('<')), "{}", p.url);
+        assert!(p.normalized.tags.iter().all(|t| JobType::from_label(t).is_none()), "{}", p.url);
+    }
+}
+
+/// El registro tiene un adaptador por fuente integrada, rechaza duplicados y
+/// solo devuelve como activas las fuentes habilitadas.
+#[test]
+fn registry_keys_adapters_by_source() {
+    let mut registry = SourceRegistry::builtin();
+    assert_eq!(registry.sources().count(), 19);
+
+    let remoteok = registry.get(&JobSource::RemoteOk).cloned().expect("RemoteOK");
+    assert!(!registry.register(remoteok));
+
+    let mut criteria = criteria(JobSource::Arbeitnow);
+    criteria.sources_config[0].enabled = false;
+    let enabled = registry.enabled(&criteria);
+    assert!(!enabled.contains(&JobSource::Arbeitnow));
+    assert!(enabled.contains(&JobSource::RemoteOk));
+    // Los boards ATS sin watchlist no se activan
+    assert!(!enabled.contains(&JobSource::Greenhouse));
+    assert_eq!(agent(&JobSource::Himalayas).name(), "scraper_himalayas");
+}
+
+/// Los boards ATS recorren la watchlist de `companies` y dejan la empresa
+/// rellena: la de la watchlist (`slug:Nombre`), si no la del board, si no el
+/// slug legible.
+#[tokio::test]
+async fn ats_boards_prefill_company() {
+    let boards = [
+        (JobSource::Greenhouse, "acme", "Acme Systems"),
+        (JobSource::Lever, "globex:Globex Corp", "Globex Corp"),
+        (JobSource::Ashby, "initech", "Initech"),
+        (JobSource::Workable, "umbrella", "Umbrella Health"),
+    ];
+    for (source, entry, company) in boards {
+        let mut criteria = criteria(source.clone());
+        criteria.sources_config[0].companies = vec![entry.to_string()];
+        let postings = run(agent(&source).as_ref(), criteria).await;
+
+        assert_eq!(postings.len(), 2, "{}", source.slug());
+        for p in &postings {
+            let n = &p.normalized;
+            assert_eq!(p.source, source);
+            assert!(n.is_complete(), "{}", p.url);
+            assert_eq!(n.company.as_deref(), Some(company), "{}", p.url);
+            assert!(n.description.as_deref().is_some_and(|d| !d.contains('<')), "{}", p.url);
+            assert!(n.posted_at.is_some(), "{}", p.url);
+        }
+    }
+
+    // Keywords filtradas en local y salario estructurado de Lever
+    let mut criteria = criteria(JobSource::Lever);
+    criteria.keywords = vec!["go".into()];
+    criteria.sources_config[0].companies = vec!["globex".into()];
+    let postings = run(agent(&JobSource::Lever).as_ref(), criteria).await;
+    assert_eq!(postings.len(), 1);
+    let n = &postings[0].normalized;
+    assert_eq!(n.company.as_deref(), Some("Globex"));
+    assert_eq!(n.salary.as_ref().map(|s| (s.min, s.max)), Some((140_000, 170_000)));
+    assert_eq!(n.is_remote, Some(true));
+}
+
+/// Un 404 en el board de una empresa solo se avisa; la fuente falla únicamente
+/// si ningún board responde por errores de red o de parseo.
+#[tokio::test]
+async fn ats_missing_boards_are_skipped() {
+    let agent = agent(&JobSource::Greenhouse);
+    let scrape = |companies: &[&str]| {
+        let mut criteria = criteria(JobSource::Greenhouse);
+        criteria.sources_config[0].companies = companies.iter().map(|c| c.to_string()).collect();
+        agent.process(AgentMessage::StartScraping(Arc::new(criteria)))
+    };
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    HttpTape::install(Some(HttpTape::new(TapeMode::Replay, fixtures)));
+
+    let jobs = |msg| match msg {
+        Ok(AgentMessage::RawJobsScraped(p)) => p.len(),
+        other => panic!("respuesta inesperada: {other:?}"),
+    };
+    assert_eq!(jobs(scrape(&["missing"]).await), 0);
+    assert_eq!(jobs(scrape(&["missing", "acme"]).await), 2);
+    assert_eq!(jobs(scrape(&["broken", "acme"]).await), 2);
+    assert!(scrape(&["broken"]).await.is_err());
+    assert!(scrape(&["missing", "broken"]).await.is_err());
+}
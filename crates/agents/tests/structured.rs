@@ -0,0 +1,128 @@
+//! Extracción de schema.org `JobPosting` (JSON-LD y microdata).
+
+use job_hunter_agents::structured::{extract_job_posting, with_structured_data};
+use job_hunter_core::*;
+
+const JSON_LD_PAGE: &str = r#"<!DOCTYPE html>
+<html><head>
+<script type="application/ld+json">{"@context": "https://schema.org", "@type": "Organization", "name": "Jobs Board"}</script>
+<script type="application/ld+json">
+{
+  "@context": "https://schema.org",
+  "@graph": [
+    { "@type": "WebPage", "name": "Senior Rust Engineer at Acme" },
+    {
+      "@type": "JobPosting",
+      "title": "Senior Rust Engineer",
+      "hiringOrganization": { "@type": "Organization", "name": "Acme Payments" },
+      "datePosted": "2025-10-01",
+      "validThrough": "2099-12-31T23:59:59Z",
+      "employmentType": ["FULL_TIME", "CONTRACTOR"],
+      "jobLocationType": "TELECOMMUTE",
+      "applicantLocationRequirements": [{ "@type": "Country", "name": "Spain" }, { "@type": "Country", "name": "Portugal" }],
+      "baseSalary": {
+        "@type": "MonetaryAmount",
+        "currency": "EUR",
+        "value": { "@type": "QuantitativeValue", "minValue": 60000, "maxValue": 75000, "unitText": "YEAR" }
+      }
+    }
+  ]
+}
+</script>
+</head>
+<body><h1>Senior Rust Engineer</h1><p>Salary: $10k per month (old banner)</p></body></html>"#;
+
+const MICRODATA_PAGE: &str = r#"<html><body>
+<div itemscope itemtype="https://schema.org/JobPosting">
+  <h1 itemprop="title">Data Engineer</h1>
+  <div itemprop="hiringOrganization" itemscope itemtype="https://schema.org/Organization">
+    <span itemprop="name">Globex</span>
+  </div>
+  <time itemprop="datePosted" datetime="2025-09-15">15 Sep</time>
+  <meta itemprop="employmentType" content="PART_TIME">
+  <div itemprop="jobLocation" itemscope itemtype="https://schema.org/Place">
+    <div itemprop="address" itemscope itemtype="https://schema.org/PostalAddress">
+      <span itemprop="addressLocality">Berlin</span>, <span itemprop="addressCountry">DE</span>
+    </div>
+  </div>
+  <div itemprop="baseSalary" itemscope itemtype="https://schema.org/MonetaryAmount">
+    <meta itemprop="currency" content="EUR">
+    <div itemprop="value" itemscope itemtype="https://schema.org/QuantitativeValue">
+      <meta itemprop="value" content="45">
+      <meta itemprop="unitText" content="HOUR">
+    </div>
+  </div>
+</div>
+</body></html>"#;
+
+#[test]
+fn json_ld_job_posting_in_graph() {
+    let job = extract_job_posting(JSON_LD_PAGE).expect("JobPosting");
+
+    assert_eq!(job.title.as_deref(), Some("Senior Rust Engineer"));
+    assert_eq!(job.company.as_deref(), Some("Acme Payments"));
+    assert_eq!(job.date_posted.unwrap().to_rfc3339(), "2025-10-01T00:00:00+00:00");
+    assert_eq!(job.valid_through.unwrap().to_rfc3339(), "2099-12-31T23:59:59+00:00");
+    assert!(matches!(job.job_type, Some(JobType::FullTime)));
+    assert_eq!(job.is_remote, Some(true));
+    assert_eq!(job.location.as_deref(), Some("Remote (Spain, Portugal)"));
+    let salary = job.salary.unwrap();
+    assert_eq!((salary.min, salary.max, salary.currency.as_str()), (60_000, 75_000, "EUR"));
+    assert!(matches!(salary.period, SalaryPeriod::Yearly));
+}
+
+#[test]
+fn microdata_job_posting() {
+    let job = extract_job_posting(MICRODATA_PAGE).expect("JobPosting");
+
+    assert_eq!(job.title.as_deref(), Some("Data Engineer"));
+    assert_eq!(job.company.as_deref(), Some("Globex"));
+    assert_eq!(job.date_posted.unwrap().to_rfc3339(), "2025-09-15T00:00:00+00:00");
+    assert!(matches!(job.job_type, Some(JobType::PartTime)));
+    assert_eq!(job.location.as_deref(), Some("Berlin, DE"));
+    let salary = job.salary.unwrap();
+    assert_eq!((salary.min, salary.max, salary.currency.as_str()), (45, 45, "EUR"));
+    assert!(matches!(salary.period, SalaryPeriod::Hourly));
+}
+
+#[test]
+fn structured_data_wins_for_salary_dates_and_company() {
+    let heuristic = SalaryRange { min: 10_000, max: 10_000, currency: "USD".into(), period: SalaryPeriod::Monthly };
+    let posting = RawJobPosting::new(JobSource::Jobspresso, None, "https://jobspresso.co/job/rust/", JSON_LD_PAGE)
+        .with_details(Some("Rust Engineer (Remote)".into()), Some("acme".into()), None)
+        .with_salary(Some(heuristic));
+    let posting = with_structured_data(posting, JSON_LD_PAGE);
+
+    // Título nativo se conserva; empresa, salario y fechas vienen del JSON-LD
+    assert_eq!(posting.normalized.title.as_deref(), Some("Rust Engineer (Remote)"));
+    assert_eq!(posting.normalized.company.as_deref(), Some("Acme Payments"));
+    assert_eq!(posting.normalized.salary.as_ref().map(|s| s.min), Some(60_000));
+    assert!(posting.normalized.posted_at.is_some() && posting.normalized.valid_through.is_some());
+    assert!(!posting.is_expired());
+}
+
+#[test]
+fn pages_without_job_posting_are_untouched() {
+    assert!(extract_job_posting("<html><body><h1>About us</h1></body></html>").is_none());
+    assert!(extract_job_posting(r#"{"title": "JobPosting fan club"}"#).is_none());
+}
+
+/// Una moneda desconocida descarta el salario: su texto acabaría en la UI.
+#[test]
+fn unknown_salary_currency_is_dropped() {
+    let page = |currency: &str| {
+        format!(
+            r#"<script type="application/ld+json">{{"@type": "JobPosting", "title": "Dev",
+              "baseSalary": {{"currency": "{currency}", "value": {{"minValue": 50000, "maxValue": 60000}}}}}}</script>"#
+        )
+    };
+    let job = extract_job_posting(&page("<img src=x onerror=alert(1)>")).expect("JobPosting");
+    assert_eq!(job.title.as_deref(), Some("Dev"));
+    assert!(job.salary.is_none());
+    assert!(extract_job_posting(&page("DOGE")).unwrap().salary.is_none());
+
+    // Símbolos y minúsculas conocidos se normalizan al código ISO
+    let salary = extract_job_posting(&page("€")).unwrap().salary.expect("salario");
+    assert_eq!((salary.min, salary.max, salary.currency.as_str()), (50_000, 60_000, "EUR"));
+    assert_eq!(extract_job_posting(&page("gbp")).unwrap().salary.unwrap().currency, "GBP");
+}
@@ -0,0 +1,127 @@
+use url::Url;
+use uuid::Uuid;
+
+use crate::JobSource;
+
+/// Namespace fijo para los UUID v5 de ofertas. No cambiar: invalidaría
+/// todas las identidades ya persistidas.
+const JOB_ID_NAMESPACE: Uuid = Uuid::from_u128(0x6a0b_5c3e_1f2d_4e8a_9b7c_0d1e_2f3a_4b5c);
+
+/// Parámetros de tracking que no forman parte de la identidad de una oferta.
+/// `source`, `src` o `ref` no entran: algunos boards los usan para identificar
+/// la oferta y quitarlos fusionaría ofertas distintas.
+const TRACKING_PARAMS: &[&str] = &["referrer", "gclid", "fbclid", "mc_cid", "mc_eid", "trk"];
+
+/// Normaliza una URL para que la misma oferta produzca siempre la misma cadena:
+/// host en minúsculas sin `www.`, sin fragmento, sin parámetros de tracking
+/// (`utm_*`, `gclid`, ...), query ordenada y sin `/` final.
+///
+/// Si la URL no es parseable se devuelve recortada tal cual.
+pub fn canonical_url(raw: &str) -> String {
+    let Ok(mut url) = Url::parse(raw.trim()) else {
+        return raw.trim().to_string();
+    };
+
+    url.set_fragment(None);
+    if let Some(host) = url.host_str().map(|h| h.to_lowercase()) {
+        let host = host.strip_prefix("www.").unwrap_or(&host).to_string();
+        let _ = url.set_host(Some(&host));
+    }
+    if url.scheme() == "http" {
+        let _ = url.set_scheme("https");
+    }
+
+    let mut params: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(k, _)| {
+            let k = k.to_lowercase();
+            !k.starts_with("utm_") && !TRACKING_PARAMS.contains(&k.as_str())
+        })
+        .map(|(k, v)| (k.into_owned(), v.into_owned()))
+        .collect();
+    params.sort();
+
+    if params.is_empty() {
+        url.set_query(None);
+    } else {
+        url.query_pairs_mut().clear().extend_pairs(params);
+    }
+
+    let path = url.path().trim_end_matches('/').to_string();
+    url.set_path(if path.is_empty() { "/" } else { &path });
+
+    url.to_string().trim_end_matches('/').to_string()
+}
+
+/// Id estable de una oferta: UUID v5 sobre la fuente y la clave nativa del
+/// board (si la API la expone) o, en su defecto, la URL canónica.
+pub fn job_id(source: &JobSource, native_id: Option<&str>, url: &str) -> String {
+    let key = match native_id.map(str::trim).filter(|s| !s.is_empty()) {
+        Some(id) => format!("{}|id:{}", source.slug(), id),
+        None => format!("{}|url:{}", source.slug(), canonical_url(url)),
+    };
+    Uuid::new_v5(&JOB_ID_NAMESPACE, key.as_bytes()).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drops_www_fragment_and_forces_https() {
+        assert_eq!(canonical_url("http://WWW.Example.com/jobs/42#apply"), "https://example.com/jobs/42");
+        assert_eq!(canonical_url("https://www.example.com/jobs/42"), canonical_url("http://example.com/jobs/42"));
+    }
+
+    #[test]
+    fn strips_tracking_params_and_sorts_query() {
+        assert_eq!(
+            canonical_url("https://example.com/job?utm_source=rss&b=2&UTM_Medium=x&gclid=1&fbclid=2&trk=3&referrer=a&a=1"),
+            "https://example.com/job?a=1&b=2"
+        );
+        assert_eq!(canonical_url("https://example.com/job?utm_campaign=x"), "https://example.com/job");
+        // Parámetros que algunos boards usan como identidad se conservan
+        assert_eq!(
+            canonical_url("https://example.com/job?src=board&ref=77&source=api"),
+            "https://example.com/job?ref=77&source=api&src=board"
+        );
+    }
+
+    #[test]
+    fn trims_trailing_slash() {
+        assert_eq!(canonical_url("https://example.com/jobs/42/"), "https://example.com/jobs/42");
+        assert_eq!(canonical_url("https://example.com/"), "https://example.com");
+        assert_eq!(canonical_url("https://example.com/jobs/?a=1"), "https://example.com/jobs?a=1");
+    }
+
+    #[test]
+    fn unparsable_input_is_trimmed_as_is() {
+        assert_eq!(canonical_url("  /jobs/42#x "), "/jobs/42#x");
+        assert_eq!(canonical_url(""), "");
+    }
+
+    #[test]
+    fn native_id_wins_over_url() {
+        let a = job_id(&JobSource::Remotive, Some("42"), "https://remotive.com/jobs/42?utm_source=x");
+        let b = job_id(&JobSource::Remotive, Some(" 42 "), "https://remotive.com/other-slug");
+        assert_eq!(a, b);
+        // Un id vacío cae a la URL canónica
+        assert_eq!(
+            job_id(&JobSource::Remotive, Some(" "), "http://www.remotive.com/jobs/42/"),
+            job_id(&JobSource::Remotive, None, "https://remotive.com/jobs/42")
+        );
+        assert_ne!(a, job_id(&JobSource::Remotive, None, "https://remotive.com/jobs/42"));
+    }
+
+    #[test]
+    fn ids_are_stable_and_scoped_by_source() {
+        let id = job_id(&JobSource::Remotive, Some("42"), "https://remotive.com/jobs/42");
+        assert_eq!(id, job_id(&JobSource::Remotive, Some("42"), "https://remotive.com/jobs/42"));
+        assert!(Uuid::parse_str(&id).is_ok_and(|u| u.get_version_num() == 5));
+        assert_ne!(id, job_id(&JobSource::RemoteOk, Some("42"), "https://remotive.com/jobs/42"));
+        assert_ne!(
+            job_id(&JobSource::Custom("a".into()), None, "https://example.com/jobs/1"),
+            job_id(&JobSource::Custom("b".into()), None, "https://example.com/jobs/1")
+        );
+    }
+}
@@ -3,6 +3,13 @@ use rkyv::{Archive, Deserialize, Serialize};
 use serde::{Deserialize as SerdeDeserialize, Serialize as SerdeSerialize};
 use std::sync::Arc;
 
+mod identity;
+mod salary;
+mod text;
+pub use identity::{canonical_url, job_id};
+pub use salary::convert_currency;
+pub use text::contains_term;
+
 #[derive(
     Debug, Clone, SerdeSerialize, SerdeDeserialize, Archive, Serialize, Deserialize, PartialEq, Eq,
 )]
@@ -10,9 +17,69 @@ use std::sync::Arc;
 pub struct SourceSettings {
     pub source: JobSource,
     pub enabled: bool,
+    /// Intervalo mínimo entre peticiones al host de la fuente (cuota del
+    /// rate limiter por host, compartido entre ejecuciones).
     pub delay_ms: u64,
     pub user_agent: String,
     pub use_proxy: bool,
+    /// Máximo de ofertas a extraer de la fuente en cada búsqueda.
+    pub max_results: u32,
+    /// Máximo de páginas a recorrer por consulta si la fuente pagina.
+    pub max_pages: u32,
+    /// Desactiva la comprobación de robots.txt en los scrapers HTML (queda en el log).
+    #[serde(default)]
+    pub ignore_robots: bool,
+    /// Reintentos de una petición ante 429/5xx o errores de red transitorios.
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    /// Espera base del backoff exponencial entre reintentos.
+    #[serde(default = "default_retry_backoff_ms")]
+    pub retry_backoff_ms: u64,
+    /// Segundos durante los que una respuesta cacheada se usa sin revalidar
+    /// (0 = revalidar siempre con ETag/Last-Modified).
+    #[serde(default = "default_cache_max_age_secs")]
+    pub cache_max_age_secs: u64,
+    /// Descarga la página de detalle de las ofertas cuyo contenido es solo un
+    /// teaser (feeds RSS/Atom), para que el análisis no puntúe a ciegas.
+    #[serde(default)]
+    pub fetch_details: bool,
+    /// Watchlist de empresas para las fuentes ATS (Greenhouse, Lever, Ashby,
+    /// Workable): slug del board, opcionalmente `slug:Nombre visible`.
+    #[serde(default)]
+    pub companies: Vec<String>,
+}
+
+fn default_max_retries() -> u32 {
+    2
+}
+
+fn default_retry_backoff_ms() -> u64 {
+    500
+}
+
+fn default_cache_max_age_secs() -> u64 {
+    900
+}
+
+impl SourceSettings {
+    /// Configuración de una fuente que no viene en la petición.
+    pub fn defaults(source: JobSource) -> Self {
+        Self {
+            source,
+            enabled: true,
+            delay_ms: 1200,
+            user_agent: "Mozilla/5.0".to_string(),
+            use_proxy: false,
+            max_results: 15,
+            max_pages: 2,
+            ignore_robots: false,
+            max_retries: default_max_retries(),
+            retry_backoff_ms: default_retry_backoff_ms(),
+            cache_max_age_secs: default_cache_max_age_secs(),
+            fetch_details: false,
+            companies: Vec::new(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, SerdeSerialize, SerdeDeserialize, Archive, Serialize, Deserialize)]
@@ -22,6 +89,61 @@ pub struct SearchCriteria {
     pub experience_level: ExperienceLevel,
     pub sources_config: Vec<SourceSettings>,
     pub user_cv: Option<String>,
+    /// Divisa (ISO 4217) en la que se normalizan los salarios anuales.
+    pub target_currency: String,
+    /// Antigüedad máxima (días) de una oferta; las más antiguas se descartan
+    /// antes del análisis. `None` = sin límite.
+    pub max_age_days: Option<u32>,
+    #[serde(default)]
+    pub filters: SearchFilters,
+}
+
+/// Búsqueda sin keywords ni fuentes configuradas (todas con su configuración
+/// por defecto), cualquier nivel, salarios en USD y sin filtros.
+impl Default for SearchCriteria {
+    fn default() -> Self {
+        Self {
+            keywords: Vec::new(),
+            experience_level: ExperienceLevel::Any,
+            sources_config: Vec::new(),
+            user_cv: None,
+            target_currency: "USD".to_string(),
+            max_age_days: None,
+            filters: SearchFilters::default(),
+        }
+    }
+}
+
+impl SearchCriteria {
+    /// Configuración enviada para `source` o, si no viene, la de por defecto.
+    pub fn source_settings(&self, source: &JobSource) -> SourceSettings {
+        self.sources_config
+            .iter()
+            .find(|s| &s.source == source)
+            .cloned()
+            .unwrap_or_else(|| SourceSettings::defaults(source.clone()))
+    }
+}
+
+/// Filtros deterministas que se evalúan tras el scraping y antes del análisis
+/// LLM. Una lista vacía (o `None`) desactiva el filtro correspondiente.
+#[derive(
+    Debug, Clone, Default, SerdeSerialize, SerdeDeserialize, Archive, Serialize, Deserialize,
+)]
+#[archive(check_bytes)]
+#[serde(default)]
+pub struct SearchFilters {
+    /// Se descarta la oferta si contiene cualquiera de estos términos.
+    pub exclude_keywords: Vec<String>,
+    /// La oferta debe contener todos estos términos.
+    pub required_keywords: Vec<String>,
+    /// Salario anual mínimo en `SearchCriteria::target_currency`.
+    pub min_salary: Option<u32>,
+    pub job_types: Vec<JobType>,
+    pub remote_only: bool,
+    /// Países o regiones aceptados ("Spain", "EU", "Europe", ...).
+    pub locations: Vec<String>,
+    pub company_blocklist: Vec<String>,
 }
 
 #[derive(
@@ -49,8 +171,29 @@ pub enum JobType {
     Internship,
 }
 
+impl JobType {
+    /// Interpreta las etiquetas habituales de boards y LLM ("full-time",
+    /// "Full Time", "contractor", "intern", ...).
+    pub fn from_label(label: &str) -> Option<Self> {
+        let v: String = label
+            .trim()
+            .to_lowercase()
+            .chars()
+            .filter(|c| c.is_alphanumeric())
+            .collect();
+        match v.as_str() {
+            "fulltime" | "permanent" | "employee" => Some(JobType::FullTime),
+            "parttime" => Some(JobType::PartTime),
+            "contract" | "contractor" | "temporary" => Some(JobType::Contract),
+            "freelance" | "freelancer" => Some(JobType::Freelance),
+            "internship" | "intern" | "werkstudent" => Some(JobType::Internship),
+            _ => None,
+        }
+    }
+}
+
 #[derive(
-    Debug, Clone, SerdeSerialize, SerdeDeserialize, Archive, Serialize, Deserialize, PartialEq, Eq,
+    Debug, Clone, SerdeSerialize, SerdeDeserialize, Archive, Serialize, Deserialize, PartialEq, Eq, Hash,
 )]
 #[archive(check_bytes)]
 pub enum JobSource {
@@ -71,10 +214,73 @@ pub enum JobSource {
     DevItJobs,
     PythonOrg,
     GolangProjects,
+    // Boards ATS de una watchlist de empresas
+    Greenhouse,
+    Lever,
+    Ashby,
+    Workable,
     // Fallback
     Custom(String),
 }
 
+impl JobSource {
+    /// Identificador corto y estable de la fuente (usado en ids y logs).
+    pub fn slug(&self) -> &str {
+        match self {
+            JobSource::RemoteOk => "remoteok",
+            JobSource::WeWorkRemotely => "wwr",
+            JobSource::Arbeitnow => "arbeitnow",
+            JobSource::Himalayas => "himalayas",
+            JobSource::Jobspresso => "jobspresso",
+            JobSource::Remotive => "remotive",
+            JobSource::Jobicy => "jobicy",
+            JobSource::FindWork => "find_work",
+            JobSource::WorkingNomads => "working_nomads",
+            JobSource::VueJobs => "vue_jobs",
+            JobSource::CryptoJobs => "crypto_jobs",
+            JobSource::RemoteCo => "remote_co",
+            JobSource::DevItJobs => "dev_it_jobs",
+            JobSource::PythonOrg => "python_org",
+            JobSource::GolangProjects => "golang_projects",
+            JobSource::Greenhouse => "greenhouse",
+            JobSource::Lever => "lever",
+            JobSource::Ashby => "ashby",
+            JobSource::Workable => "workable",
+            JobSource::Custom(name) => name,
+        }
+    }
+}
+
+/// Datos factuales de una oferta tal como los publica el board, rellenados
+/// por el mapeo de campos de cada fuente (API JSON, feed, schema.org). Son
+/// exactos: el analizador no los vuelve a extraer, solo puntúa la oferta.
+#[derive(Debug, Clone, Default, SerdeSerialize, SerdeDeserialize, Archive, Serialize, Deserialize)]
+#[archive(check_bytes)]
+pub struct NormalizedPosting {
+    pub title: Option<String>,
+    pub company: Option<String>,
+    pub apply_url: Option<String>,
+    /// Descripción en texto plano (sin HTML).
+    pub description: Option<String>,
+    /// Tags, categorías o skills que el board asocia a la oferta.
+    pub tags: Vec<String>,
+    pub salary: Option<SalaryRange>,
+    pub posted_at: Option<DateTime<Utc>>,
+    pub location: Option<String>,
+    pub is_remote: Option<bool>,
+    pub job_type: Option<JobType>,
+    /// Fecha hasta la que se aceptan candidaturas (schema.org `validThrough`).
+    pub valid_through: Option<DateTime<Utc>>,
+}
+
+impl NormalizedPosting {
+    /// `true` si la fuente aportó título y empresa: basta con que el LLM
+    /// puntúe la oferta, sin extraer datos del texto.
+    pub fn is_complete(&self) -> bool {
+        self.title.is_some() && self.company.is_some()
+    }
+}
+
 #[derive(Debug, Clone, SerdeSerialize, SerdeDeserialize, Archive, Serialize, Deserialize)]
 #[archive(check_bytes)]
 pub struct RawJobPosting {
@@ -83,6 +289,126 @@ pub struct RawJobPosting {
     pub url: String,
     pub html_content: String,
     pub scraped_at: DateTime<Utc>,
+    /// Datos nativos del board (si la fuente los expone) para dedup, filtros y análisis.
+    pub normalized: NormalizedPosting,
+}
+
+impl RawJobPosting {
+    /// Crea una oferta con id determinista (ver [`job_id`]): `native_id` es el id
+    /// propio del board cuando la API lo expone; si no, se usa la URL canónica.
+    pub fn new(
+        source: JobSource,
+        native_id: Option<&str>,
+        url: impl Into<String>,
+        html_content: impl Into<String>,
+    ) -> Self {
+        let url = url.into();
+        Self {
+            id: job_id(&source, native_id, &url),
+            source,
+            url,
+            html_content: html_content.into(),
+            scraped_at: Utc::now(),
+            normalized: NormalizedPosting::default(),
+        }
+    }
+
+    /// Añade título, empresa y URL de aplicación nativos (cadenas vacías se ignoran).
+    pub fn with_details(
+        mut self,
+        title: Option<String>,
+        company: Option<String>,
+        apply_url: Option<String>,
+    ) -> Self {
+        let non_empty = |v: Option<String>| v.map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+        self.normalized.title = non_empty(title);
+        self.normalized.company = non_empty(company);
+        self.normalized.apply_url = non_empty(apply_url);
+        self
+    }
+
+    /// Descripción en texto plano y tags del board (tags vacíos o repetidos se descartan).
+    pub fn with_description(mut self, description: Option<String>, tags: Vec<String>) -> Self {
+        self.normalized.description = description.map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+        let mut seen = std::collections::HashSet::new();
+        self.normalized.tags = tags
+            .into_iter()
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty() && seen.insert(t.to_lowercase()))
+            .collect();
+        self
+    }
+
+    pub fn with_salary(mut self, salary: Option<SalaryRange>) -> Self {
+        self.normalized.salary = salary;
+        self
+    }
+
+    pub fn with_posted_at(mut self, posted_at: Option<DateTime<Utc>>) -> Self {
+        self.normalized.posted_at = posted_at;
+        self
+    }
+
+    pub fn with_location(mut self, location: Option<String>, is_remote: Option<bool>) -> Self {
+        self.normalized.location = location.map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+        self.normalized.is_remote = is_remote;
+        self
+    }
+
+    pub fn with_job_type(mut self, job_type: Option<JobType>) -> Self {
+        self.normalized.job_type = job_type;
+        self
+    }
+
+    pub fn with_valid_through(mut self, valid_through: Option<DateTime<Utc>>) -> Self {
+        self.normalized.valid_through = valid_through;
+        self
+    }
+
+    /// `true` si el board indica que la oferta ya no acepta candidaturas.
+    pub fn is_expired(&self) -> bool {
+        self.normalized.valid_through.is_some_and(|d| d < Utc::now())
+    }
+
+    /// `true` si la fecha de publicación conocida supera `max_age_days`.
+    /// Las ofertas sin fecha nunca se consideran caducadas.
+    pub fn is_older_than(&self, max_age_days: u32) -> bool {
+        self.normalized
+            .posted_at
+            .is_some_and(|d| Utc::now() - d > chrono::Duration::days(max_age_days as i64))
+    }
+
+    /// Texto contra el que se buscan keywords: título, empresa, descripción y
+    /// tags. El contenido bruto solo entra si no hay descripción, porque en
+    /// las fuentes JSON es el item serializado y sus claves ("salary",
+    /// "remote", "company"...) coincidirían con cualquier keyword.
+    pub fn searchable_text(&self) -> String {
+        let n = &self.normalized;
+        let body = n.description.as_deref().unwrap_or(&self.html_content);
+        let mut text = String::new();
+        for part in [n.title.as_deref(), n.company.as_deref(), Some(body)].into_iter().flatten().chain(n.tags.iter().map(String::as_str)) {
+            text.push_str(part);
+            text.push('\n');
+        }
+        text
+    }
+
+    pub fn sighting(&self) -> JobSighting {
+        JobSighting {
+            source: self.source.clone(),
+            url: self.url.clone(),
+        }
+    }
+}
+
+/// Una aparición de la oferta en una fuente concreta.
+#[derive(
+    Debug, Clone, SerdeSerialize, SerdeDeserialize, Archive, Serialize, Deserialize, PartialEq, Eq,
+)]
+#[archive(check_bytes)]
+pub struct JobSighting {
+    pub source: JobSource,
+    pub url: String,
 }
 
 #[derive(Debug, Clone, SerdeSerialize, SerdeDeserialize, Archive, Serialize, Deserialize)]
@@ -105,26 +431,24 @@ pub struct AnalyzedJobPosting {
     pub experience_level: ExperienceLevel,
     pub url: String,
     pub posted_date: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub valid_through: Option<DateTime<Utc>>,
     pub match_score: f32,
     pub match_reasons: Vec<String>,
+    /// Todas las fuentes/URLs en las que se encontró la oferta (tras dedup).
+    #[serde(default)]
+    pub sources: Vec<JobSighting>,
 }
 
-#[derive(Debug, Clone, SerdeSerialize, SerdeDeserialize, Archive, Serialize, Deserialize)]
+#[derive(
+    Debug, Clone, Default, SerdeSerialize, SerdeDeserialize, Archive, Serialize, Deserialize,
+)]
 #[archive(check_bytes)]
 pub struct SkillsGap {
     pub matching: Vec<String>,
     pub missing: Vec<String>,
 }
 
-impl Default for SkillsGap {
-    fn default() -> Self {
-        Self {
-            matching: vec![],
-            missing: vec![],
-        }
-    }
-}
-
 #[derive(Debug, Clone, SerdeSerialize, SerdeDeserialize, Archive, Serialize, Deserialize)]
 #[archive(check_bytes)]
 pub struct CompanyInfo {
@@ -171,6 +495,15 @@ pub enum AgentMessage {
 pub trait Agent: Send + Sync {
     async fn process(&self, msg: AgentMessage) -> Result<AgentMessage, AgentError>;
     fn name(&self) -> &str;
+    /// Fuente que cubre el agente (solo scrapers): salud y circuit breaker.
+    fn source(&self) -> Option<JobSource> {
+        None
+    }
+    /// Configuración efectiva de su fuente para `criteria` (solo scrapers):
+    /// el orquestador solo activa las fuentes habilitadas.
+    fn source_settings(&self, criteria: &SearchCriteria) -> Option<SourceSettings> {
+        self.source().map(|s| criteria.source_settings(&s))
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
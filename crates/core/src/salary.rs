@@ -0,0 +1,91 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use crate::{SalaryPeriod, SalaryRange};
+
+/// Fichero opcional con tipos de cambio (`EUR=1.08`, 1 unidad = N USD).
+/// Sobrescribe/añade entradas a la tabla embebida sin recompilar.
+const KEY_FX_FILE: &str = "JOB_HUNTER_FX_RATES";
+
+/// Tabla offline por defecto: valor en USD de una unidad de cada divisa.
+const DEFAULT_USD_RATES: &[(&str, f64)] = &[
+    ("USD", 1.0),
+    ("EUR", 1.08),
+    ("GBP", 1.27),
+    ("CHF", 1.12),
+    ("CAD", 0.73),
+    ("AUD", 0.66),
+    ("NZD", 0.61),
+    ("JPY", 0.0067),
+    ("INR", 0.012),
+    ("BRL", 0.18),
+    ("MXN", 0.055),
+    ("PLN", 0.25),
+    ("SEK", 0.095),
+    ("NOK", 0.093),
+    ("DKK", 0.145),
+    ("CZK", 0.043),
+    ("SGD", 0.74),
+    ("ZAR", 0.054),
+];
+
+// Jornadas de referencia para anualizar
+const HOURS_PER_YEAR: f64 = 2080.0;
+const DAYS_PER_YEAR: f64 = 260.0;
+const MONTHS_PER_YEAR: f64 = 12.0;
+
+fn usd_rates() -> &'static HashMap<String, f64> {
+    static RATES: OnceLock<HashMap<String, f64>> = OnceLock::new();
+    RATES.get_or_init(|| {
+        let mut rates: HashMap<String, f64> = DEFAULT_USD_RATES
+            .iter()
+            .map(|(c, r)| (c.to_string(), *r))
+            .collect();
+
+        if let Some(content) = std::env::var(KEY_FX_FILE)
+            .ok()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+        {
+            for line in content.lines() {
+                let l = line.trim();
+                if l.is_empty() || l.starts_with('#') {
+                    continue;
+                }
+                let Some((code, rate)) = l.split_once('=') else { continue };
+                if let Ok(rate) = rate.trim().parse::<f64>() {
+                    if rate > 0.0 {
+                        rates.insert(code.trim().to_uppercase(), rate);
+                    }
+                }
+            }
+        }
+        rates
+    })
+}
+
+/// Convierte `amount` de la divisa `from` a `to` con la tabla offline.
+pub fn convert_currency(amount: f64, from: &str, to: &str) -> Option<f64> {
+    let rates = usd_rates();
+    let from = rates.get(&from.trim().to_uppercase())?;
+    let to = rates.get(&to.trim().to_uppercase())?;
+    Some(amount * from / to)
+}
+
+impl SalaryRange {
+    /// Punto medio del rango expresado como salario anual en su propia divisa.
+    pub fn yearly_midpoint(&self) -> f64 {
+        let mid = (self.min as f64 + self.max.max(self.min) as f64) / 2.0;
+        match self.period {
+            SalaryPeriod::Hourly => mid * HOURS_PER_YEAR,
+            SalaryPeriod::Daily => mid * DAYS_PER_YEAR,
+            SalaryPeriod::Monthly => mid * MONTHS_PER_YEAR,
+            SalaryPeriod::Yearly => mid,
+        }
+    }
+
+    /// Salario anual (punto medio) convertido a la divisa `currency`.
+    /// `None` si alguna divisa no está en la tabla de cambio.
+    pub fn normalized_yearly(&self, currency: &str) -> Option<f64> {
+        convert_currency(self.yearly_midpoint(), &self.currency, currency).map(f64::round)
+    }
+}
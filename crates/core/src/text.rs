@@ -0,0 +1,16 @@
+/// Busca `term` en `haystack` sin distinguir mayúsculas y respetando límites
+/// de palabra, para que "go" no coincida con "google" ni "java" con
+/// "javascript". `+` y `#` cuentan como parte de la palabra ("c++", "c#").
+pub fn contains_term(haystack: &str, term: &str) -> bool {
+    let term = term.trim().to_lowercase();
+    if term.is_empty() {
+        return false;
+    }
+    let haystack = haystack.to_lowercase();
+    let is_word = |c: char| c.is_alphanumeric() || c == '+' || c == '#';
+    haystack.match_indices(&term).any(|(i, _)| {
+        let before = haystack[..i].chars().next_back();
+        let after = haystack[i + term.len()..].chars().next();
+        !before.is_some_and(is_word) && !after.is_some_and(is_word)
+    })
+}
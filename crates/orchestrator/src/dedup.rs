@@ -0,0 +1,236 @@
+use std::collections::{BTreeSet, HashMap};
+
+use job_hunter_core::{canonical_url, JobSighting, RawJobPosting};
+
+/// Umbral de similitud (Jaccard sobre tokens del título) para considerar
+/// que dos ofertas de la misma empresa son el mismo puesto.
+const TITLE_SIMILARITY: f64 = 0.8;
+
+/// Tokens que no aportan identidad al título ("Senior Rust Engineer (Remote)").
+const TITLE_NOISE: &[&str] = &[
+    "remote", "remoto", "the", "a", "an", "and", "of", "for", "m", "f", "d", "w", "x", "h", "all", "genders",
+];
+
+/// Sufijos societarios que se ignoran al comparar empresas.
+const COMPANY_SUFFIXES: &[&str] = &[
+    "inc", "llc", "ltd", "limited", "gmbh", "corp", "corporation", "co", "sl", "sa", "bv", "ag", "plc",
+];
+
+struct Entry {
+    company: String,
+    title_tokens: BTreeSet<String>,
+    sightings: Vec<JobSighting>,
+}
+
+/// Índice de ofertas ya admitidas en el run. Detecta duplicados entre fuentes
+/// (mismo id, misma URL canónica de oferta o de aplicación, o misma empresa con
+/// título casi idéntico) y acumula dónde se ha visto cada oferta.
+#[derive(Default)]
+pub(crate) struct Deduplicator {
+    entries: HashMap<String, Entry>,
+    by_url: HashMap<String, String>,
+}
+
+impl Deduplicator {
+    /// Devuelve solo las ofertas nuevas del lote; los duplicados se registran
+    /// como apariciones adicionales de la oferta original.
+    pub(crate) fn admit(&mut self, batch: Vec<RawJobPosting>) -> (Vec<RawJobPosting>, usize) {
+        let mut fresh = Vec::new();
+        let mut merged = 0;
+
+        for job in batch {
+            let urls = job_urls(&job);
+            match self.find(&job, &urls) {
+                Some(id) => {
+                    merged += 1;
+                    let sighting = job.sighting();
+                    if let Some(entry) = self.entries.get_mut(&id) {
+                        if !entry.sightings.contains(&sighting) {
+                            entry.sightings.push(sighting);
+                        }
+                    }
+                    for u in urls {
+                        self.by_url.entry(u).or_insert_with(|| id.clone());
+                    }
+                }
+                None => {
+                    for u in urls {
+                        self.by_url.entry(u).or_insert_with(|| job.id.clone());
+                    }
+                    self.entries.insert(
+                        job.id.clone(),
+                        Entry {
+                            company: job.normalized.company.as_deref().map(normalize_company).unwrap_or_default(),
+                            title_tokens: job.normalized.title.as_deref().map(title_tokens).unwrap_or_default(),
+                            sightings: vec![job.sighting()],
+                        },
+                    );
+                    fresh.push(job);
+                }
+            }
+        }
+
+        (fresh, merged)
+    }
+
+    /// Todas las apariciones registradas de la oferta `id`.
+    pub(crate) fn sightings(&self, id: &str) -> &[JobSighting] {
+        self.entries.get(id).map(|e| e.sightings.as_slice()).unwrap_or(&[])
+    }
+
+    fn find(&self, job: &RawJobPosting, urls: &[String]) -> Option<String> {
+        if self.entries.contains_key(&job.id) {
+            return Some(job.id.clone());
+        }
+        if let Some(id) = urls.iter().find_map(|u| self.by_url.get(u)) {
+            return Some(id.clone());
+        }
+
+        let company = normalize_company(job.normalized.company.as_deref()?);
+        let tokens = title_tokens(job.normalized.title.as_deref()?);
+        if company.is_empty() || tokens.is_empty() {
+            return None;
+        }
+
+        self.entries
+            .iter()
+            .find(|(_, e)| e.company == company && jaccard(&e.title_tokens, &tokens) >= TITLE_SIMILARITY)
+            .map(|(id, _)| id.clone())
+    }
+}
+
+fn job_urls(job: &RawJobPosting) -> Vec<String> {
+    let mut urls = vec![canonical_url(&job.url)];
+    if let Some(apply) = &job.normalized.apply_url {
+        let apply = canonical_url(apply);
+        if !urls.contains(&apply) {
+            urls.push(apply);
+        }
+    }
+    urls.retain(|u| !u.is_empty());
+    urls
+}
+
+fn words(s: &str) -> impl Iterator<Item = String> + '_ {
+    s.split(|c: char| !c.is_alphanumeric() && c != '+' && c != '#')
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_lowercase())
+}
+
+fn normalize_company(name: &str) -> String {
+    words(name)
+        .filter(|w| !COMPANY_SUFFIXES.contains(&w.as_str()))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn title_tokens(title: &str) -> BTreeSet<String> {
+    words(title)
+        .filter(|w| !TITLE_NOISE.contains(&w.as_str()))
+        .map(|w| match w.as_str() {
+            "sr" => "senior".to_string(),
+            "jr" => "junior".to_string(),
+            "eng" | "engr" => "engineer".to_string(),
+            "dev" => "developer".to_string(),
+            "mgr" => "manager".to_string(),
+            _ => w,
+        })
+        .collect()
+}
+
+fn jaccard(a: &BTreeSet<String>, b: &BTreeSet<String>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use job_hunter_core::JobSource;
+
+    fn job(source: JobSource, id: &str, url: &str, company: &str, title: &str) -> RawJobPosting {
+        RawJobPosting::new(source, Some(id), url, "").with_details(Some(title.into()), Some(company.into()), None)
+    }
+
+    /// `(descripción, segunda oferta, ¿duplicado de la primera?)`
+    #[test]
+    fn admit_matches_duplicates_across_sources() {
+        let first = || {
+            job(JobSource::RemoteOk, "1", "https://remoteok.com/jobs/1", "Acme Inc.", "Senior Rust Engineer (Remote)")
+        };
+        let cases = [
+            (
+                "misma empresa, título casi igual",
+                job(JobSource::Himalayas, "9", "https://himalayas.app/jobs/9", "ACME", "Sr. Rust Engineer"),
+                true,
+            ),
+            (
+                "mismo título, otra empresa",
+                job(JobSource::Himalayas, "9", "https://himalayas.app/jobs/9", "Globex", "Senior Rust Engineer"),
+                false,
+            ),
+            (
+                "misma empresa, otro puesto",
+                job(JobSource::Himalayas, "9", "https://himalayas.app/jobs/9", "Acme", "Senior Go Engineer"),
+                false,
+            ),
+            (
+                "misma URL canónica",
+                job(JobSource::Remotive, "7", "https://RemoteOK.com/jobs/1/?utm_source=feed", "Other", "Other"),
+                true,
+            ),
+            ("mismo id", job(JobSource::RemoteOk, "1", "https://remoteok.com/jobs/other", "X", "Y"), true),
+        ];
+
+        for (case, second, duplicate) in cases {
+            let mut dedup = Deduplicator::default();
+            let first = first();
+            let first_id = first.id.clone();
+            assert_eq!(dedup.admit(vec![first]).0.len(), 1, "{case}");
+
+            let sighting = second.sighting();
+            let (fresh, merged) = dedup.admit(vec![second]);
+            assert_eq!((fresh.is_empty(), merged == 1), (duplicate, duplicate), "{case}");
+            assert_eq!(dedup.sightings(&first_id).contains(&sighting), duplicate, "{case}");
+        }
+    }
+
+    /// La URL de aplicación compartida también delata el duplicado.
+    #[test]
+    fn admit_matches_shared_apply_url() {
+        let mut dedup = Deduplicator::default();
+        let a = job(JobSource::RemoteOk, "1", "https://remoteok.com/jobs/1", "Acme", "Backend Engineer")
+            .with_details(Some("Backend Engineer".into()), Some("Acme".into()), Some("https://acme.com/careers/42".into()));
+        let b = job(JobSource::Jobicy, "2", "https://jobicy.com/jobs/2", "Acme Labs", "Platform Engineer")
+            .with_details(Some("Platform Engineer".into()), Some("Acme Labs".into()), Some("https://acme.com/careers/42#apply".into()));
+        let id = a.id.clone();
+
+        dedup.admit(vec![a]);
+        let (fresh, merged) = dedup.admit(vec![b]);
+        assert!(fresh.is_empty() && merged == 1);
+        assert_eq!(dedup.sightings(&id).len(), 2);
+    }
+
+    /// Un duplicado dentro del mismo lote se fusiona y la misma aparición no se repite.
+    #[test]
+    fn sightings_are_merged_once() {
+        let mut dedup = Deduplicator::default();
+        let a = job(JobSource::RemoteOk, "1", "https://remoteok.com/jobs/1", "Acme", "Data Engineer");
+        let id = a.id.clone();
+        let (fresh, merged) = dedup.admit(vec![a.clone(), a.clone()]);
+        assert_eq!((fresh.len(), merged), (1, 1));
+        dedup.admit(vec![a]);
+        assert_eq!(dedup.sightings(&id).len(), 1);
+    }
+
+    #[test]
+    fn title_similarity_threshold() {
+        let a = title_tokens("Senior Rust Engineer");
+        assert!(jaccard(&a, &title_tokens("Sr Rust Eng (m/f/d)")) >= TITLE_SIMILARITY);
+        assert!(jaccard(&a, &title_tokens("Senior Rust Engineer, Payments")) < TITLE_SIMILARITY);
+        assert_eq!(normalize_company("Acme Systems GmbH"), "acme systems");
+    }
+}
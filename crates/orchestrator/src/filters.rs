@@ -0,0 +1,298 @@
+use job_hunter_core::{contains_term, RawJobPosting, SearchCriteria};
+
+/// Ubicaciones que equivalen a "sin restricción geográfica".
+const GLOBAL_LOCATIONS: &[&str] = &["worldwide", "anywhere", "global", "international"];
+
+/// Indicadores de presencialidad en el texto de ubicación.
+const ONSITE_HINTS: &[&str] = &["on-site", "onsite", "on site", "in office", "in-office", "hybrid", "presencial", "híbrido"];
+
+/// Sinónimos de países/regiones frecuentes en los boards.
+const LOCATION_ALIASES: &[&[&str]] = &[
+    &["eu", "europe", "european union", "emea"],
+    &["us", "usa", "united states", "north america"],
+    &["uk", "united kingdom", "great britain", "england"],
+    &["latam", "latin america", "south america"],
+    &["spain", "españa"],
+    &["germany", "deutschland"],
+];
+
+/// Descartes por filtro en un lote, en el orden en que se evalúan.
+#[derive(Default)]
+pub(crate) struct FilterReport {
+    counts: Vec<(&'static str, usize)>,
+}
+
+impl FilterReport {
+    fn reject(&mut self, filter: &'static str) {
+        match self.counts.iter_mut().find(|(f, _)| *f == filter) {
+            Some((_, n)) => *n += 1,
+            None => self.counts.push((filter, 1)),
+        }
+    }
+
+    pub(crate) fn total(&self) -> usize {
+        self.counts.iter().map(|(_, n)| n).sum()
+    }
+
+    /// "exclude_keywords: 3, remote_only: 1"
+    pub(crate) fn summary(&self) -> String {
+        self.counts
+            .iter()
+            .map(|(f, n)| format!("{}: {}", f, n))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Aplica los filtros duros de `criteria` (antigüedad, caducidad y `SearchFilters`).
+/// Cada oferta descartada se atribuye al primer filtro que la rechaza. Los
+/// datos desconocidos (sin salario, sin ubicación...) nunca descartan.
+pub(crate) fn apply(criteria: &SearchCriteria, jobs: Vec<RawJobPosting>) -> (Vec<RawJobPosting>, FilterReport) {
+    let mut report = FilterReport::default();
+    let kept = jobs
+        .into_iter()
+        .filter(|job| match rejected_by(criteria, job) {
+            Some(filter) => {
+                report.reject(filter);
+                false
+            }
+            None => true,
+        })
+        .collect();
+    (kept, report)
+}
+
+fn rejected_by(criteria: &SearchCriteria, job: &RawJobPosting) -> Option<&'static str> {
+    let f = &criteria.filters;
+
+    if criteria.max_age_days.is_some_and(|days| job.is_older_than(days)) {
+        return Some("max_age_days");
+    }
+
+    if job.is_expired() {
+        return Some("expired");
+    }
+
+    if !f.company_blocklist.is_empty() {
+        if let Some(company) = job.normalized.company.as_deref() {
+            if f.company_blocklist.iter().any(|c| contains_term(company, c)) {
+                return Some("company_blocklist");
+            }
+        }
+    }
+
+    if !f.exclude_keywords.is_empty() || !f.required_keywords.is_empty() {
+        let text = job.searchable_text();
+        if f.exclude_keywords.iter().any(|k| contains_term(&text, k)) {
+            return Some("exclude_keywords");
+        }
+        if !f.required_keywords.iter().all(|k| contains_term(&text, k)) {
+            return Some("required_keywords");
+        }
+    }
+
+    if let Some(min) = f.min_salary {
+        let yearly = job
+            .normalized
+            .salary
+            .as_ref()
+            .and_then(|s| s.normalized_yearly(&criteria.target_currency));
+        if yearly.is_some_and(|y| y < min as f64) {
+            return Some("min_salary");
+        }
+    }
+
+    if !f.job_types.is_empty() && job.normalized.job_type.as_ref().is_some_and(|t| !f.job_types.contains(t)) {
+        return Some("job_types");
+    }
+
+    if f.remote_only && !is_remote(job) {
+        return Some("remote_only");
+    }
+
+    if !f.locations.is_empty() {
+        if let Some(location) = job.normalized.location.as_deref().map(str::to_lowercase) {
+            if !location_allowed(&location, &f.locations) {
+                return Some("locations");
+            }
+        }
+    }
+
+    None
+}
+
+fn is_remote(job: &RawJobPosting) -> bool {
+    match (job.normalized.is_remote, job.normalized.location.as_deref()) {
+        (Some(remote), _) => remote,
+        (None, Some(loc)) => {
+            let loc = loc.to_lowercase();
+            !ONSITE_HINTS.iter().any(|h| loc.contains(h))
+        }
+        (None, None) => true,
+    }
+}
+
+fn location_allowed(location: &str, allowed: &[String]) -> bool {
+    if GLOBAL_LOCATIONS.iter().any(|g| location.contains(g)) {
+        return true;
+    }
+    allowed.iter().any(|wanted| {
+        let wanted = wanted.trim().to_lowercase();
+        if contains_term(location, &wanted) {
+            return true;
+        }
+        LOCATION_ALIASES
+            .iter()
+            .filter(|group| group.contains(&wanted.as_str()))
+            .any(|group| group.iter().any(|alias| contains_term(location, alias)))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+    use job_hunter_core::{JobSource, JobType, SalaryPeriod, SalaryRange, SearchFilters};
+
+    fn job(title: &str) -> RawJobPosting {
+        RawJobPosting::new(JobSource::RemoteOk, Some(title), format!("https://remoteok.com/{title}"), "Rust and Tokio")
+            .with_details(Some(title.into()), Some("Acme Systems".into()), None)
+    }
+
+    fn criteria(filters: SearchFilters) -> SearchCriteria {
+        SearchCriteria { filters, ..Default::default() }
+    }
+
+    #[test]
+    fn max_age_days_and_expired() {
+        let c = SearchCriteria { max_age_days: Some(7), ..Default::default() };
+        let old = job("old").with_posted_at(Some(Utc::now() - Duration::days(10)));
+        let fresh = job("fresh").with_posted_at(Some(Utc::now() - Duration::days(2)));
+        assert_eq!(rejected_by(&c, &old), Some("max_age_days"));
+        assert_eq!(rejected_by(&c, &fresh), None);
+        assert_eq!(rejected_by(&c, &job("undated")), None);
+
+        let expired = job("expired").with_valid_through(Some(Utc::now() - Duration::days(1)));
+        assert_eq!(rejected_by(&SearchCriteria::default(), &expired), Some("expired"));
+    }
+
+    #[test]
+    fn company_blocklist() {
+        let c = criteria(SearchFilters { company_blocklist: vec!["acme".into()], ..Default::default() });
+        assert_eq!(rejected_by(&c, &job("a")), Some("company_blocklist"));
+        let c = criteria(SearchFilters { company_blocklist: vec!["globex".into()], ..Default::default() });
+        assert_eq!(rejected_by(&c, &job("a")), None);
+    }
+
+    #[test]
+    fn exclude_and_required_keywords() {
+        let c = criteria(SearchFilters { exclude_keywords: vec!["tokio".into()], ..Default::default() });
+        assert_eq!(rejected_by(&c, &job("a")), Some("exclude_keywords"));
+
+        let c = criteria(SearchFilters { required_keywords: vec!["rust".into(), "kafka".into()], ..Default::default() });
+        assert_eq!(rejected_by(&c, &job("a")), Some("required_keywords"));
+        let c = criteria(SearchFilters { required_keywords: vec!["rust".into()], ..Default::default() });
+        assert_eq!(rejected_by(&c, &job("a")), None);
+    }
+
+    /// En fuentes JSON el contenido es el item serializado: sus claves no
+    /// cuentan como texto de la oferta.
+    #[test]
+    fn keywords_ignore_json_keys() {
+        let item = serde_json::json!({
+            "title": "Data Analyst", "company": "Acme", "salary": "", "remote": true,
+            "location": "Europe", "tags": ["sql"], "description": "SQL and dashboards"
+        });
+        let posting = RawJobPosting::new(JobSource::Remotive, Some("1"), "https://remotive.com/1", item.to_string())
+            .with_details(Some("Data Analyst".into()), Some("Acme".into()), None)
+            .with_description(Some("SQL and dashboards".into()), vec!["sql".into()]);
+        let keywords = |exclude: &str, required: &str| {
+            criteria(SearchFilters {
+                exclude_keywords: vec![exclude.into()],
+                required_keywords: vec![required.into()],
+                ..Default::default()
+            })
+        };
+
+        for key in ["salary", "remote", "location", "tags", "company"] {
+            assert_eq!(rejected_by(&keywords(key, "sql"), &posting), None, "{key}");
+            assert_eq!(rejected_by(&keywords("php", key), &posting), Some("required_keywords"), "{key}");
+        }
+        assert_eq!(rejected_by(&keywords("dashboards", "sql"), &posting), Some("exclude_keywords"));
+        assert_eq!(rejected_by(&keywords("php", "analyst"), &posting), None);
+    }
+
+    /// El mínimo se compara con el salario anual convertido a `target_currency`.
+    #[test]
+    fn min_salary_normalizes_currency() {
+        let salary = SalaryRange { min: 60_000, max: 60_000, currency: "EUR".into(), period: SalaryPeriod::Yearly };
+        let paid = job("paid").with_salary(Some(salary));
+        let min = |min: u32, currency: &str| SearchCriteria {
+            target_currency: currency.into(),
+            filters: SearchFilters { min_salary: Some(min), ..Default::default() },
+            ..Default::default()
+        };
+
+        // 60.000 EUR ≈ 64.800 USD con la tabla por defecto
+        assert_eq!(rejected_by(&min(62_000, "USD"), &paid), None);
+        assert_eq!(rejected_by(&min(62_000, "EUR"), &paid), Some("min_salary"));
+        assert_eq!(rejected_by(&min(70_000, "USD"), &paid), Some("min_salary"));
+        // Sin salario no se descarta
+        assert_eq!(rejected_by(&min(70_000, "USD"), &job("unknown")), None);
+    }
+
+    #[test]
+    fn job_types() {
+        let c = criteria(SearchFilters { job_types: vec![JobType::FullTime], ..Default::default() });
+        assert_eq!(rejected_by(&c, &job("c").with_job_type(Some(JobType::Contract))), Some("job_types"));
+        assert_eq!(rejected_by(&c, &job("f").with_job_type(Some(JobType::FullTime))), None);
+        assert_eq!(rejected_by(&c, &job("unknown")), None);
+    }
+
+    #[test]
+    fn remote_only() {
+        let c = criteria(SearchFilters { remote_only: true, ..Default::default() });
+        assert_eq!(rejected_by(&c, &job("office").with_location(Some("Berlin".into()), Some(false))), Some("remote_only"));
+        assert_eq!(rejected_by(&c, &job("hybrid").with_location(Some("Madrid (Hybrid)".into()), None)), Some("remote_only"));
+        assert_eq!(rejected_by(&c, &job("remote").with_location(Some("Europe".into()), None)), None);
+        assert_eq!(rejected_by(&c, &job("unknown")), None);
+    }
+
+    #[test]
+    fn locations_with_aliases() {
+        let c = criteria(SearchFilters { locations: vec!["Spain".into(), "EU".into()], ..Default::default() });
+        let at = |loc: &str| job(loc).with_location(Some(loc.into()), None);
+        assert_eq!(rejected_by(&c, &at("Madrid, España")), None);
+        assert_eq!(rejected_by(&c, &at("Remote - Europe")), None);
+        assert_eq!(rejected_by(&c, &at("Worldwide")), None);
+        assert_eq!(rejected_by(&c, &at("USA only")), Some("locations"));
+        assert_eq!(rejected_by(&c, &job("unknown")), None);
+
+        // «America» a secas no es EE. UU.: Latinoamérica no pasa por "US"
+        let c = criteria(SearchFilters { locations: vec!["US".into()], ..Default::default() });
+        assert_eq!(rejected_by(&c, &at("Remote - North America")), None);
+        assert_eq!(rejected_by(&c, &at("United States")), None);
+        assert_eq!(rejected_by(&c, &at("Latin America")), Some("locations"));
+        assert_eq!(rejected_by(&c, &at("South America")), Some("locations"));
+    }
+
+    /// Cada descarte se atribuye al primer filtro que lo rechaza.
+    #[test]
+    fn report_counts_per_filter() {
+        let c = SearchCriteria {
+            filters: SearchFilters { exclude_keywords: vec!["php".into()], remote_only: true, ..Default::default() },
+            ..Default::default()
+        };
+        let jobs = vec![
+            job("php-dev").with_description(Some("PHP".into()), vec![]),
+            job("PHP Developer").with_location(Some("Onsite".into()), Some(false)),
+            job("office").with_location(Some("Paris".into()), Some(false)),
+            job("ok"),
+        ];
+        let (kept, report) = apply(&c, jobs);
+        // «PHP Developer» es presencial, pero cuenta solo para `exclude_keywords`
+        assert_eq!(kept.len(), 1);
+        assert_eq!(report.total(), 3);
+        assert_eq!(report.summary(), "exclude_keywords: 2, remote_only: 1");
+    }
+}
@@ -0,0 +1,351 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use job_hunter_core::JobSource;
+
+/// Intentos que se conservan por fuente.
+const HISTORY_LEN: usize = 20;
+/// Fallos seguidos (error o timeout) que abren el circuito.
+const FAILURE_THRESHOLD: u32 = 3;
+/// Primera pausa del circuito abierto; se dobla en cada recaída hasta `MAX_COOLDOWN`.
+const BASE_COOLDOWN: Duration = Duration::from_secs(10 * 60);
+const MAX_COOLDOWN: Duration = Duration::from_secs(2 * 60 * 60);
+/// Ejecuciones seguidas sin ofertas a partir de las cuales cada una cuenta como fallo
+/// (selectores rotos, bloqueos que devuelven una página vacía...).
+const EMPTY_THRESHOLD: u32 = 5;
+/// Tiempo que se reserva la prueba del circuito semiabierto; si la ejecución no
+/// llega a registrarse, pasado este plazo se permite otra.
+const PROBE_LEASE: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttemptOutcome {
+    Ok,
+    /// Terminó sin error pero sin ninguna oferta.
+    Empty,
+    Failed,
+    TimedOut,
+}
+
+/// Una ejecución de scraper sobre la fuente.
+#[derive(Debug, Clone)]
+pub struct SourceAttempt {
+    pub at: DateTime<Utc>,
+    pub outcome: AttemptOutcome,
+    pub latency_ms: u64,
+    /// Ofertas devueltas por el scraper (antes de filtros y dedup).
+    pub jobs: usize,
+    pub error: Option<String>,
+}
+
+/// `Closed`: la fuente se ejecuta. `Open`: se omite hasta `open_until`.
+/// `HalfOpen`: la pausa terminó y la próxima ejecución decide si se cierra.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+/// Foto del estado de una fuente para la API.
+#[derive(Debug, Clone)]
+pub struct SourceHealth {
+    pub source: JobSource,
+    pub state: CircuitState,
+    pub consecutive_failures: u32,
+    pub consecutive_empty: u32,
+    pub open_until: Option<DateTime<Utc>>,
+    pub last_success_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    /// Sobre el historial conservado (0.0 - 1.0).
+    pub success_rate: f32,
+    /// Medias de las ejecuciones sin error del historial.
+    pub avg_latency_ms: u64,
+    pub avg_jobs: f32,
+    /// Más reciente primero.
+    pub recent: Vec<SourceAttempt>,
+}
+
+struct Entry {
+    source: JobSource,
+    history: VecDeque<SourceAttempt>,
+    consecutive_failures: u32,
+    consecutive_empty: u32,
+    /// Veces que se ha abierto el circuito sin un éxito entre medias.
+    trips: u32,
+    open_until: Option<DateTime<Utc>>,
+    /// Prueba del circuito semiabierto en curso, reservada hasta esta hora.
+    probe_until: Option<DateTime<Utc>>,
+    last_success_at: Option<DateTime<Utc>>,
+}
+
+impl Entry {
+    fn new(source: JobSource) -> Self {
+        Self {
+            source,
+            history: VecDeque::with_capacity(HISTORY_LEN),
+            consecutive_failures: 0,
+            consecutive_empty: 0,
+            trips: 0,
+            open_until: None,
+            probe_until: None,
+            last_success_at: None,
+        }
+    }
+
+    fn state(&self, now: DateTime<Utc>) -> CircuitState {
+        match self.open_until {
+            None => CircuitState::Closed,
+            Some(t) if t > now => CircuitState::Open,
+            Some(_) => CircuitState::HalfOpen,
+        }
+    }
+
+    fn snapshot(&self, now: DateTime<Utc>) -> SourceHealth {
+        let ok: Vec<&SourceAttempt> = self
+            .history
+            .iter()
+            .filter(|a| matches!(a.outcome, AttemptOutcome::Ok | AttemptOutcome::Empty))
+            .collect();
+        let (success_rate, avg_latency_ms, avg_jobs) = if ok.is_empty() {
+            (0.0, 0, 0.0)
+        } else {
+            let n = ok.len();
+            (
+                n as f32 / self.history.len() as f32,
+                ok.iter().map(|a| a.latency_ms).sum::<u64>() / n as u64,
+                ok.iter().map(|a| a.jobs).sum::<usize>() as f32 / n as f32,
+            )
+        };
+        SourceHealth {
+            source: self.source.clone(),
+            state: self.state(now),
+            consecutive_failures: self.consecutive_failures,
+            consecutive_empty: self.consecutive_empty,
+            open_until: self.open_until,
+            last_success_at: self.last_success_at,
+            last_error: self.history.iter().rev().find_map(|a| a.error.clone()),
+            success_rate,
+            avg_latency_ms,
+            avg_jobs,
+            recent: self.history.iter().rev().cloned().collect(),
+        }
+    }
+}
+
+/// Historial de salud de las fuentes y circuit breaker, compartido por todas
+/// las ejecuciones del proceso (cada búsqueda crea su propio `Orchestrator`).
+///
+/// Tras `FAILURE_THRESHOLD` fallos seguidos el circuito se abre y la fuente se
+/// omite durante la pausa; pasada la pausa se deja pasar una única ejecución de
+/// prueba: un éxito lo cierra y un fallo lo reabre con el doble de pausa. A partir
+/// de `EMPTY_THRESHOLD` ejecuciones seguidas sin ofertas, cada una cuenta como fallo.
+#[derive(Default)]
+pub struct SourceHealthRegistry {
+    entries: Mutex<HashMap<String, Entry>>,
+}
+
+impl SourceHealthRegistry {
+    pub fn global() -> &'static SourceHealthRegistry {
+        static REGISTRY: OnceLock<SourceHealthRegistry> = OnceLock::new();
+        REGISTRY.get_or_init(SourceHealthRegistry::default)
+    }
+
+    /// Decide si `source` se ejecuta ahora. `Err(hasta)` si hay que omitirla: el
+    /// circuito está abierto o ya hay otra ejecución de prueba en curso. Con el
+    /// circuito semiabierto, el `Ok` reserva la prueba para quien llama.
+    pub fn admit(&self, source: &JobSource) -> Result<(), DateTime<Utc>> {
+        let now = Utc::now();
+        let mut entries = self.entries.lock().unwrap();
+        let Some(entry) = entries.get_mut(source.slug()) else { return Ok(()) };
+        match entry.state(now) {
+            CircuitState::Closed => Ok(()),
+            CircuitState::Open => Err(entry.open_until.unwrap_or(now)),
+            CircuitState::HalfOpen => match entry.probe_until {
+                Some(until) if until > now => Err(until),
+                _ => {
+                    entry.probe_until = Some(now + lease());
+                    Ok(())
+                }
+            },
+        }
+    }
+
+    /// Registra una ejecución. Devuelve `Some(hasta)` si con ella se abre el circuito.
+    pub fn record(&self, source: &JobSource, mut attempt: SourceAttempt) -> Option<DateTime<Utc>> {
+        let mut entries = self.entries.lock().unwrap();
+        let entry = entries
+            .entry(source.slug().to_string())
+            .or_insert_with(|| Entry::new(source.clone()));
+
+        let now = attempt.at;
+        let mut opened = None;
+        entry.probe_until = None;
+        let failed = match attempt.outcome {
+            AttemptOutcome::Ok => {
+                entry.consecutive_failures = 0;
+                entry.consecutive_empty = 0;
+                entry.trips = 0;
+                entry.open_until = None;
+                entry.last_success_at = Some(now);
+                false
+            }
+            AttemptOutcome::Empty => {
+                entry.consecutive_empty += 1;
+                let degraded = entry.consecutive_empty >= EMPTY_THRESHOLD;
+                if degraded && attempt.error.is_none() {
+                    attempt.error = Some(format!("sin ofertas en {} ejecuciones seguidas", entry.consecutive_empty));
+                }
+                degraded
+            }
+            AttemptOutcome::Failed | AttemptOutcome::TimedOut => true,
+        };
+        if failed {
+            entry.consecutive_failures += 1;
+            if entry.consecutive_failures >= FAILURE_THRESHOLD {
+                entry.trips += 1;
+                let until = now + cooldown(entry.trips);
+                entry.open_until = Some(until);
+                opened = Some(until);
+            }
+        }
+
+        if entry.history.len() == HISTORY_LEN {
+            entry.history.pop_front();
+        }
+        entry.history.push_back(attempt);
+        opened
+    }
+
+    /// Estado de todas las fuentes con historial, ordenadas por slug.
+    pub fn snapshot(&self) -> Vec<SourceHealth> {
+        let now = Utc::now();
+        let entries = self.entries.lock().unwrap();
+        let mut out: Vec<SourceHealth> = entries.values().map(|e| e.snapshot(now)).collect();
+        out.sort_by(|a, b| a.source.slug().cmp(b.source.slug()));
+        out
+    }
+}
+
+fn lease() -> chrono::Duration {
+    chrono::Duration::from_std(PROBE_LEASE).unwrap_or_else(|_| chrono::Duration::minutes(5))
+}
+
+fn cooldown(trips: u32) -> chrono::Duration {
+    let d = BASE_COOLDOWN
+        .saturating_mul(1 << trips.saturating_sub(1).min(8))
+        .min(MAX_COOLDOWN);
+    chrono::Duration::from_std(d).unwrap_or_else(|_| chrono::Duration::hours(2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attempt(at: DateTime<Utc>, outcome: AttemptOutcome, jobs: usize) -> SourceAttempt {
+        SourceAttempt { at, outcome, latency_ms: 100, jobs, error: None }
+    }
+
+    fn source() -> JobSource {
+        JobSource::Custom("health_test".into())
+    }
+
+    fn minutes(d: Option<DateTime<Utc>>, since: DateTime<Utc>) -> i64 {
+        (d.expect("circuito abierto") - since).num_minutes()
+    }
+
+    #[test]
+    fn opens_after_failure_threshold() {
+        let reg = SourceHealthRegistry::default();
+        let now = Utc::now();
+        for _ in 1..FAILURE_THRESHOLD {
+            assert!(reg.record(&source(), attempt(now, AttemptOutcome::Failed, 0)).is_none());
+        }
+        assert!(reg.admit(&source()).is_ok());
+
+        let until = reg.record(&source(), attempt(now, AttemptOutcome::TimedOut, 0));
+        assert_eq!(minutes(until, now), 10);
+        assert_eq!(reg.admit(&source()), Err(until.unwrap()));
+        assert_eq!(reg.snapshot()[0].state, CircuitState::Open);
+    }
+
+    /// Cada recaída dobla la pausa hasta `MAX_COOLDOWN`.
+    #[test]
+    fn cooldown_doubles_up_to_max() {
+        let reg = SourceHealthRegistry::default();
+        let now = Utc::now();
+        for _ in 1..FAILURE_THRESHOLD {
+            reg.record(&source(), attempt(now, AttemptOutcome::Failed, 0));
+        }
+        let trips: Vec<i64> = (0..6)
+            .map(|_| minutes(reg.record(&source(), attempt(now, AttemptOutcome::Failed, 0)), now))
+            .collect();
+        assert_eq!(trips, [10, 20, 40, 80, 120, 120]);
+    }
+
+    #[test]
+    fn success_resets_circuit() {
+        let reg = SourceHealthRegistry::default();
+        let now = Utc::now();
+        for _ in 0..FAILURE_THRESHOLD + 1 {
+            reg.record(&source(), attempt(now, AttemptOutcome::Failed, 0));
+        }
+        assert!(reg.record(&source(), attempt(now, AttemptOutcome::Ok, 4)).is_none());
+
+        let health = &reg.snapshot()[0];
+        assert_eq!(health.state, CircuitState::Closed);
+        assert_eq!(health.consecutive_failures, 0);
+        assert_eq!(health.last_success_at, Some(now));
+        assert!(reg.admit(&source()).is_ok());
+
+        // Vuelve a necesitar `FAILURE_THRESHOLD` fallos y la pausa base
+        for _ in 1..FAILURE_THRESHOLD {
+            assert!(reg.record(&source(), attempt(now, AttemptOutcome::Failed, 0)).is_none());
+        }
+        assert_eq!(minutes(reg.record(&source(), attempt(now, AttemptOutcome::Failed, 0)), now), 10);
+    }
+
+    /// Con la pausa vencida solo pasa una ejecución de prueba a la vez.
+    #[test]
+    fn half_open_admits_a_single_probe() {
+        let reg = SourceHealthRegistry::default();
+        let long_ago = Utc::now() - chrono::Duration::days(1);
+        for _ in 0..FAILURE_THRESHOLD {
+            reg.record(&source(), attempt(long_ago, AttemptOutcome::Failed, 0));
+        }
+        assert_eq!(reg.snapshot()[0].state, CircuitState::HalfOpen);
+
+        assert!(reg.admit(&source()).is_ok());
+        assert!(reg.admit(&source()).is_err());
+        assert!(reg.admit(&source()).is_err());
+
+        reg.record(&source(), attempt(Utc::now(), AttemptOutcome::Ok, 2));
+        assert!(reg.admit(&source()).is_ok());
+        assert!(reg.admit(&source()).is_ok());
+    }
+
+    /// Las ejecuciones vacías cuentan como fallo a partir de `EMPTY_THRESHOLD`.
+    #[test]
+    fn repeated_empty_runs_degrade_the_source() {
+        let reg = SourceHealthRegistry::default();
+        let now = Utc::now();
+        for _ in 1..EMPTY_THRESHOLD {
+            reg.record(&source(), attempt(now, AttemptOutcome::Empty, 0));
+        }
+        let health = &reg.snapshot()[0];
+        assert_eq!((health.consecutive_empty, health.consecutive_failures), (EMPTY_THRESHOLD - 1, 0));
+        assert!(health.last_error.is_none());
+
+        let opened: Vec<bool> = (0..FAILURE_THRESHOLD)
+            .map(|_| reg.record(&source(), attempt(now, AttemptOutcome::Empty, 0)).is_some())
+            .collect();
+        assert_eq!(opened.last(), Some(&true));
+        assert!(!opened[..opened.len() - 1].iter().any(|o| *o));
+        assert!(reg.snapshot()[0].last_error.as_deref().unwrap().contains("sin ofertas"));
+
+        reg.record(&source(), attempt(now, AttemptOutcome::Ok, 3));
+        let health = &reg.snapshot()[0];
+        assert_eq!((health.consecutive_empty, health.state), (0, CircuitState::Closed));
+    }
+}
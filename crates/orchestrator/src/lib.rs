@@ -1,2 +1,7 @@
+mod dedup;
+mod filters;
+mod health;
 mod orchestrator;
+mod run;
+pub use health::{AttemptOutcome, CircuitState, SourceAttempt, SourceHealth, SourceHealthRegistry};
 pub use orchestrator::Orchestrator;
@@ -1,37 +1,79 @@
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::mpsc;
+use std::time::Duration;
+use tokio::sync::{broadcast, mpsc};
 use tracing::{debug, error, info, warn};
 use std::panic::AssertUnwindSafe;
-use futures::FutureExt; 
+use futures::FutureExt;
 
-use job_hunter_core::{Agent, AgentMessage, AnalyzedJobPosting, SearchCriteria};
+use chrono::Utc;
+use job_hunter_core::{Agent, AgentMessage, AnalyzedJobPosting, JobSource, SearchCriteria};
+
+use crate::dedup::Deduplicator;
+use crate::filters;
+use crate::health::{AttemptOutcome, SourceAttempt, SourceHealthRegistry};
+use crate::run::{BranchDone, BranchOutcome, RunTracker, Stage};
 
 pub struct Orchestrator {
     agents: HashMap<String, Arc<dyn Agent>>,
-    message_tx: mpsc::Sender<(String, AgentMessage)>,
-    message_rx: mpsc::Receiver<(String, AgentMessage)>,
+    done_tx: mpsc::UnboundedSender<BranchDone>,
+    done_rx: mpsc::UnboundedReceiver<BranchDone>,
     result_tx: mpsc::Sender<Vec<AnalyzedJobPosting>>,
     current_criteria: Option<Arc<SearchCriteria>>,
+    // Scrapers activados por start_search, pendientes de despachar en run()
+    queued: Vec<(String, AgentMessage)>,
+    run: RunTracker,
+    dedup: Deduplicator,
+    scrape_timeout: Duration,
+    stage_timeout: Duration,
+    health: &'static SourceHealthRegistry,
+    ws_tx: Option<broadcast::Sender<String>>,
 }
 
 impl Orchestrator {
     pub fn new() -> (Self, mpsc::Receiver<Vec<AnalyzedJobPosting>>) {
-        let (message_tx, message_rx) = mpsc::channel(100);
+        let (done_tx, done_rx) = mpsc::unbounded_channel();
         let (result_tx, result_rx) = mpsc::channel(10);
 
         (
             Self {
                 agents: HashMap::new(),
-                message_tx,
-                message_rx,
+                done_tx,
+                done_rx,
                 result_tx,
                 current_criteria: None,
+                queued: Vec::new(),
+                run: RunTracker::default(),
+                dedup: Deduplicator::default(),
+                scrape_timeout: Duration::from_secs(120),
+                // Los modelos locales pueden tardar minutos por oferta
+                stage_timeout: Duration::from_secs(30 * 60),
+                health: SourceHealthRegistry::global(),
+                ws_tx: None,
             },
             result_rx,
         )
     }
 
+    /// Ajusta los timeouts por rama: scraping y análisis/enriquecimiento.
+    pub fn with_timeouts(mut self, scrape: Duration, stage: Duration) -> Self {
+        self.scrape_timeout = scrape;
+        self.stage_timeout = stage;
+        self
+    }
+
+    /// Canal WebSocket para reportar a la UI el progreso del run.
+    pub fn with_ws_tx(mut self, tx: broadcast::Sender<String>) -> Self {
+        self.ws_tx = Some(tx);
+        self
+    }
+
+    fn emit_log(&self, level: &str, msg: impl Into<String>) {
+        if let Some(tx) = &self.ws_tx {
+            let _ = tx.send(serde_json::json!({"type":"log", "payload": {"level": level, "msg": msg.into()}}).to_string());
+        }
+    }
+
     pub fn register_agent(&mut self, agent: Arc<dyn Agent>) {
         info!("📝 Registrando agente: {}", agent.name());
         self.agents.insert(agent.name().to_string(), agent);
@@ -43,119 +85,229 @@ impl Orchestrator {
 
         let scrapers: Vec<String> = self
             .agents
-            .keys()
-            .filter(|k| k.starts_with("scraper_"))
-            .cloned()
+            .iter()
+            .filter(|(_, agent)| agent.source().is_some())
+            .map(|(name, _)| name.clone())
             .collect();
 
         if scrapers.is_empty() {
             warn!("⚠️ No hay scrapers registrados. La búsqueda no hará nada.");
-            let _ = self.result_tx.send(vec![]).await;
-            let _ = self
-                .message_tx
-                .send(("__orchestrator__".into(), AgentMessage::Shutdown))
-                .await;
             return Ok(());
         }
 
         for name in scrapers {
+            // Solo las fuentes activadas en la búsqueda reciben el mensaje
+            let Some(source) = self.enabled_source(&name) else {
+                debug!("⏭️ [{}] Fuente desactivada: no se activa.", name);
+                continue;
+            };
+            // Circuito abierto: la fuente lleva varios fallos seguidos y está en pausa
+            if let Err(until) = self.health.admit(&source) {
+                let msg = format!(
+                    "⛔ [{}] Fuente en pausa por fallos repetidos hasta las {} UTC: se omite.",
+                    name,
+                    until.format("%H:%M")
+                );
+                warn!("{}", msg);
+                self.emit_log("warn", msg);
+                continue;
+            }
             info!("🛰️ Activando scraper: {}", name);
-            let _ = self
-                .message_tx
-                .send((name, AgentMessage::StartScraping(criteria_arc.clone())))
-                .await;
+            self.queued
+                .push((name, AgentMessage::StartScraping(criteria_arc.clone())));
+        }
+
+        if self.queued.is_empty() {
+            warn!("⚠️ Ninguna fuente activada. La búsqueda no hará nada.");
         }
 
         Ok(())
     }
 
+    /// Fuente del agente `name` si es un scraper activado en la búsqueda actual.
+    fn enabled_source(&self, name: &str) -> Option<JobSource> {
+        let agent = self.agents.get(name)?;
+        let criteria = self.current_criteria.as_ref()?;
+        let settings = agent.source_settings(criteria)?;
+        settings.enabled.then_some(settings.source)
+    }
+
     pub async fn run(mut self) -> anyhow::Result<()> {
         info!("🚀 Orquestador (con Supervisión) iniciado...");
 
-        while let Some((target, msg)) = self.message_rx.recv().await {
-            
-            if let AgentMessage::Shutdown = msg {
-                break;
-            }
+        for (target, msg) in std::mem::take(&mut self.queued) {
+            self.dispatch(&target, msg);
+        }
 
-            if let Some(agent) = self.agents.get(&target) {
-                let agent = agent.clone();
-                let tx = self.message_tx.clone();
-                let res_tx = self.result_tx.clone();
-                let criteria_ref = self.current_criteria.clone();
-
-                // --- SUPERVISOR TASK WRAPPER ---
-                tokio::spawn(async move {
-                    // Usamos AssertUnwindSafe para capturar pánicos (crashes de Rust)
-                    let result = AssertUnwindSafe(async {
-                        debug!("🔎 [Supervisor] Ejecutando agente: {}", agent.name());
-                        agent.process(msg).await
-                    }).catch_unwind().await;
-
-                    match result {
-                        // El agente terminó "bien" (Ok o Err controlado)
-                        Ok(process_result) => {
-                            match process_result {
-                                Ok(response) => {
-                                    Self::route(response, tx, res_tx, criteria_ref).await;
-                                }
-                                Err(e) => {
-                                    error!("⚠️ [Supervisor] Agente '{}' reportó error: {}", agent.name(), e);
-                                    // Notificamos error interno pero no cerramos el sistema completo
-                                    let _ = tx.send(("__orchestrator__".into(), AgentMessage::Error(e.to_string()))).await;
-                                }
-                            }
-                        },
-                        // El agente entró en PÁNICO (Crash real)
-                        Err(panic_cause) => {
-                            let cause_str = if let Some(s) = panic_cause.downcast_ref::<&str>() {
-                                s.to_string()
-                            } else {
-                                "Unknown panic".to_string()
-                            };
-                            error!("🚨 [SUPERVISOR] CRITICAL: Agente '{}' CRASHED! Causa: {}", agent.name(), cause_str);
-                            let _ = tx.send(("__orchestrator__".into(), AgentMessage::Error(format!("PANIC: {}", cause_str)))).await;
-                        }
-                    }
-                });
-            } else if target == "__orchestrator__" {
-                if let AgentMessage::Error(e) = msg {
-                    warn!("⚙️ [Orchestrator Logic] Error recibido de subsistema: {}", e);
+        // Cada rama (scraper, lote en analyzer, lote en enricher) avisa al terminar.
+        // El run concluye cuando no queda ninguna pendiente.
+        while !self.run.is_idle() {
+            let Some(done) = self.done_rx.recv().await else { break };
+            self.on_branch_done(done);
+        }
+
+        let mut merged = self.run.take_merged();
+        for job in &mut merged {
+            for sighting in self.dedup.sightings(&job.id) {
+                if !job.sources.contains(sighting) {
+                    job.sources.push(sighting.clone());
                 }
-            } else {
-                warn!("❓ Mensaje a agente desconocido: {}", target);
             }
         }
-        
+        info!(
+            "✅ Proceso completado ({}). Enviando {} resultados.",
+            self.run.summary(),
+            merged.len()
+        );
+        let _ = self.result_tx.send(merged).await;
+
         info!("🛑 Orquestador detenido.");
         Ok(())
     }
 
-    async fn route(
-        msg: AgentMessage,
-        tx: mpsc::Sender<(String, AgentMessage)>,
-        res_tx: mpsc::Sender<Vec<AnalyzedJobPosting>>,
-        criteria: Option<Arc<SearchCriteria>>,
-    ) {
+    /// Lanza `msg` sobre el agente `target` como una nueva rama supervisada.
+    fn dispatch(&mut self, target: &str, msg: AgentMessage) {
+        let Some(agent) = self.agents.get(target).cloned() else {
+            warn!("❓ Mensaje a agente desconocido: {}", target);
+            return;
+        };
+
+        let stage = Stage::of(&msg);
+        let limit = if stage == Stage::Scrape { self.scrape_timeout } else { self.stage_timeout };
+        let id = self.run.open(target, stage);
+        let done_tx = self.done_tx.clone();
+
+        // --- SUPERVISOR TASK WRAPPER ---
+        tokio::spawn(async move {
+            // Usamos AssertUnwindSafe para capturar pánicos (crashes de Rust)
+            let result = tokio::time::timeout(
+                limit,
+                AssertUnwindSafe(async {
+                    debug!("🔎 [Supervisor] Ejecutando agente: {}", agent.name());
+                    agent.process(msg).await
+                })
+                .catch_unwind(),
+            )
+            .await;
+
+            let outcome = match result {
+                Err(_) => {
+                    error!("⏱️ [Supervisor] Agente '{}' superó el timeout de {:?}", agent.name(), limit);
+                    BranchOutcome::TimedOut
+                }
+                // El agente terminó "bien" (Ok o Err controlado)
+                Ok(Ok(Ok(response))) => BranchOutcome::Completed(response),
+                Ok(Ok(Err(e))) => {
+                    error!("⚠️ [Supervisor] Agente '{}' reportó error: {}", agent.name(), e);
+                    BranchOutcome::Failed(e.to_string())
+                }
+                // El agente entró en PÁNICO (Crash real)
+                Ok(Err(panic_cause)) => {
+                    let cause_str = if let Some(s) = panic_cause.downcast_ref::<&str>() {
+                        s.to_string()
+                    } else {
+                        "Unknown panic".to_string()
+                    };
+                    error!("🚨 [SUPERVISOR] CRITICAL: Agente '{}' CRASHED! Causa: {}", agent.name(), cause_str);
+                    BranchOutcome::Failed(format!("PANIC: {}", cause_str))
+                }
+            };
+
+            let _ = done_tx.send(BranchDone { id, outcome });
+        });
+    }
+
+    fn on_branch_done(&mut self, done: BranchDone) {
+        let Some((agent, stage, ms)) = self.run.close(done.id, &done.outcome) else {
+            return;
+        };
+        debug!("🧾 Rama '{}' ({:?}) cerrada en {}ms", agent, stage, ms);
+        if stage == Stage::Scrape {
+            self.record_health(&agent, ms, &done.outcome);
+        }
+
+        match done.outcome {
+            BranchOutcome::Completed(response) => self.route(response),
+            BranchOutcome::Failed(e) => {
+                warn!("⚙️ [Orchestrator Logic] Rama '{}' fallida: {}", agent, e);
+            }
+            BranchOutcome::TimedOut => {
+                warn!("⚙️ [Orchestrator Logic] Rama '{}' descartada por timeout", agent);
+            }
+        }
+    }
+
+    /// Anota el resultado de un scraper en el historial de su fuente.
+    fn record_health(&self, agent: &str, ms: u128, outcome: &BranchOutcome) {
+        let Some(source) = self.enabled_source(agent) else { return };
+        let (outcome, jobs, error) = match outcome {
+            BranchOutcome::Completed(AgentMessage::RawJobsScraped(jobs)) if jobs.is_empty() => (AttemptOutcome::Empty, 0, None),
+            BranchOutcome::Completed(AgentMessage::RawJobsScraped(jobs)) => (AttemptOutcome::Ok, jobs.len(), None),
+            BranchOutcome::Completed(_) => (AttemptOutcome::Empty, 0, None),
+            BranchOutcome::Failed(e) => (AttemptOutcome::Failed, 0, Some(e.clone())),
+            BranchOutcome::TimedOut => (AttemptOutcome::TimedOut, 0, Some(format!("timeout ({:?})", self.scrape_timeout))),
+        };
+        let attempt = SourceAttempt {
+            at: Utc::now(),
+            outcome,
+            latency_ms: ms as u64,
+            jobs,
+            error,
+        };
+        if let Some(until) = self.health.record(&source, attempt) {
+            let msg = format!(
+                "⛔ [{}] Demasiados fallos seguidos: la fuente queda en pausa hasta las {} UTC.",
+                agent,
+                until.format("%H:%M")
+            );
+            warn!("{}", msg);
+            self.emit_log("warn", msg);
+        }
+    }
+
+    fn route(&mut self, msg: AgentMessage) {
         match msg {
             AgentMessage::RawJobsScraped(jobs) => {
-                info!("📡 Scraper finalizado. {} ofertas encontradas.", jobs.len());
+                info!(
+                    "📡 Scraper finalizado. {} ofertas encontradas ({} scrapers pendientes).",
+                    jobs.len(),
+                    self.run.pending_in(Stage::Scrape)
+                );
+                let Some(c) = self.current_criteria.clone() else { return };
+
+                // Filtros deterministas: lo que se descarta aquí no paga LLM
+                let (jobs, report) = filters::apply(&c, jobs);
+                if report.total() > 0 {
+                    let msg = format!(
+                        "🧹 Filtros: {} ofertas descartadas antes del análisis ({}).",
+                        report.total(),
+                        report.summary()
+                    );
+                    info!("{}", msg);
+                    self.emit_log("info", msg);
+                }
+
+                // Dedup entre fuentes antes de pagar el análisis LLM
+                let (jobs, merged) = self.dedup.admit(jobs);
+                if merged > 0 {
+                    info!("🧬 Dedup: {} ofertas duplicadas fusionadas con otras fuentes.", merged);
+                }
+
                 // Si jobs viene vacío, NO matamos el flujo, permitimos que otros scrapers sigan.
                 if jobs.is_empty() { return; }
-                
-                if let Some(c) = criteria {
-                    let _ = tx.send(("analyzer".into(), AgentMessage::AnalyzeJobs(jobs, c))).await;
-                }
-            },
+
+                self.dispatch("analyzer", AgentMessage::AnalyzeJobs(jobs, c));
+            }
             AgentMessage::JobsAnalyzed(jobs) => {
                 info!("✨ Análisis completado para {} ofertas.", jobs.len());
-                let _ = tx.send(("enricher".into(), AgentMessage::JobsAnalyzed(jobs))).await;
+                self.dispatch("enricher", AgentMessage::JobsAnalyzed(jobs));
             }
             AgentMessage::JobsEnriched(jobs) => {
-                info!("✅ Proceso completado. Enviando {} resultados.", jobs.len());
-                let _ = res_tx.send(jobs).await;
-                // Señal de finalización del ciclo
-                let _ = tx.send(("__orchestrator__".into(), AgentMessage::Shutdown)).await;
+                info!("📥 Lote enriquecido: {} ofertas acumuladas para el resultado final.", jobs.len());
+                self.run.collect(jobs);
+            }
+            AgentMessage::Error(e) => {
+                warn!("⚙️ [Orchestrator Logic] Error recibido de subsistema: {}", e);
             }
             _ => {}
         }
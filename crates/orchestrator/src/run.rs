@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+use std::time::Instant;
+
+use job_hunter_core::{AgentMessage, AnalyzedJobPosting};
+
+/// Etapa del pipeline a la que pertenece una rama de trabajo.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Stage {
+    Scrape,
+    Analyze,
+    Enrich,
+    Other,
+}
+
+impl Stage {
+    pub(crate) fn of(msg: &AgentMessage) -> Self {
+        match msg {
+            AgentMessage::StartScraping(_) => Stage::Scrape,
+            AgentMessage::AnalyzeJobs(..) => Stage::Analyze,
+            AgentMessage::JobsAnalyzed(_) | AgentMessage::EnrichCompanyInfo(_) => Stage::Enrich,
+            _ => Stage::Other,
+        }
+    }
+}
+
+/// Resultado de una rama (una llamada a `Agent::process`).
+pub(crate) enum BranchOutcome {
+    Completed(AgentMessage),
+    Failed(String),
+    TimedOut,
+}
+
+/// Notificación que envía la tarea supervisora al terminar una rama.
+pub(crate) struct BranchDone {
+    pub id: u64,
+    pub outcome: BranchOutcome,
+}
+
+struct Branch {
+    agent: String,
+    stage: Stage,
+    started: Instant,
+}
+
+/// Estado de una ejecución: ramas en curso, terminadas y resultados acumulados.
+///
+/// El run termina cuando no queda ninguna rama pendiente (completada, fallida o
+/// con timeout), momento en el que se emite un único resultado fusionado.
+#[derive(Default)]
+pub(crate) struct RunTracker {
+    next_id: u64,
+    pending: HashMap<u64, Branch>,
+    completed: usize,
+    failed: Vec<(String, String)>,
+    timed_out: Vec<String>,
+    results: Vec<AnalyzedJobPosting>,
+}
+
+impl RunTracker {
+    /// Registra una nueva rama en curso y devuelve su identificador.
+    pub(crate) fn open(&mut self, agent: &str, stage: Stage) -> u64 {
+        self.next_id += 1;
+        self.pending.insert(
+            self.next_id,
+            Branch {
+                agent: agent.to_string(),
+                stage,
+                started: Instant::now(),
+            },
+        );
+        self.next_id
+    }
+
+    /// Cierra una rama. Devuelve `(agente, etapa, ms)` si la rama estaba pendiente.
+    pub(crate) fn close(&mut self, id: u64, outcome: &BranchOutcome) -> Option<(String, Stage, u128)> {
+        let branch = self.pending.remove(&id)?;
+        match outcome {
+            BranchOutcome::Completed(_) => self.completed += 1,
+            BranchOutcome::Failed(e) => self.failed.push((branch.agent.clone(), e.clone())),
+            BranchOutcome::TimedOut => self.timed_out.push(branch.agent.clone()),
+        }
+        Some((branch.agent, branch.stage, branch.started.elapsed().as_millis()))
+    }
+
+    pub(crate) fn collect(&mut self, jobs: Vec<AnalyzedJobPosting>) {
+        self.results.extend(jobs);
+    }
+
+    pub(crate) fn is_idle(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    pub(crate) fn pending_in(&self, stage: Stage) -> usize {
+        self.pending.values().filter(|b| b.stage == stage).count()
+    }
+
+    pub(crate) fn summary(&self) -> String {
+        format!(
+            "{} ramas OK, {} fallidas, {} con timeout",
+            self.completed,
+            self.failed.len(),
+            self.timed_out.len()
+        )
+    }
+
+    /// Consume los resultados acumulados: sin duplicados (por id) y ordenados por score.
+    pub(crate) fn take_merged(&mut self) -> Vec<AnalyzedJobPosting> {
+        let mut by_id: HashMap<String, AnalyzedJobPosting> = HashMap::new();
+        for job in self.results.drain(..) {
+            match by_id.get(&job.id) {
+                Some(prev) if prev.match_score >= job.match_score => {}
+                _ => {
+                    by_id.insert(job.id.clone(), job);
+                }
+            }
+        }
+
+        let mut merged: Vec<AnalyzedJobPosting> = by_id.into_values().collect();
+        merged.sort_by(|a, b| {
+            b.match_score
+                .partial_cmp(&a.match_score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        merged
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use job_hunter_agents::analyzer::LlmAnalysis;
+    use job_hunter_core::{JobSource, RawJobPosting, SearchCriteria};
+
+    fn job(native_id: &str, score: f32) -> AnalyzedJobPosting {
+        let raw = RawJobPosting::new(JobSource::RemoteOk, Some(native_id), format!("https://remoteok.com/{native_id}"), "");
+        let analysis: LlmAnalysis = serde_json::from_value(serde_json::json!({ "match_score": score })).unwrap();
+        analysis.into_analyzed(&raw, &SearchCriteria::default())
+    }
+
+    #[test]
+    fn tracker_is_idle_only_when_every_branch_closed() {
+        let mut run = RunTracker::default();
+        let scrape = run.open("scraper_a", Stage::Scrape);
+        let analyze = run.open("analyzer", Stage::Analyze);
+        assert_eq!(run.pending_in(Stage::Scrape), 1);
+
+        assert!(run.close(scrape, &BranchOutcome::TimedOut).is_some());
+        assert!(!run.is_idle());
+        // Una rama ya cerrada (o desconocida) no cuenta dos veces
+        assert!(run.close(scrape, &BranchOutcome::Failed("x".into())).is_none());
+        run.close(analyze, &BranchOutcome::Failed("LLM caído".into()));
+        assert!(run.is_idle());
+        assert_eq!(run.summary(), "0 ramas OK, 1 fallidas, 1 con timeout");
+    }
+
+    /// Lotes de varias ramas: una oferta por id (la de mayor score) y orden por score.
+    #[test]
+    fn merged_results_keep_best_score_per_id() {
+        let mut run = RunTracker::default();
+        run.collect(vec![job("1", 0.4), job("2", 0.9)]);
+        run.collect(vec![job("1", 0.7), job("3", 0.1)]);
+
+        let merged = run.take_merged();
+        let scores: Vec<f32> = merged.iter().map(|j| j.match_score).collect();
+        assert_eq!(scores, [0.9, 0.7, 0.1]);
+        assert!(run.take_merged().is_empty());
+    }
+}
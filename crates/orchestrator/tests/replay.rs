@@ -0,0 +1,74 @@
+//! Pipeline completo sin red: scraper, analyzer (Ollama) y enricher reales
+//! sobre respuestas grabadas, de `StartScraping` a `JobsEnriched`.
+//!
+//! Los fixtures están en `tests/fixtures/<host>/` con el mismo formato que los
+//! de `job-hunter-agents`. Las peticiones al LLM solo se reproducen si el
+//! cuerpo coincide byte a byte: si cambia el prompt hay que regrabarlas con
+//! `JOB_HUNTER_HTTP_MODE=record` contra un Ollama local.
+
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use job_hunter_agents::http::{HttpTape, TapeMode};
+use job_hunter_agents::{AnalyzerAgent, EnricherAgent, SourceRegistry};
+use job_hunter_core::*;
+use job_hunter_orchestrator::Orchestrator;
+
+const OLLAMA: &str = "http://localhost:11434";
+
+/// Solo Remotive, una página y sin esperas ni reintentos.
+fn criteria() -> SearchCriteria {
+    let mut cfg = SourceSettings::defaults(JobSource::Remotive);
+    cfg.delay_ms = 0;
+    cfg.max_pages = 1;
+    cfg.max_results = 50;
+    cfg.max_retries = 0;
+    SearchCriteria {
+        sources_config: vec![cfg],
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn pipeline_replays_scraper_and_llm_fixtures() {
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    HttpTape::install(Some(HttpTape::new(TapeMode::Replay, fixtures)));
+
+    let (orch, mut results) = Orchestrator::new();
+    let mut orch = orch.with_timeouts(Duration::from_secs(5), Duration::from_secs(5));
+    orch.register_agent(SourceRegistry::builtin().agent(&JobSource::Remotive).expect("fuente registrada"));
+    orch.register_agent(Arc::new(AnalyzerAgent::new_local(OLLAMA.into(), "llama3".into())));
+    orch.register_agent(Arc::new(EnricherAgent::new()));
+    orch.start_search(criteria()).await.unwrap();
+
+    tokio::time::timeout(Duration::from_secs(10), orch.run())
+        .await
+        .expect("el run no termina")
+        .unwrap();
+    let mut jobs = results.recv().await.expect("sin resultado");
+    jobs.sort_by(|a, b| a.title.cmp(&b.title));
+
+    // Un fallo del LLM descarta la oferta, así que ambas tienen que llegar
+    assert_eq!(jobs.len(), 2, "{:#?}", jobs.iter().map(|j| &j.url).collect::<Vec<_>>());
+
+    let marketer = &jobs[0];
+    assert_eq!(marketer.title, "Growth Marketer");
+    assert_eq!(marketer.match_score, 0.2);
+    assert_eq!(marketer.red_flags, ["Marketing role, not engineering"]);
+
+    let elixir = &jobs[1];
+    assert_eq!(elixir.title, "Senior Elixir Engineer");
+    assert_eq!(elixir.match_score, 0.85);
+    assert_eq!(elixir.match_reasons, ["Elixir and Phoenix experience"]);
+    assert_eq!(elixir.skills_analysis.matching, ["elixir"]);
+    let salary = elixir.salary_range.as_ref().expect("salario de la fuente");
+    assert_eq!((salary.min, salary.max, salary.currency.as_str()), (70_000, 90_000, "EUR"));
+
+    // Datos de la fuente (no del modelo) y empresa enriquecida
+    let company = elixir.company.as_ref().expect("empresa");
+    assert_eq!(company.name, "Sage Robotics");
+    assert_eq!(company.website.as_deref(), Some("https://www.sagerobotics.com"));
+    assert_eq!(elixir.sources.len(), 1);
+    assert_eq!(elixir.sources[0].source, JobSource::Remotive);
+}
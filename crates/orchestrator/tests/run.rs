@@ -0,0 +1,156 @@
+//! Ejecución completa del orquestador con agentes de prueba: el run espera a
+//! todas las ramas (scrapers lentos, caídos o colgados) y fusiona los lotes.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use job_hunter_agents::analyzer::LlmAnalysis;
+use job_hunter_core::*;
+use job_hunter_orchestrator::Orchestrator;
+
+enum Behavior {
+    Jobs(usize),
+    Panic,
+    Hang,
+}
+
+/// Scraper de una fuente `Custom(name)` que tarda `delay` y luego se comporta según `behavior`.
+struct StubScraper {
+    name: String,
+    source: JobSource,
+    delay: Duration,
+    behavior: Behavior,
+}
+
+impl StubScraper {
+    fn agent(name: &str, delay_ms: u64, behavior: Behavior) -> Arc<dyn Agent> {
+        Arc::new(Self {
+            name: format!("scraper_{name}"),
+            source: JobSource::Custom(name.to_string()),
+            delay: Duration::from_millis(delay_ms),
+            behavior,
+        })
+    }
+}
+
+#[async_trait]
+impl Agent for StubScraper {
+    async fn process(&self, _msg: AgentMessage) -> Result<AgentMessage, AgentError> {
+        tokio::time::sleep(self.delay).await;
+        match self.behavior {
+            Behavior::Jobs(n) => Ok(AgentMessage::RawJobsScraped(
+                (0..n)
+                    .map(|i| {
+                        let slug = self.source.slug();
+                        RawJobPosting::new(self.source.clone(), Some(&i.to_string()), format!("https://{slug}.test/jobs/{i}"), "Rust")
+                            .with_details(Some(format!("{slug} role {i}")), Some(format!("{slug} company {i}")), None)
+                    })
+                    .collect(),
+            )),
+            Behavior::Panic => panic!("scraper roto"),
+            Behavior::Hang => {
+                tokio::time::sleep(Duration::from_secs(3600)).await;
+                Ok(AgentMessage::RawJobsScraped(vec![]))
+            }
+        }
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn source(&self) -> Option<JobSource> {
+        Some(self.source.clone())
+    }
+}
+
+/// Analyzer sin LLM: puntúa cada oferta con 0.5.
+struct StubAnalyzer;
+
+#[async_trait]
+impl Agent for StubAnalyzer {
+    async fn process(&self, msg: AgentMessage) -> Result<AgentMessage, AgentError> {
+        let AgentMessage::AnalyzeJobs(jobs, criteria) = msg else {
+            return Err(AgentError::Analysis("Msg inválido".into()));
+        };
+        let analyzed = jobs
+            .iter()
+            .map(|raw| {
+                let analysis: LlmAnalysis = serde_json::from_value(serde_json::json!({ "match_score": 0.5 })).unwrap();
+                analysis.into_analyzed(raw, &criteria)
+            })
+            .collect();
+        Ok(AgentMessage::JobsAnalyzed(analyzed))
+    }
+
+    fn name(&self) -> &str {
+        "analyzer"
+    }
+}
+
+struct StubEnricher;
+
+#[async_trait]
+impl Agent for StubEnricher {
+    async fn process(&self, msg: AgentMessage) -> Result<AgentMessage, AgentError> {
+        match msg {
+            AgentMessage::JobsAnalyzed(jobs) => Ok(AgentMessage::JobsEnriched(jobs)),
+            _ => Err(AgentError::Enrichment("Msg inválido".into())),
+        }
+    }
+
+    fn name(&self) -> &str {
+        "enricher"
+    }
+}
+
+/// Ejecuta un run con `scrapers` y devuelve el único resultado emitido.
+async fn run(scrapers: Vec<Arc<dyn Agent>>) -> Vec<AnalyzedJobPosting> {
+    let (orch, mut results) = Orchestrator::new();
+    let mut orch = orch.with_timeouts(Duration::from_millis(500), Duration::from_secs(5));
+    for agent in scrapers {
+        orch.register_agent(agent);
+    }
+    orch.register_agent(Arc::new(StubAnalyzer));
+    orch.register_agent(Arc::new(StubEnricher));
+    orch.start_search(SearchCriteria::default()).await.unwrap();
+
+    tokio::time::timeout(Duration::from_secs(10), orch.run())
+        .await
+        .expect("el run no termina")
+        .unwrap();
+    let merged = results.recv().await.expect("sin resultado");
+    assert!(results.try_recv().is_err(), "más de un resultado por run");
+    merged
+}
+
+#[tokio::test]
+async fn run_waits_for_every_branch_and_merges_batches() {
+    let merged = run(vec![
+        StubScraper::agent("run_fast", 0, Behavior::Jobs(2)),
+        StubScraper::agent("run_slow", 300, Behavior::Jobs(3)),
+        StubScraper::agent("run_panic", 0, Behavior::Panic),
+        StubScraper::agent("run_hang", 0, Behavior::Hang),
+    ])
+    .await;
+
+    // Lotes de las dos fuentes buenas, también el del scraper lento
+    assert_eq!(merged.len(), 5);
+    for source in ["run_fast", "run_slow"] {
+        assert!(
+            merged.iter().any(|j| j.sources.iter().any(|s| s.source == JobSource::Custom(source.into()))),
+            "{source}"
+        );
+    }
+}
+
+/// Sin ramas pendientes el run termina enseguida, con un resultado vacío.
+#[tokio::test]
+async fn run_ends_when_tracker_is_idle() {
+    let merged = run(vec![StubScraper::agent("idle_empty", 0, Behavior::Jobs(0))]).await;
+    assert!(merged.is_empty());
+
+    let merged = run(vec![StubScraper::agent("idle_panic", 0, Behavior::Panic)]).await;
+    assert!(merged.is_empty());
+}
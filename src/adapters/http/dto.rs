@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use uuid::Uuid;
@@ -59,6 +60,25 @@ pub struct CriteriaV1 {
     pub sources_config: Vec<SourceSettingsV1>,
     #[serde(default)]
     pub user_cv: Option<String>,
+    #[serde(default = "default_target_currency")]
+    pub target_currency: String,
+    #[serde(default)]
+    pub max_age_days: Option<u32>,
+    #[serde(default)]
+    pub filters: SearchFiltersV1,
+}
+
+/// Filtros deterministas previos al LLM (todos opcionales).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SearchFiltersV1 {
+    pub exclude_keywords: Vec<String>,
+    pub required_keywords: Vec<String>,
+    pub min_salary: Option<u32>,
+    pub job_types: Vec<ApiJobType>,
+    pub remote_only: bool,
+    pub locations: Vec<String>,
+    pub company_blocklist: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -71,6 +91,23 @@ pub struct SourceSettingsV1 {
     pub user_agent: String,
     #[serde(default)]
     pub use_proxy: bool,
+    #[serde(default = "default_max_results")]
+    pub max_results: u32,
+    #[serde(default = "default_max_pages")]
+    pub max_pages: u32,
+    #[serde(default)]
+    pub ignore_robots: bool,
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    #[serde(default = "default_retry_backoff_ms")]
+    pub retry_backoff_ms: u64,
+    #[serde(default = "default_cache_max_age_secs")]
+    pub cache_max_age_secs: u64,
+    #[serde(default)]
+    pub fetch_details: bool,
+    /// Watchlist de empresas (`slug` o `slug:Nombre`) para las fuentes ATS.
+    #[serde(default)]
+    pub companies: Vec<String>,
 }
 
 fn default_delay_ms() -> u64 {
@@ -79,6 +116,24 @@ fn default_delay_ms() -> u64 {
 fn default_user_agent() -> String {
     "Mozilla/5.0".to_string()
 }
+fn default_max_results() -> u32 {
+    15
+}
+fn default_max_pages() -> u32 {
+    2
+}
+fn default_max_retries() -> u32 {
+    2
+}
+fn default_retry_backoff_ms() -> u64 {
+    500
+}
+fn default_cache_max_age_secs() -> u64 {
+    900
+}
+fn default_target_currency() -> String {
+    "USD".to_string()
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -91,6 +146,16 @@ pub enum ApiExperienceLevel {
     Any,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiJobType {
+    FullTime,
+    PartTime,
+    Contract,
+    Freelance,
+    Internship,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum ApiJobSource {
@@ -112,6 +177,50 @@ pub enum ApiJobSource {
     DevItJobs,
     PythonOrg,
     GolangProjects,
+
+    // --- BOARDS ATS (watchlist de empresas) ---
+    Greenhouse,
+    Lever,
+    Ashby,
+    Workable,
+}
+
+//
+// V1: Salud de fuentes
+//
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourcesHealthResponseV1 {
+    pub sources: Vec<SourceHealthV1>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourceHealthV1 {
+    /// Mismo identificador que `ApiJobSource` (o el nombre de la fuente personalizada).
+    pub source: String,
+    /// `closed` | `open` | `half_open`
+    pub state: String,
+    pub consecutive_failures: u32,
+    /// Ejecuciones seguidas terminadas sin ofertas.
+    pub consecutive_empty: u32,
+    pub open_until: Option<DateTime<Utc>>,
+    pub last_success_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub success_rate: f32,
+    pub avg_latency_ms: u64,
+    pub avg_jobs: f32,
+    pub recent: Vec<SourceAttemptV1>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourceAttemptV1 {
+    pub at: DateTime<Utc>,
+    /// `ok` | `empty` | `failed` | `timed_out`
+    pub outcome: String,
+    pub latency_ms: u64,
+    pub jobs: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
 }
 
 //
@@ -137,21 +246,16 @@ pub enum ApiLlmProvider {
     Anthropic,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum ApiUseCase {
     Fast,
+    #[default]
     Balanced,
     Deep,
     LongContext,
 }
 
-impl Default for ApiUseCase {
-    fn default() -> Self {
-        Self::Balanced
-    }
-}
-
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LlmLocalV1 {
     pub endpoint: String,
@@ -14,7 +14,7 @@ use job_hunter_agents::{
     get_all_scrapers, AnalyzerAgent, EnricherAgent, UseCase
 };
 use job_hunter_core::*;
-use job_hunter_orchestrator::Orchestrator;
+use job_hunter_orchestrator::{AttemptOutcome, CircuitState, Orchestrator, SourceHealthRegistry};
 
 use super::{
     dto::*,
@@ -35,6 +35,7 @@ pub struct AppState {
 impl AppState {
     pub fn new(cfg: AppStateConfig) -> anyhow::Result<Self> {
         let (ws_tx, _) = broadcast::channel(256);
+        job_hunter_agents::http::set_ws_tx(ws_tx.clone());
         let http_client = reqwest::Client::builder()
             .timeout(std::time::Duration::from_secs(25))
             .build()?;
@@ -119,7 +120,8 @@ async fn run_search_from_v1(
     req: StartSearchRequestV1,
     run_id: Uuid,
 ) -> anyhow::Result<()> {
-    let (mut orch, mut result_rx) = Orchestrator::new();
+    let (orch, mut result_rx) = Orchestrator::new();
+    let mut orch = orch.with_ws_tx(state.ws_tx.clone());
 
     // 1. REGISTRO AUTOMÁTICO DE SCRAPERS
     // Usamos la fábrica centralizada para cargar todos los scrapers disponibles
@@ -149,9 +151,20 @@ async fn run_search_from_v1(
                 delay_ms: s.delay_ms,
                 user_agent: s.user_agent.clone(),
                 use_proxy: s.use_proxy,
+                max_results: s.max_results.max(1),
+                max_pages: s.max_pages.max(1),
+                ignore_robots: s.ignore_robots,
+                max_retries: s.max_retries,
+                retry_backoff_ms: s.retry_backoff_ms,
+                cache_max_age_secs: s.cache_max_age_secs,
+                fetch_details: s.fetch_details,
+                companies: clean_companies(&s.companies),
             })
             .collect(),
         user_cv: req.criteria.user_cv.clone(),
+        target_currency: req.criteria.target_currency.trim().to_uppercase(),
+        max_age_days: req.criteria.max_age_days.filter(|d| *d > 0),
+        filters: map_filters(&req.criteria.filters),
     };
 
     send_log(
@@ -173,11 +186,14 @@ async fn run_search_from_v1(
             "info",
             format!("run_id={} finalizado. {} resultados", run_id, results.len()),
         );
+        let _ = state
+            .ws_tx
+            .send(serde_json::to_string(&WsEvent::Status("done".into())).unwrap_or_default());
 
         for job in results.iter().take(200) {
             let _ = state
                 .ws_tx
-                .send(serde_json::to_string(&WsEvent::JobFound(job.clone())).unwrap_or_default());
+                .send(serde_json::to_string(&WsEvent::JobFound(Box::new(job.clone()))).unwrap_or_default());
         }
     } else {
         send_log(
@@ -185,6 +201,9 @@ async fn run_search_from_v1(
             "warn",
             format!("run_id={} finalizado sin resultados (o timeout)", run_id),
         );
+        let _ = state
+            .ws_tx
+            .send(serde_json::to_string(&WsEvent::Status("done_empty".into())).unwrap_or_default());
     }
 
     // Asegura que el task del orquestador finalice
@@ -272,6 +291,31 @@ fn map_experience(level: ApiExperienceLevel) -> ExperienceLevel {
     }
 }
 
+fn map_job_type(t: &ApiJobType) -> JobType {
+    match t {
+        ApiJobType::FullTime => JobType::FullTime,
+        ApiJobType::PartTime => JobType::PartTime,
+        ApiJobType::Contract => JobType::Contract,
+        ApiJobType::Freelance => JobType::Freelance,
+        ApiJobType::Internship => JobType::Internship,
+    }
+}
+
+fn map_filters(f: &SearchFiltersV1) -> SearchFilters {
+    let terms = |v: &[String]| -> Vec<String> {
+        v.iter().map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect()
+    };
+    SearchFilters {
+        exclude_keywords: terms(&f.exclude_keywords),
+        required_keywords: terms(&f.required_keywords),
+        min_salary: f.min_salary.filter(|m| *m > 0),
+        job_types: f.job_types.iter().map(map_job_type).collect(),
+        remote_only: f.remote_only,
+        locations: terms(&f.locations),
+        company_blocklist: terms(&f.company_blocklist),
+    }
+}
+
 // Mapeo exhaustivo de fuentes para incluir los nuevos scrapers
 fn map_source(s: ApiJobSource) -> JobSource {
     match s {
@@ -292,9 +336,73 @@ fn map_source(s: ApiJobSource) -> JobSource {
         ApiJobSource::DevItJobs => JobSource::DevItJobs,
         ApiJobSource::PythonOrg => JobSource::PythonOrg,
         ApiJobSource::GolangProjects => JobSource::GolangProjects,
+        // Boards ATS
+        ApiJobSource::Greenhouse => JobSource::Greenhouse,
+        ApiJobSource::Lever => JobSource::Lever,
+        ApiJobSource::Ashby => JobSource::Ashby,
+        ApiJobSource::Workable => JobSource::Workable,
     }
 }
 
+/// Entradas de la watchlist sin espacios ni vacías.
+fn clean_companies(companies: &[String]) -> Vec<String> {
+    companies
+        .iter()
+        .map(|c| c.trim().to_string())
+        .filter(|c| !c.is_empty())
+        .collect()
+}
+
+//
+// Salud de fuentes (V1)
+//
+
+pub async fn sources_health_v1() -> Result<Json<ApiOk<SourcesHealthResponseV1>>, ApiError> {
+    let sources = SourceHealthRegistry::global()
+        .snapshot()
+        .into_iter()
+        .map(|h| SourceHealthV1 {
+            source: h.source.slug().to_string(),
+            state: match h.state {
+                CircuitState::Closed => "closed",
+                CircuitState::Open => "open",
+                CircuitState::HalfOpen => "half_open",
+            }
+            .to_string(),
+            consecutive_failures: h.consecutive_failures,
+            consecutive_empty: h.consecutive_empty,
+            open_until: h.open_until,
+            last_success_at: h.last_success_at,
+            last_error: h.last_error,
+            success_rate: h.success_rate,
+            avg_latency_ms: h.avg_latency_ms,
+            avg_jobs: h.avg_jobs,
+            recent: h
+                .recent
+                .into_iter()
+                .map(|a| SourceAttemptV1 {
+                    at: a.at,
+                    outcome: match a.outcome {
+                        AttemptOutcome::Ok => "ok",
+                        AttemptOutcome::Empty => "empty",
+                        AttemptOutcome::Failed => "failed",
+                        AttemptOutcome::TimedOut => "timed_out",
+                    }
+                    .to_string(),
+                    latency_ms: a.latency_ms,
+                    jobs: a.jobs,
+                    error: a.error,
+                })
+                .collect(),
+        })
+        .collect();
+
+    Ok(Json(ApiOk {
+        ok: true,
+        data: SourcesHealthResponseV1 { sources },
+    }))
+}
+
 //
 // Extract CV (V1 tipado + IA)
 //
@@ -586,7 +694,8 @@ pub async fn start_search_legacy(
     let tx = state.ws_tx.clone();
 
     tokio::spawn(async move {
-        let (mut orch, mut result_rx) = Orchestrator::new();
+        let (orch, mut result_rx) = Orchestrator::new();
+        let mut orch = orch.with_ws_tx(tx.clone());
 
         // Carga automática de TODOS los scrapers (Legacy también se beneficia)
         let all_scrapers = get_all_scrapers();
@@ -696,6 +805,19 @@ pub async fn start_search_legacy(
                     .and_then(|v| v.as_bool())
                     .unwrap_or(false);
 
+                let max_results = s.get("max_results").and_then(|v| v.as_u64()).unwrap_or(15).max(1) as u32;
+                let max_pages = s.get("max_pages").and_then(|v| v.as_u64()).unwrap_or(2).max(1) as u32;
+                let ignore_robots = s.get("ignore_robots").and_then(|v| v.as_bool()).unwrap_or(false);
+                let max_retries = s.get("max_retries").and_then(|v| v.as_u64()).unwrap_or(2) as u32;
+                let retry_backoff_ms = s.get("retry_backoff_ms").and_then(|v| v.as_u64()).unwrap_or(500);
+                let cache_max_age_secs = s.get("cache_max_age_secs").and_then(|v| v.as_u64()).unwrap_or(900);
+                let fetch_details = s.get("fetch_details").and_then(|v| v.as_bool()).unwrap_or(false);
+                let companies: Vec<String> = s
+                    .get("companies")
+                    .and_then(|v| v.as_array())
+                    .map(|a| a.iter().filter_map(|c| c.as_str().map(String::from)).collect())
+                    .unwrap_or_default();
+
                 let source = match source {
                     "remote_ok" => JobSource::RemoteOk,
                     "arbeitnow" => JobSource::Arbeitnow,
@@ -712,6 +834,14 @@ pub async fn start_search_legacy(
                     delay_ms,
                     user_agent,
                     use_proxy,
+                    max_results,
+                    max_pages,
+                    ignore_robots,
+                    max_retries,
+                    retry_backoff_ms,
+                    cache_max_age_secs,
+                    fetch_details,
+                    companies: clean_companies(&companies),
                 })
             })
             .collect();
@@ -721,11 +851,32 @@ pub async fn start_search_legacy(
             .and_then(|v| v.as_str())
             .map(|s| s.to_string());
 
+        let target_currency = criteria
+            .get("target_currency")
+            .and_then(|v| v.as_str())
+            .unwrap_or("USD")
+            .to_uppercase();
+
+        let max_age_days = criteria
+            .get("max_age_days")
+            .and_then(|v| v.as_u64())
+            .filter(|d| *d > 0)
+            .map(|d| d as u32);
+
+        let filters: SearchFilters = criteria
+            .get("filters")
+            .cloned()
+            .and_then(|v| serde_json::from_value(v).ok())
+            .unwrap_or_default();
+
         let criteria = SearchCriteria {
             keywords,
             experience_level,
             sources_config,
             user_cv,
+            target_currency,
+            max_age_days,
+            filters,
         };
 
         let _ = tx.send(serde_json::json!({"type":"status","payload":"starting"}).to_string());
@@ -29,6 +29,7 @@ pub fn router(state: Arc<AppState>) -> Router {
         .route("/api/v1/cv/extract", post(handlers::extract_cv_v1))
         .route("/api/v1/models/ollama", get(handlers::ollama_models_v1))
         .route("/api/v1/models/cloud", post(handlers::cloud_models_v1))
+        .route("/api/v1/sources/health", get(handlers::sources_health_v1))
         // OpenAPI
         .route("/api-docs/openapi.json", get(openapi::openapi_json))
         .route("/docs", get(openapi::docs_page))
@@ -120,6 +120,21 @@ pub async fn openapi_json() -> impl IntoResponse {
             }
           }
         },
+        "/api/v1/sources/health": {
+          "get": {
+            "summary": "Per-source health history and circuit breaker state",
+            "responses": {
+              "200": {
+                "description": "Sources health",
+                "content": {
+                  "application/json": {
+                    "schema": { "$ref": "#/components/schemas/ApiOkSourcesHealthResponseV1" }
+                  }
+                }
+              }
+            }
+          }
+        },
 
         "/ws": {
           "get": {
@@ -163,9 +178,25 @@ pub async fn openapi_json() -> impl IntoResponse {
             "type": "string",
             "enum": ["entry","junior","mid","senior","lead","any"]
           },
+          "ApiJobType": {
+            "type": "string",
+            "enum": ["full_time","part_time","contract","freelance","internship"]
+          },
+          "SearchFiltersV1": {
+            "type": "object",
+            "properties": {
+              "exclude_keywords": { "type": "array", "items": { "type": "string" } },
+              "required_keywords": { "type": "array", "items": { "type": "string" } },
+              "min_salary": { "type": "integer", "format": "int32", "nullable": true, "description": "Salario anual mínimo en target_currency" },
+              "job_types": { "type": "array", "items": { "$ref": "#/components/schemas/ApiJobType" } },
+              "remote_only": { "type": "boolean" },
+              "locations": { "type": "array", "items": { "type": "string" }, "description": "Países o regiones aceptados" },
+              "company_blocklist": { "type": "array", "items": { "type": "string" } }
+            }
+          },
           "ApiJobSource": {
             "type": "string",
-            "enum": ["remoteok","wwr","arbeitnow","himalayas","jobspresso"]
+            "enum": ["remoteok","wwr","arbeitnow","himalayas","jobspresso","remotive","jobicy","find_work","working_nomads","vue_jobs","crypto_jobs","remote_co","dev_it_jobs","python_org","golang_projects","greenhouse","lever","ashby","workable"]
           },
           "ApiLlmProvider": {
             "type": "string",
@@ -182,7 +213,15 @@ pub async fn openapi_json() -> impl IntoResponse {
               "enabled": { "type": "boolean" },
               "delay_ms": { "type": "integer", "format": "int64" },
               "user_agent": { "type": "string" },
-              "use_proxy": { "type": "boolean" }
+              "use_proxy": { "type": "boolean" },
+              "max_results": { "type": "integer", "format": "int32", "description": "Máximo de ofertas por fuente (default 15)" },
+              "max_pages": { "type": "integer", "format": "int32", "description": "Máximo de páginas por consulta (default 2)" },
+              "ignore_robots": { "type": "boolean", "description": "No comprobar robots.txt en scrapers HTML (se registra en el log)" },
+              "max_retries": { "type": "integer", "format": "int32", "description": "Reintentos ante 429/5xx o errores de red (default 2)" },
+              "retry_backoff_ms": { "type": "integer", "format": "int64", "description": "Espera base del backoff exponencial con jitter (default 500)" },
+              "cache_max_age_secs": { "type": "integer", "format": "int64", "description": "Segundos que se reutiliza una respuesta cacheada sin revalidar; 0 = revalidar siempre (default 900)" },
+              "fetch_details": { "type": "boolean", "description": "Descargar la página de detalle de las ofertas con contenido corto (teasers de feeds RSS/Atom; default false)" },
+              "companies": { "type": "array", "items": { "type": "string" }, "description": "Watchlist de empresas para greenhouse/lever/ashby/workable: slug del board o `slug:Nombre`" }
             },
             "required": ["source","enabled"]
           },
@@ -192,7 +231,10 @@ pub async fn openapi_json() -> impl IntoResponse {
               "keywords": { "type": "array", "items": { "type": "string" } },
               "experience_level": { "$ref": "#/components/schemas/ApiExperienceLevel" },
               "sources_config": { "type": "array", "items": { "$ref": "#/components/schemas/SourceSettingsV1" } },
-              "user_cv": { "type": "string", "nullable": true }
+              "user_cv": { "type": "string", "nullable": true },
+              "target_currency": { "type": "string", "description": "ISO 4217 para normalizar salarios anuales (default USD)" },
+              "max_age_days": { "type": "integer", "format": "int32", "nullable": true, "description": "Descarta ofertas publicadas hace más de N días" },
+              "filters": { "$ref": "#/components/schemas/SearchFiltersV1" }
             },
             "required": ["keywords","experience_level","sources_config"]
           },
@@ -305,6 +347,49 @@ pub async fn openapi_json() -> impl IntoResponse {
               "data": { "$ref": "#/components/schemas/CloudModelsResponseV1" }
             },
             "required": ["ok","data"]
+          },
+
+          "SourceAttemptV1": {
+            "type": "object",
+            "properties": {
+              "at": { "type": "string", "format": "date-time" },
+              "outcome": { "type": "string", "enum": ["ok","failed","timed_out"] },
+              "latency_ms": { "type": "integer", "minimum": 0 },
+              "jobs": { "type": "integer", "minimum": 0 },
+              "error": { "type": "string" }
+            },
+            "required": ["at","outcome","latency_ms","jobs"]
+          },
+          "SourceHealthV1": {
+            "type": "object",
+            "properties": {
+              "source": { "type": "string" },
+              "state": { "type": "string", "enum": ["closed","open","half_open"] },
+              "consecutive_failures": { "type": "integer", "minimum": 0 },
+              "open_until": { "type": "string", "format": "date-time", "nullable": true },
+              "last_success_at": { "type": "string", "format": "date-time", "nullable": true },
+              "last_error": { "type": "string", "nullable": true },
+              "success_rate": { "type": "number" },
+              "avg_latency_ms": { "type": "integer", "minimum": 0 },
+              "avg_jobs": { "type": "number" },
+              "recent": { "type": "array", "items": { "$ref": "#/components/schemas/SourceAttemptV1" } }
+            },
+            "required": ["source","state","consecutive_failures","success_rate","avg_latency_ms","avg_jobs","recent"]
+          },
+          "SourcesHealthResponseV1": {
+            "type": "object",
+            "properties": {
+              "sources": { "type": "array", "items": { "$ref": "#/components/schemas/SourceHealthV1" } }
+            },
+            "required": ["sources"]
+          },
+          "ApiOkSourcesHealthResponseV1": {
+            "type": "object",
+            "properties": {
+              "ok": { "type": "boolean" },
+              "data": { "$ref": "#/components/schemas/SourcesHealthResponseV1" }
+            },
+            "required": ["ok","data"]
           }
         }
       }
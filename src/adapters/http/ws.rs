@@ -20,7 +20,7 @@ pub enum WsEvent {
     Status(String),
 
     #[serde(rename = "job_found")]
-    JobFound(job_hunter_core::AnalyzedJobPosting),
+    JobFound(Box<job_hunter_core::AnalyzedJobPosting>),
 }
 
 pub fn send_log(state: &AppState, level: &str, msg: impl Into<String>) {
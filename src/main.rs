@@ -1,3 +1,6 @@
+// El spec OpenAPI de `openapi.rs` es un único `json!` grande
+#![recursion_limit = "256"]
+
 mod adapters;
 
 use std::{net::SocketAddr, sync::Arc};
@@ -80,6 +80,12 @@ export async function listCloudModels(provider, apiKey, baseUrl) {
   return apiJson("/api/llm/models", { provider, api_key: apiKey, base_url: baseUrl });
 }
 
+export async function getSourcesHealth() {
+  const res = await fetch("/api/v1/sources/health", { method: "GET" });
+  const json = await res.json().catch(() => ({}));
+  return json?.ok ? (json.data?.sources || []) : [];
+}
+
 export async function pingBackend() {
   const res = await fetch("/api/v1/health", { method: "GET" });
   return { ok: res.ok, status: res.status };
@@ -3,7 +3,7 @@ import { $, providerDefaults } from "./utils.js";
 import { connectWs } from "./ws.js";
 import { uploadCv, startSearchV1, startSearchLegacy, listOllamaModels, listCloudModels, pingBackend } from "./api.js";
 import {
-  renderSources, buildSourceConfigs, addLog, clearUi,
+  renderSources, sourceKeyToV1, refreshSourceHealth, buildSourceConfigs, addLog, clearUi,
   setProviderUi, renderCloudModelSelect, setCloudError,
   showToast, setupTabs, setBackendStatus, setLastEvent,
   renderCvKeywords, exportResults // <-- Importamos la nueva función
@@ -55,13 +55,17 @@ async function extractCv() { /* ... igual que antes ... */
 
 function splitKeywords(s) { return String(s || "").split(",").map(x => x.trim()).filter(Boolean); }
 function mapExperience(exp) { const e = String(exp || "").toLowerCase(); if (["entry","junior","senior","lead","any"].includes(e)) return e; return "mid"; }
-function mapSourceKeyToV1(k) {
-  const map = {
-    "remoteok":"remoteok", "wwr":"wwr", "arbeitnow":"arbeitnow", "himalayas":"himalayas", "jobspresso":"jobspresso",
-    "remotive":"remotive", "jobicy":"jobicy", "findwork":"find_work", "workingnomads":"working_nomads", "vuejobs":"vue_jobs",
-    "cryptojobs":"crypto_jobs", "devitjobs":"dev_it_jobs", "golangprojects":"golang_projects", "pythonorg":"python_org", "remoteco":"remote_co"
+function buildFilters() {
+  const minSalary = Number($("filterMinSalary")?.value);
+  return {
+    exclude_keywords: splitKeywords($("filterExclude")?.value),
+    required_keywords: splitKeywords($("filterRequired")?.value),
+    min_salary: minSalary > 0 ? minSalary : null,
+    job_types: Array.from($("filterJobTypes")?.selectedOptions || []).map(o => o.value),
+    remote_only: Boolean($("filterRemoteOnly")?.checked),
+    locations: splitKeywords($("filterLocations")?.value),
+    company_blocklist: splitKeywords($("filterCompanies")?.value),
   };
-  return map[k] || "remoteok";
 }
 
 function buildStartPayloadV1() {
@@ -69,11 +73,19 @@ function buildStartPayloadV1() {
   const legacySources = buildSourceConfigs(); // <-- Esto ahora lee el Delay global
   
   const sources_config = Object.entries(legacySources || {}).map(([k,v]) => ({
-    source: mapSourceKeyToV1(k),
+    source: sourceKeyToV1(k),
     enabled: Boolean(v?.enabled),
     delay_ms: Number(v?.delay_ms || 1200), // <-- Se envía al backend
     user_agent: String(v?.user_agent || "Mozilla/5.0"),
     use_proxy: Boolean(v?.use_proxy),
+    max_results: Number(v?.max_results || 15),
+    max_pages: Number(v?.max_pages || 2),
+    ignore_robots: Boolean(v?.ignore_robots),
+    max_retries: Number(v?.max_retries ?? 2),
+    retry_backoff_ms: Number(v?.retry_backoff_ms || 500),
+    cache_max_age_secs: Number(v?.cache_max_age_secs ?? 900),
+    fetch_details: Boolean(v?.fetch_details),
+    companies: Array.isArray(v?.companies) ? v.companies : [],
   }));
 
   const criteria = {
@@ -81,6 +93,9 @@ function buildStartPayloadV1() {
     experience_level: mapExperience($("experience").value),
     sources_config,
     user_cv: $("cvText").value || null,
+    target_currency: $("targetCurrency")?.value || "USD",
+    max_age_days: Number($("maxAgeDays")?.value) > 0 ? Number($("maxAgeDays").value) : null,
+    filters: buildFilters(),
   };
 
   const llm = {
@@ -142,7 +157,7 @@ function wireEvents() {
 
 function bootstrap() {
   console.log("Iniciando Job Hunter UI...");
-  setupTabs(); renderSources();
+  setupTabs(); renderSources(); refreshSourceHealth();
   const providerSel = $("llmProvider"); if(providerSel) setProviderUi(providerSel.value);
   connectWs(); refreshLocalModels(); doPing(); wireEvents();
 }
@@ -1,25 +1,36 @@
 import { state } from "./state.js";
 import { $, escapeHtml, escapeAttr, clamp01, providerDefaults, modelTags } from "./utils.js";
+import { getSourcesHealth } from "./api.js";
 
 // --- CONSTANTES ---
+// `v1`: identificador de la fuente en la API v1 (ApiJobSource)
 const SOURCES = [
-  { id: "remoteok", name: "RemoteOK" },
-  { id: "wwr", name: "We Work Remotely" },
-  { id: "arbeitnow", name: "Arbeitnow" },
-  { id: "himalayas", name: "Himalayas" },
-  { id: "jobspresso", name: "Jobspresso" },
-  { id: "remotive", name: "Remotive" },
-  { id: "jobicy", name: "Jobicy" },
-  { id: "findwork", name: "FindWork" },
-  { id: "workingnomads", name: "Working Nomads" },
-  { id: "vuejobs", name: "VueJobs" },
-  { id: "cryptojobs", name: "CryptoJobs" },
-  { id: "devitjobs", name: "DevITJobs" },
-  { id: "golangprojects", name: "Golang Projects" },
-  { id: "pythonorg", name: "Python.org" },
-  { id: "remoteco", name: "Remote.co" },
+  { id: "remoteok", v1: "remoteok", name: "RemoteOK" },
+  { id: "wwr", v1: "wwr", name: "We Work Remotely" },
+  { id: "arbeitnow", v1: "arbeitnow", name: "Arbeitnow" },
+  { id: "himalayas", v1: "himalayas", name: "Himalayas" },
+  { id: "jobspresso", v1: "jobspresso", name: "Jobspresso" },
+  { id: "remotive", v1: "remotive", name: "Remotive" },
+  { id: "jobicy", v1: "jobicy", name: "Jobicy" },
+  { id: "findwork", v1: "find_work", name: "FindWork" },
+  { id: "workingnomads", v1: "working_nomads", name: "Working Nomads" },
+  { id: "vuejobs", v1: "vue_jobs", name: "VueJobs" },
+  { id: "cryptojobs", v1: "crypto_jobs", name: "CryptoJobs" },
+  { id: "devitjobs", v1: "dev_it_jobs", name: "DevITJobs" },
+  { id: "golangprojects", v1: "golang_projects", name: "Golang Projects" },
+  { id: "pythonorg", v1: "python_org", name: "Python.org" },
+  { id: "remoteco", v1: "remote_co", name: "Remote.co" },
+  // `ats`: board de empresa; solo busca en las empresas de su watchlist
+  { id: "greenhouse", v1: "greenhouse", name: "Greenhouse", ats: true },
+  { id: "lever", v1: "lever", name: "Lever", ats: true },
+  { id: "ashby", v1: "ashby", name: "Ashby", ats: true },
+  { id: "workable", v1: "workable", name: "Workable", ats: true },
 ];
 
+export function sourceKeyToV1(id) {
+  return SOURCES.find(s => s.id === id)?.v1 || "remoteok";
+}
+
 // --- RENDERIZADO DE FUENTES (Grid Layout) ---
 export function renderSources() {
   const host = $("sources");
@@ -36,21 +47,67 @@ export function renderSources() {
     label.innerHTML = `
       <input type="checkbox" id="src_${s.id}" checked />
       <div><div class="source-title">${escapeHtml(s.name)}</div></div>
+      <span class="dot source-health" id="health_${s.id}" title="Sin historial"></span>
     `;
     host.appendChild(label);
   }
 }
 
+// --- SALUD DE FUENTES (circuit breaker) ---
+function healthTitle(h) {
+  const pct = Math.round((h.success_rate || 0) * 100);
+  const lines = [`Éxito: ${pct}% · ${Math.round(h.avg_latency_ms || 0)} ms · ${(h.avg_jobs || 0).toFixed(1)} ofertas/run`];
+  if (h.state === "open" && h.open_until) lines.unshift(`En pausa hasta ${new Date(h.open_until).toLocaleTimeString()}`);
+  if (h.state === "half_open") lines.unshift("Pausa terminada: se reintenta en la próxima búsqueda");
+  if (h.consecutive_empty > 1) lines.push(`Sin ofertas en las últimas ${h.consecutive_empty} ejecuciones`);
+  if (h.consecutive_failures > 0 && h.last_error) lines.push(`Último error: ${h.last_error}`);
+  return lines.join("\n");
+}
+
+export function renderSourceHealth(items) {
+  const bySource = new Map((items || []).map(h => [h.source, h]));
+  for (const s of SOURCES) {
+    const dot = $(`health_${s.id}`);
+    if (!dot) continue;
+    const h = bySource.get(s.v1);
+    if (!h) { dot.className = "dot source-health"; dot.title = "Sin historial"; continue; }
+    const kind = h.state === "open" ? "bad" : (h.state === "half_open" || h.consecutive_failures > 0) ? "warn" : "ok";
+    dot.className = `dot source-health dot--${kind}`;
+    dot.title = healthTitle(h);
+  }
+}
+
+export async function refreshSourceHealth() {
+  try { renderSourceHealth(await getSourcesHealth()); }
+  catch (e) { console.warn("Salud de fuentes no disponible:", e); }
+}
+
 export function buildSourceConfigs() {
   const sourceConfigs = {};
   const globalDelay = Number($("globalDelay")?.value || 1200);
+  const maxResults = Number($("globalMaxResults")?.value || 15);
+  const maxPages = Number($("globalMaxPages")?.value || 2);
+  const maxRetries = Number($("globalMaxRetries")?.value ?? 2);
+  const cacheMaxAge = Number($("globalCacheMaxAge")?.value ?? 900);
+  const fetchDetails = Boolean($("globalFetchDetails")?.checked);
   for (const s of SOURCES) {
     const el = $(`src_${s.id}`);
+    const companies = s.ats
+      ? String($(`watch_${s.id}`)?.value || "").split(/[,\n]/).map(x => x.trim()).filter(Boolean)
+      : [];
     sourceConfigs[s.id] = {
       enabled: el ? el.checked : true,
       delay_ms: globalDelay,
       user_agent: "Mozilla/5.0",
-      use_proxy: false
+      use_proxy: false,
+      max_results: maxResults,
+      max_pages: maxPages,
+      ignore_robots: false,
+      max_retries: maxRetries,
+      retry_backoff_ms: 500,
+      cache_max_age_secs: cacheMaxAge,
+      fetch_details: fetchDetails,
+      companies
     };
   }
   return sourceConfigs;
@@ -199,15 +256,23 @@ export function upsertJob(job, mode = "analyzed") {
   // Formateo inteligente de salario
   let salary = "No especificado";
   if (job.salary_normalized && job.salary_normalized > 0) {
-      salary = `$${(job.salary_normalized/1000).toFixed(0)}k / año`;
+      const cur = escapeHtml($("targetCurrency")?.value || "USD");
+      salary = `${cur} ${(job.salary_normalized/1000).toFixed(0)}k / año`;
   } else if (job.salary_range) {
-      salary = `${job.salary_range.currency} ${job.salary_range.min}-${job.salary_range.max}`;
+      // Viene de la fuente: se escapa entero antes de ir al innerHTML
+      const { currency, min, max } = job.salary_range;
+      salary = escapeHtml(`${currency} ${min}-${max}`);
   }
 
+  const posted = job.posted_date ? new Date(job.posted_date).toLocaleDateString() : null;
+  const expires = job.valid_through ? new Date(job.valid_through).toLocaleDateString() : null;
+
   const reasons = (job.match_reasons || []).slice(0, 4);
   const redFlags = (job.red_flags || []);
   const matching = (job.skills_analysis?.matching || []);
   const missing = (job.skills_analysis?.missing || []);
+  // Otras fuentes donde se encontró la misma oferta (dedup backend)
+  const otherSources = (job.sources || []).filter(s => s.url && s.url !== url);
 
   // Helpers HTML
   const renderList = (items, isFlag) => 
@@ -240,6 +305,8 @@ export function upsertJob(job, mode = "analyzed") {
       <div class="meta-item" title="Salario">💰 <span>${salary}</span></div>
       <div class="meta-item" title="Tipo">💼 <span>${type}</span></div>
       <div class="meta-item" title="Experiencia">🎓 <span>${level}</span></div>
+      ${posted ? `<div class="meta-item" title="Publicada">🗓️ <span>${escapeHtml(posted)}</span></div>` : ''}
+      ${expires ? `<div class="meta-item" title="Candidaturas hasta">⏳ <span>${escapeHtml(expires)}</span></div>` : ''}
     </div>
 
     <div class="job__body">
@@ -279,6 +346,11 @@ export function upsertJob(job, mode = "analyzed") {
       <a href="${escapeAttr(url)}" target="_blank" rel="noopener" class="btn-link">
         Ver Oferta Original ↗
       </a>
+      ${otherSources.length ? `
+      <span class="small muted">También en: ${otherSources.map(s => {
+        const name = typeof s.source === "string" ? s.source : Object.values(s.source || {})[0];
+        return `<a href="${escapeAttr(s.url)}" target="_blank" rel="noopener">${escapeHtml(name || "?")}</a>`;
+      }).join(", ")}</span>` : ''}
     </div>
   `;
 
@@ -1,4 +1,4 @@
-import { addLog, setWsStatus, upsertJob, setRunStatus, setLastEvent } from "./ui.js";
+import { addLog, setWsStatus, upsertJob, setRunStatus, setLastEvent, refreshSourceHealth } from "./ui.js";
 
 function wsUrl() {
   const proto = (location.protocol === "https:") ? "wss" : "ws";
@@ -41,7 +41,7 @@ export function connectWs() {
         const p = String(msg.payload || "");
         addLog("info", `Estado: ${p}`);
         if (p.includes("started")) setRunStatus("running");
-        if (p.includes("done") || p.includes("error")) setRunStatus("idle");
+        if (p.includes("done") || p.includes("error")) { setRunStatus("idle"); refreshSourceHealth(); }
         
       } else {
         // Mensajes desconocidos